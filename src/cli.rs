@@ -43,6 +43,9 @@ pub struct Args {
     /// Max depth(NUMBER) of folder, default is 4.
     #[arg(short, long)]
     pub max_depth: Option<usize>,
+    /// Start as a mini player of one to three lines, e.g. for a tmux split.
+    #[arg(long)]
+    pub mini: bool,
 }
 
 #[derive(Subcommand, Debug)]
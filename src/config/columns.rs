@@ -0,0 +1,325 @@
+//! The columns of the playlist.
+//!
+//! A column shows a format string in which `{field}` stands for a tag of the
+//! track. `{field:N}` pads the value to N characters and `{field:0N}` pads
+//! numbers with zeros, `{{` and `}}` are literal braces. The fields are
+//! `track`, `title`, `artist`, `album`, `genre`, `duration`, `file`, `name`,
+//! `rating`, which is stars and a heart for favourites, and `downloaded`,
+//! which is `[D] ` for episodes saved to disk. Widths are
+//! percentages of the playlist, the space between columns included, so they
+//! should add up to a bit less than 100. A column with a `sort` can order
+//! the playlist by it.
+//!
+//! ```toml
+//! [[playlist_columns.music]]
+//! header = "Album"
+//! format = "{album} ({track:02})"
+//! width = 30
+//! sort = "album"
+//! ```
+use super::theme::ThemeModifier;
+use super::ColorTermusic;
+use crate::track::Track;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::Write;
+use tuirealm::props::{Alignment, TextModifiers};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl From<ColumnAlign> for Alignment {
+    fn from(align: ColumnAlign) -> Self {
+        match align {
+            ColumnAlign::Left => Self::Left,
+            ColumnAlign::Center => Self::Center,
+            ColumnAlign::Right => Self::Right,
+        }
+    }
+}
+
+/// What a column orders the playlist by. Ties fall back to the tags that
+/// usually come next, so sorting by album keeps the tracks in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    Track,
+    Title,
+    Artist,
+    Album,
+    Genre,
+    Duration,
+    File,
+    Rating,
+}
+
+impl SortKey {
+    pub fn compare(self, a: &Track, b: &Track) -> Ordering {
+        let text = |tag: fn(&Track) -> Option<&str>| {
+            move |a: &Track, b: &Track| {
+                let a = tag(a).unwrap_or_default().to_lowercase();
+                let b = tag(b).unwrap_or_default().to_lowercase();
+                a.cmp(&b)
+            }
+        };
+        let track = |a: &Track, b: &Track| a.track_number().cmp(&b.track_number());
+        let album = |a: &Track, b: &Track| text(Track::album)(a, b).then_with(|| track(a, b));
+        match self {
+            Self::Track => track(a, b),
+            Self::Title => text(Track::title)(a, b),
+            Self::Artist => text(Track::artist)(a, b).then_with(|| album(a, b)),
+            Self::Album => album(a, b),
+            Self::Genre => text(Track::genre)(a, b)
+                .then_with(|| text(Track::artist)(a, b))
+                .then_with(|| album(a, b)),
+            Self::Duration => a.duration().cmp(&b.duration()),
+            Self::File => text(Track::file)(a, b),
+            Self::Rating => {
+                let rating = |t: &Track| (t.rating().stars, t.rating().favourite);
+                rating(a).cmp(&rating(b)).then_with(|| album(a, b))
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaylistColumn {
+    pub header: String,
+    pub format: String,
+    /// Percentage of the playlist width
+    pub width: u16,
+    #[serde(default)]
+    pub align: ColumnAlign,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SortKey>,
+    /// Palette color of the text, the playlist foreground if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorTermusic>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<ThemeModifier>,
+}
+
+impl PlaylistColumn {
+    fn new(header: &str, format: &str, width: u16) -> Self {
+        Self {
+            header: header.to_string(),
+            format: format.to_string(),
+            width,
+            align: ColumnAlign::Left,
+            sort: None,
+            color: None,
+            modifiers: Vec::new(),
+        }
+    }
+
+    const fn align(mut self, align: ColumnAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    const fn sort(mut self, sort: SortKey) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    const fn color(mut self, color: ColorTermusic) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    fn bold(mut self) -> Self {
+        self.modifiers.push(ThemeModifier::Bold);
+        self
+    }
+
+    pub fn modifiers(&self) -> TextModifiers {
+        self.modifiers
+            .iter()
+            .fold(TextModifiers::empty(), |modifiers, modifier| {
+                modifiers | TextModifiers::from(*modifier)
+            })
+    }
+
+    /// The format string filled in with the tags of `track`.
+    pub fn render(&self, track: &Track) -> String {
+        let mut out = String::new();
+        let mut chars = self.format.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    out.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    out.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        placeholder.push(c);
+                    }
+                    if let Some(value) = closed.then(|| render_field(&placeholder, track)).flatten()
+                    {
+                        out.push_str(&value);
+                    } else {
+                        // unknown fields are shown as written, so typos are easy to spot
+                        out.push('{');
+                        out.push_str(&placeholder);
+                        if closed {
+                            out.push('}');
+                        }
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+        out
+    }
+}
+
+/// The value of `{placeholder}` for `track`, or None if the field is unknown.
+fn render_field(placeholder: &str, track: &Track) -> Option<String> {
+    let (name, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+    let value = match name.trim() {
+        "track" => track
+            .track_number()
+            .map(|number| number.to_string())
+            .unwrap_or_default(),
+        "title" => track
+            .title()
+            .or_else(|| track.name())
+            .unwrap_or_default()
+            .to_string(),
+        "artist" => track.artist().unwrap_or_default().to_string(),
+        "album" => track.album().unwrap_or_default().to_string(),
+        "genre" => track.genre().unwrap_or_default().to_string(),
+        "duration" => track.duration_formatted(),
+        "file" => track.file().unwrap_or_default().to_string(),
+        "name" => track.name().unwrap_or_default().to_string(),
+        "rating" => track.rating().display(),
+        "downloaded" => {
+            if track.podcast_localfile.is_some() {
+                "[D] ".to_string()
+            } else {
+                String::new()
+            }
+        }
+        _ => return None,
+    };
+    let Ok(width) = spec.parse::<usize>() else {
+        return Some(value);
+    };
+    let mut padded = String::new();
+    if spec.starts_with('0') && !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        write!(padded, "{value:0>width$}").ok();
+    } else {
+        write!(padded, "{value:width$}").ok();
+    }
+    Some(padded)
+}
+
+/// Columns of the playlist for music and for podcast episodes.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaylistColumns {
+    pub music: Vec<PlaylistColumn>,
+    pub podcast: Vec<PlaylistColumn>,
+}
+
+impl Default for PlaylistColumns {
+    fn default() -> Self {
+        Self {
+            music: vec![
+                PlaylistColumn::new("#", "{track:02}", 4)
+                    .align(ColumnAlign::Right)
+                    .sort(SortKey::Track),
+                PlaylistColumn::new("Title", "{title}", 26)
+                    .sort(SortKey::Title)
+                    .bold(),
+                PlaylistColumn::new("Artist", "{artist}", 20)
+                    .sort(SortKey::Artist)
+                    .color(ColorTermusic::LightYellow),
+                PlaylistColumn::new("Album", "{album}", 19).sort(SortKey::Album),
+                PlaylistColumn::new("Rating", "{rating}", 8)
+                    .sort(SortKey::Rating)
+                    .color(ColorTermusic::LightRed),
+                PlaylistColumn::new("Duration", "{duration}", 9)
+                    .align(ColumnAlign::Right)
+                    .sort(SortKey::Duration),
+            ],
+            podcast: vec![
+                PlaylistColumn::new("Episode", "{downloaded}{title}", 80)
+                    .sort(SortKey::Title)
+                    .bold(),
+                PlaylistColumn::new("Duration", "{duration}", 12)
+                    .align(ColumnAlign::Right)
+                    .sort(SortKey::Duration),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::podcast::Episode;
+    use crate::track::Rating;
+    use pretty_assertions::assert_eq;
+
+    fn episode(title: &str, seconds: i64) -> Track {
+        Track::from_episode(&Episode {
+            title: title.to_string(),
+            duration: Some(seconds),
+            ..Episode::default()
+        })
+    }
+
+    #[test]
+    fn test_render_and_sort_columns() {
+        let column = |format: &str| PlaylistColumn::new("", format, 10);
+        let intro = episode("Intro", 75);
+        assert_eq!(
+            column("{title:8}|{artist}").render(&intro),
+            "Intro   |Episode"
+        );
+        assert_eq!(column("{downloaded}{title}").render(&intro), "Intro");
+        assert_eq!(
+            column("{track:02}{{x}} {nope}").render(&intro),
+            "  {x} {nope}"
+        );
+        assert_eq!(column("{title").render(&intro), "{title");
+
+        let mut rated = episode("Outro", 30);
+        rated.set_rating(Rating {
+            stars: 3,
+            favourite: true,
+        });
+        assert_eq!(column("{rating}").render(&intro), "");
+        assert_eq!(
+            column("{rating}").render(&rated),
+            "\u{2665}\u{2605}\u{2605}\u{2605}\u{2606}\u{2606}"
+        );
+        assert_eq!(SortKey::Rating.compare(&intro, &rated), Ordering::Less);
+
+        let long = episode("an interview", 3600);
+        assert_eq!(SortKey::Duration.compare(&intro, &long), Ordering::Less);
+        assert_eq!(SortKey::Title.compare(&intro, &long), Ordering::Greater);
+        assert_eq!(SortKey::Album.compare(&intro, &long), Ordering::Equal);
+
+        let columns = PlaylistColumns::default();
+        let saved = toml::to_string(&columns).unwrap();
+        assert!(toml::from_str::<PlaylistColumns>(&saved).unwrap() == columns);
+    }
+}
@@ -47,6 +47,9 @@ pub struct Keys {
     pub global_config_open: BindingForEvent,
     pub global_save_playlist: BindingForEvent,
     pub global_layout_podcast: BindingForEvent,
+    pub global_layout_cycle: BindingForEvent,
+    pub global_mini_toggle: BindingForEvent,
+    pub global_command_palette: BindingForEvent,
     pub global_xywh_move_left: BindingForEvent,
     pub global_xywh_move_right: BindingForEvent,
     pub global_xywh_move_up: BindingForEvent,
@@ -54,6 +57,13 @@ pub struct Keys {
     pub global_xywh_zoom_in: BindingForEvent,
     pub global_xywh_zoom_out: BindingForEvent,
     pub global_xywh_hide: BindingForEvent,
+    pub global_player_chapter_next: BindingForEvent,
+    pub global_player_chapter_previous: BindingForEvent,
+    pub global_player_section_next: BindingForEvent,
+    pub global_player_section_previous: BindingForEvent,
+    pub global_rating_up: BindingForEvent,
+    pub global_rating_down: BindingForEvent,
+    pub global_favourite_toggle: BindingForEvent,
     pub library_load_dir: BindingForEvent,
     pub library_delete: BindingForEvent,
     pub library_yank: BindingForEvent,
@@ -64,6 +74,7 @@ pub struct Keys {
     pub library_switch_root: BindingForEvent,
     pub library_add_root: BindingForEvent,
     pub library_remove_root: BindingForEvent,
+    pub library_add_queue: BindingForEvent,
     pub playlist_delete: BindingForEvent,
     pub playlist_delete_all: BindingForEvent,
     pub playlist_shuffle: BindingForEvent,
@@ -75,17 +86,26 @@ pub struct Keys {
     pub playlist_swap_up: BindingForEvent,
     pub playlist_cmus_lqueue: BindingForEvent,
     pub playlist_cmus_tqueue: BindingForEvent,
+    pub playlist_sort: BindingForEvent,
+    pub playlist_shuffle_mode: BindingForEvent,
+    pub playlist_tab_next: BindingForEvent,
+    pub playlist_tab_previous: BindingForEvent,
+    pub playlist_radio: BindingForEvent,
     pub database_add_all: BindingForEvent,
+    pub database_add_queue: BindingForEvent,
     pub config_save: BindingForEvent,
     pub podcast_mark_played: BindingForEvent,
     pub podcast_mark_all_played: BindingForEvent,
     pub podcast_episode_download: BindingForEvent,
     pub podcast_episode_delete_file: BindingForEvent,
+    pub podcast_add_queue: BindingForEvent,
     pub podcast_delete_feed: BindingForEvent,
     pub podcast_delete_all_feeds: BindingForEvent,
     pub podcast_search_add_feed: BindingForEvent,
     pub podcast_refresh_feed: BindingForEvent,
     pub podcast_refresh_all_feeds: BindingForEvent,
+    pub podcast_feed_settings: BindingForEvent,
+    pub lyric_search: BindingForEvent,
 }
 
 impl Keys {
@@ -120,6 +140,9 @@ impl Keys {
             .chain(once(self.global_config_open))
             .chain(once(self.global_save_playlist))
             .chain(once(self.global_layout_podcast))
+            .chain(once(self.global_layout_cycle))
+            .chain(once(self.global_mini_toggle))
+            .chain(once(self.global_command_palette))
             .chain(once(self.global_xywh_move_left))
             .chain(once(self.global_xywh_move_right))
             .chain(once(self.global_xywh_move_up))
@@ -127,6 +150,13 @@ impl Keys {
             .chain(once(self.global_xywh_zoom_in))
             .chain(once(self.global_xywh_zoom_out))
             .chain(once(self.global_xywh_hide))
+            .chain(once(self.global_player_chapter_next))
+            .chain(once(self.global_player_chapter_previous))
+            .chain(once(self.global_player_section_next))
+            .chain(once(self.global_player_section_previous))
+            .chain(once(self.global_rating_up))
+            .chain(once(self.global_rating_down))
+            .chain(once(self.global_favourite_toggle))
         // .chain(once(self.config_save))
     }
 
@@ -141,6 +171,7 @@ impl Keys {
             .chain(once(self.library_switch_root))
             .chain(once(self.library_add_root))
             .chain(once(self.library_remove_root))
+            .chain(once(self.library_add_queue))
     }
 
     fn iter_playlist(&self) -> impl Iterator<Item = BindingForEvent> {
@@ -155,6 +186,15 @@ impl Keys {
             .chain(once(self.playlist_swap_up))
             .chain(once(self.playlist_cmus_lqueue))
             .chain(once(self.playlist_cmus_tqueue))
+            .chain(once(self.playlist_sort))
+            .chain(once(self.playlist_shuffle_mode))
+            .chain(once(self.playlist_tab_next))
+            .chain(once(self.playlist_tab_previous))
+            .chain(once(self.playlist_radio))
+    }
+
+    fn iter_database(&self) -> impl Iterator<Item = BindingForEvent> {
+        once(self.database_add_all).chain(once(self.database_add_queue))
     }
 
     fn iter_podcast(&self) -> impl Iterator<Item = BindingForEvent> {
@@ -163,6 +203,7 @@ impl Keys {
             .chain(once(self.podcast_refresh_all_feeds))
             .chain(once(self.podcast_delete_feed))
             .chain(once(self.podcast_delete_all_feeds))
+            .chain(once(self.podcast_feed_settings))
     }
 
     fn iter_episode(&self) -> impl Iterator<Item = BindingForEvent> {
@@ -170,17 +211,20 @@ impl Keys {
             .chain(once(self.podcast_mark_all_played))
             .chain(once(self.podcast_episode_download))
             .chain(once(self.podcast_episode_delete_file))
+            .chain(once(self.podcast_add_queue))
     }
 
     pub fn has_unique_elements(&self) -> bool {
         let mut uniq_global = HashSet::new();
         let mut uniq_library = HashSet::new();
         let mut uniq_playlist = HashSet::new();
+        let mut uniq_database = HashSet::new();
         let mut uniq_podcast = HashSet::new();
         let mut uniq_episode = HashSet::new();
         self.iter_global().all(move |x| uniq_global.insert(x))
             && self.iter_library().all(move |x| uniq_library.insert(x))
             && self.iter_playlist().all(move |x| uniq_playlist.insert(x))
+            && self.iter_database().all(move |x| uniq_database.insert(x))
             && self.iter_podcast().all(move |x| uniq_podcast.insert(x))
             && self.iter_episode().all(move |x| uniq_episode.insert(x))
     }
@@ -506,6 +550,26 @@ impl Default for Keys {
                 code: Key::Char('s'),
                 modifier: KeyModifiers::NONE,
             },
+            playlist_sort: BindingForEvent {
+                code: Key::Char('O'),
+                modifier: KeyModifiers::SHIFT,
+            },
+            playlist_shuffle_mode: BindingForEvent {
+                code: Key::Char('z'),
+                modifier: KeyModifiers::NONE,
+            },
+            playlist_tab_next: BindingForEvent {
+                code: Key::Char('>'),
+                modifier: KeyModifiers::SHIFT,
+            },
+            playlist_tab_previous: BindingForEvent {
+                code: Key::Char('<'),
+                modifier: KeyModifiers::SHIFT,
+            },
+            playlist_radio: BindingForEvent {
+                code: Key::Char('i'),
+                modifier: KeyModifiers::NONE,
+            },
             global_layout_treeview: BindingForEvent {
                 code: Key::Char('1'),
                 modifier: KeyModifiers::NONE,
@@ -518,6 +582,10 @@ impl Default for Keys {
                 code: Key::Char('L'),
                 modifier: KeyModifiers::SHIFT,
             },
+            database_add_queue: BindingForEvent {
+                code: Key::Char('u'),
+                modifier: KeyModifiers::NONE,
+            },
             global_player_toggle_gapless: BindingForEvent {
                 code: Key::Char('g'),
                 modifier: KeyModifiers::CONTROL,
@@ -542,6 +610,10 @@ impl Default for Keys {
                 code: Key::Char('A'),
                 modifier: KeyModifiers::SHIFT,
             },
+            library_add_queue: BindingForEvent {
+                code: Key::Char('u'),
+                modifier: KeyModifiers::NONE,
+            },
             global_save_playlist: BindingForEvent {
                 code: Key::Char('s'),
                 modifier: KeyModifiers::CONTROL,
@@ -550,6 +622,18 @@ impl Default for Keys {
                 code: Key::Char('3'),
                 modifier: KeyModifiers::NONE,
             },
+            global_layout_cycle: BindingForEvent {
+                code: Key::Char('l'),
+                modifier: KeyModifiers::CONTROL,
+            },
+            global_mini_toggle: BindingForEvent {
+                code: Key::Char('t'),
+                modifier: KeyModifiers::CONTROL,
+            },
+            global_command_palette: BindingForEvent {
+                code: Key::Char(':'),
+                modifier: KeyModifiers::NONE,
+            },
             podcast_search_add_feed: BindingForEvent {
                 code: Key::Char('s'),
                 modifier: KeyModifiers::NONE,
@@ -578,6 +662,10 @@ impl Default for Keys {
                 code: Key::Char('x'),
                 modifier: KeyModifiers::NONE,
             },
+            podcast_add_queue: BindingForEvent {
+                code: Key::Char('u'),
+                modifier: KeyModifiers::NONE,
+            },
             podcast_delete_feed: BindingForEvent {
                 code: Key::Char('d'),
                 modifier: KeyModifiers::NONE,
@@ -586,6 +674,14 @@ impl Default for Keys {
                 code: Key::Char('D'),
                 modifier: KeyModifiers::SHIFT,
             },
+            podcast_feed_settings: BindingForEvent {
+                code: Key::Char('e'),
+                modifier: KeyModifiers::NONE,
+            },
+            lyric_search: BindingForEvent {
+                code: Key::Char('/'),
+                modifier: KeyModifiers::NONE,
+            },
             global_xywh_move_left: BindingForEvent {
                 code: Key::Left,
                 modifier: CONTROL_SHIFT,
@@ -614,6 +710,47 @@ impl Default for Keys {
                 code: Key::End,
                 modifier: CONTROL_SHIFT,
             },
+            global_player_chapter_next: BindingForEvent {
+                code: Key::Char(']'),
+                modifier: KeyModifiers::NONE,
+            },
+            global_player_chapter_previous: BindingForEvent {
+                code: Key::Char('['),
+                modifier: KeyModifiers::NONE,
+            },
+            global_player_section_next: BindingForEvent {
+                code: Key::Char('}'),
+                modifier: KeyModifiers::SHIFT,
+            },
+            global_player_section_previous: BindingForEvent {
+                code: Key::Char('{'),
+                modifier: KeyModifiers::SHIFT,
+            },
+            global_rating_up: BindingForEvent {
+                code: Key::Char('.'),
+                modifier: KeyModifiers::NONE,
+            },
+            global_rating_down: BindingForEvent {
+                code: Key::Char(','),
+                modifier: KeyModifiers::NONE,
+            },
+            global_favourite_toggle: BindingForEvent {
+                code: Key::Char('*'),
+                modifier: KeyModifiers::SHIFT,
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_keys_unique() {
+        assert!(Keys::default().has_unique_elements());
+        let mut keys = Keys::default();
+        keys.database_add_queue = keys.database_add_all;
+        assert!(!keys.has_unique_elements());
+    }
+}
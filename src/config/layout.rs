@@ -0,0 +1,350 @@
+//! Declarative layouts for the main views.
+//!
+//! A layout is a tree of splits and panes. Every node has a size, written as
+//! `"20"` (rows or columns), `"20+"` (at least), `"30%"` or `"1/3"`. The
+//! layout covers the whole terminal except the footer line.
+//!
+//! ```toml
+//! [layouts.compact]
+//! split = "vertical"
+//! children = [
+//!     { pane = "main", size = "40%" },
+//!     { pane = "playlist", size = "2+" },
+//!     { pane = "progress", size = "3" },
+//! ]
+//! ```
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tuirealm::tui::layout::{Constraint, Direction, Rect};
+
+/// The components a layout can place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pane {
+    /// The pane the current view is about: library, database or podcast.
+    Main,
+    Library,
+    Database,
+    Podcast,
+    Episode,
+    Playlist,
+    Lyric,
+    Progress,
+    /// Space kept free for the album art, which is then drawn there instead
+    /// of at `album_photo_xywh`.
+    AlbumArt,
+    /// Frequency bars of what is playing.
+    Spectrum,
+    /// The waveform of what is playing.
+    Oscilloscope,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitDirection {
+    Horizontal,
+    Vertical,
+}
+
+impl From<SplitDirection> for Direction {
+    fn from(direction: SplitDirection) -> Self {
+        match direction {
+            SplitDirection::Horizontal => Self::Horizontal,
+            SplitDirection::Vertical => Self::Vertical,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PaneSize {
+    Length(u16),
+    Min(u16),
+    Percentage(u16),
+    Ratio(u32, u32),
+}
+
+impl Default for PaneSize {
+    fn default() -> Self {
+        Self::Min(1)
+    }
+}
+
+impl TryFrom<String> for PaneSize {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        let s = s.trim();
+        let size = if let Some(min) = s.strip_suffix('+') {
+            Self::Min(min.trim().parse()?)
+        } else if let Some(percentage) = s.strip_suffix('%') {
+            let percentage = percentage.trim().parse()?;
+            if percentage > 100 {
+                bail!("percentage {percentage} is over 100");
+            }
+            Self::Percentage(percentage)
+        } else if let Some((num, den)) = s.split_once('/') {
+            let (num, den) = (num.trim().parse()?, den.trim().parse()?);
+            if den == 0 || num > den {
+                bail!("ratio {s} should be between 0 and 1");
+            }
+            Self::Ratio(num, den)
+        } else {
+            Self::Length(s.parse().map_err(|_| anyhow!("invalid size: {s}"))?)
+        };
+        Ok(size)
+    }
+}
+
+impl From<PaneSize> for String {
+    fn from(size: PaneSize) -> Self {
+        match size {
+            PaneSize::Length(length) => length.to_string(),
+            PaneSize::Min(min) => format!("{min}+"),
+            PaneSize::Percentage(percentage) => format!("{percentage}%"),
+            PaneSize::Ratio(num, den) => format!("{num}/{den}"),
+        }
+    }
+}
+
+impl From<PaneSize> for Constraint {
+    fn from(size: PaneSize) -> Self {
+        match size {
+            PaneSize::Length(length) => Self::Length(length),
+            PaneSize::Min(min) => Self::Min(min),
+            PaneSize::Percentage(percentage) => Self::Percentage(percentage),
+            PaneSize::Ratio(num, den) => Self::Ratio(num, den),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LayoutNode {
+    Pane {
+        pane: Pane,
+        #[serde(default)]
+        size: PaneSize,
+    },
+    Split {
+        split: SplitDirection,
+        #[serde(default)]
+        size: PaneSize,
+        children: Vec<LayoutNode>,
+    },
+}
+
+impl LayoutNode {
+    const fn pane(pane: Pane, size: PaneSize) -> Self {
+        Self::Pane { pane, size }
+    }
+
+    const fn split(split: SplitDirection, size: PaneSize, children: Vec<Self>) -> Self {
+        Self::Split {
+            split,
+            size,
+            children,
+        }
+    }
+
+    const fn size(&self) -> PaneSize {
+        match self {
+            Self::Pane { size, .. } | Self::Split { size, .. } => *size,
+        }
+    }
+
+    pub fn has_pane(&self, pane: Pane) -> bool {
+        match self {
+            Self::Pane { pane: p, .. } => *p == pane,
+            Self::Split { children, .. } => children.iter().any(|child| child.has_pane(pane)),
+        }
+    }
+
+    /// Splits `area` and returns where each pane goes.
+    pub fn areas(&self, area: Rect) -> Vec<(Pane, Rect)> {
+        let mut areas = Vec::new();
+        self.collect_areas(area, &mut areas);
+        areas
+    }
+
+    fn collect_areas(&self, area: Rect, areas: &mut Vec<(Pane, Rect)>) {
+        match self {
+            Self::Pane { pane, .. } => areas.push((*pane, area)),
+            Self::Split {
+                split, children, ..
+            } => {
+                let constraints: Vec<Constraint> =
+                    children.iter().map(|c| c.size().into()).collect();
+                let chunks = tuirealm::tui::layout::Layout::default()
+                    .direction((*split).into())
+                    .margin(0)
+                    .constraints(constraints)
+                    .split(area);
+                for (child, chunk) in children.iter().zip(chunks) {
+                    child.collect_areas(chunk, areas);
+                }
+            }
+        }
+    }
+}
+
+/// The built-in layouts. `default` and `podcast` are the classic layouts of
+/// the music and podcast views.
+#[allow(clippy::too_many_lines)]
+pub fn default_layouts() -> BTreeMap<String, LayoutNode> {
+    use LayoutNode as N;
+    use Pane as P;
+    use PaneSize::{Length, Min, Percentage, Ratio};
+    use SplitDirection::{Horizontal as H, Vertical as V};
+
+    let mut layouts = BTreeMap::new();
+    layouts.insert(
+        "default".to_string(),
+        N::split(
+            H,
+            Min(1),
+            vec![
+                N::pane(P::Main, Ratio(1, 3)),
+                N::split(
+                    V,
+                    Ratio(2, 3),
+                    vec![
+                        N::pane(P::Playlist, Min(2)),
+                        N::pane(P::Progress, Length(3)),
+                        N::pane(P::Lyric, Length(4)),
+                    ],
+                ),
+            ],
+        ),
+    );
+    layouts.insert(
+        "podcast".to_string(),
+        N::split(
+            V,
+            Min(1),
+            vec![
+                N::split(
+                    H,
+                    Min(2),
+                    vec![
+                        N::pane(P::Main, Ratio(1, 3)),
+                        N::split(
+                            V,
+                            Ratio(2, 3),
+                            vec![
+                                N::pane(P::Playlist, Ratio(1, 2)),
+                                N::pane(P::Lyric, Ratio(1, 2)),
+                            ],
+                        ),
+                    ],
+                ),
+                N::pane(P::Progress, Length(3)),
+            ],
+        ),
+    );
+    layouts.insert(
+        "compact".to_string(),
+        N::split(
+            V,
+            Min(1),
+            vec![
+                N::pane(P::Main, Percentage(40)),
+                N::pane(P::Playlist, Min(2)),
+                N::pane(P::Progress, Length(3)),
+            ],
+        ),
+    );
+    layouts.insert(
+        "visualizer".to_string(),
+        N::split(
+            H,
+            Min(1),
+            vec![
+                N::pane(P::Main, Ratio(1, 3)),
+                N::split(
+                    V,
+                    Ratio(2, 3),
+                    vec![
+                        N::pane(P::Playlist, Min(2)),
+                        N::pane(P::Spectrum, Length(10)),
+                        N::pane(P::Progress, Length(3)),
+                    ],
+                ),
+            ],
+        ),
+    );
+    layouts.insert(
+        "wide".to_string(),
+        N::split(
+            H,
+            Min(1),
+            vec![
+                N::pane(P::Main, Ratio(1, 4)),
+                N::pane(P::Playlist, Ratio(2, 5)),
+                N::split(
+                    V,
+                    Min(2),
+                    vec![
+                        N::pane(P::AlbumArt, Percentage(50)),
+                        N::pane(P::Progress, Length(3)),
+                        N::pane(P::Lyric, Min(4)),
+                    ],
+                ),
+            ],
+        ),
+    );
+    layouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_layout_from_toml() {
+        let toml = r#"
+            split = "vertical"
+            children = [
+                { pane = "main", size = "40%" },
+                { split = "horizontal", children = [
+                    { pane = "album_art", size = "20" },
+                    { pane = "playlist", size = "2+" },
+                ] },
+                { pane = "progress", size = "3" },
+            ]
+        "#;
+        let layout: LayoutNode = toml::from_str(toml).unwrap();
+        let areas = layout.areas(Rect::new(0, 0, 90, 23));
+        let panes: Vec<Pane> = areas.iter().map(|(pane, _)| *pane).collect();
+        assert_eq!(
+            panes,
+            vec![Pane::Main, Pane::AlbumArt, Pane::Playlist, Pane::Progress]
+        );
+        assert_eq!(areas[0].1, Rect::new(0, 0, 90, 9));
+        assert_eq!((areas[1].1.x, areas[1].1.width), (0, 20));
+        assert_eq!((areas[2].1.x, areas[2].1.width), (20, 70));
+        assert_eq!(areas[3].1.bottom(), 23);
+
+        let bad = r#"
+            pane = "playlist"
+            size = "3/2"
+        "#;
+        assert!(toml::from_str::<LayoutNode>(bad).is_err());
+    }
+
+    #[test]
+    fn test_default_layouts_round_trip() {
+        #[derive(Serialize, Deserialize)]
+        struct Layouts {
+            layouts: BTreeMap<String, LayoutNode>,
+        }
+        let layouts = Layouts {
+            layouts: default_layouts(),
+        };
+        let toml = toml::to_string(&layouts).unwrap();
+        let parsed: Layouts = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.layouts, default_layouts());
+    }
+}
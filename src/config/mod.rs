@@ -21,23 +21,29 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+mod columns;
 mod key;
+mod layout;
 mod theme;
 
-use crate::player::Loop;
+use crate::player::{Loop, Shuffle, ShuffleHint};
+use crate::songtag::ServiceProvider;
 use crate::ui::components::Xywh;
 use crate::utils::get_app_config_path;
 use anyhow::Result;
+pub use columns::{PlaylistColumn, PlaylistColumns};
 use figment::{
     providers::{Format, Serialized, Toml},
     Figment,
 };
 pub use key::{BindingForEvent, Keys, ALT_SHIFT, CONTROL_ALT, CONTROL_ALT_SHIFT, CONTROL_SHIFT};
+pub use layout::{default_layouts, LayoutNode, Pane};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
-pub use theme::{load_alacritty, ColorTermusic, StyleColorSymbol};
+pub use theme::{load_theme, theme_modified, ColorTermusic, StyleColorSymbol};
 
 // pub const MUSIC_DIR: [&str; 2] = ["~/Music/mp3", "~/Music"];
 // pub const PODCAST_DIR: &str = "~/.cache/termusic/podcast";
@@ -85,6 +91,19 @@ impl std::fmt::Display for SeekStep {
     }
 }
 
+/// How album art is drawn. `Auto` picks the first the terminal supports of
+/// Kitty, iTerm, Sixel and, depending on the `cover` feature, ueberzug or
+/// half blocks.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlbumArtProtocol {
+    Auto,
+    Kitty,
+    ITerm,
+    Sixel,
+    HalfBlocks,
+    Ueberzug,
+}
+
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LastPosition {
     Yes,
@@ -115,10 +134,33 @@ pub struct Settings {
     pub disable_discord_rpc_from_cli: bool,
     #[serde(skip)]
     pub max_depth_cli: usize,
+    #[serde(skip)]
+    pub mini_from_cli: bool,
     pub loop_mode: Loop,
+    pub shuffle: Shuffle,
+    pub shuffle_hint: ShuffleHint,
     pub volume: i32,
     pub speed: i32,
     pub add_playlist_front: bool,
+    /// Keeps the playlist going with tracks like the ones before
+    pub radio: bool,
+    /// How many tracks radio keeps ahead of the current one
+    pub radio_upcoming: usize,
+    /// Radio leaves out tracks played in the last so many hours
+    pub radio_exclude_hours: u64,
+    /// Also writes ratings into the tags of the files (POPM, or `RATING`
+    /// and `FMPS_RATING`), not just into the library
+    pub rating_write_tags: bool,
+    /// Where the tag editor searches lyrics, in this order
+    pub lyric_providers: Vec<ServiceProvider>,
+    /// Folder with `.lrc` and `.txt` lyrics named like the music files, for
+    /// lyrics not kept next to them
+    pub lyric_dir: String,
+    /// Shows all lyric lines scrolling along, not just the current one
+    pub lyric_scroll: bool,
+    /// Invidious instances to search youtube with, tried in this order before
+    /// the public ones
+    pub invidious_instances: Vec<String>,
     pub gapless: bool,
     pub podcast_simultanious_download: usize,
     pub podcast_max_retries: usize,
@@ -126,13 +168,21 @@ pub struct Settings {
     pub seek_step: SeekStep,
     pub remember_last_played_position: LastPosition,
     pub enable_exit_confirmation: bool,
+    pub enable_mouse: bool,
     pub playlist_display_symbol: bool,
     pub playlist_select_random_track_quantity: u32,
     pub playlist_select_random_album_quantity: u32,
     pub theme_selected: String,
+    /// Names of the layouts used by the music library, database and podcast views
+    pub layout_treeview: String,
+    pub layout_database: String,
+    pub layout_podcast: String,
+    pub album_art_protocol: AlbumArtProtocol,
     pub album_photo_xywh: Xywh,
     pub style_color_symbol: StyleColorSymbol,
     pub keys: Keys,
+    pub layouts: BTreeMap<String, LayoutNode>,
+    pub playlist_columns: PlaylistColumns,
 }
 
 impl Default for Settings {
@@ -145,22 +195,40 @@ impl Default for Settings {
             music_dir: MUSIC_DIR.to_vec(),
             music_dir_from_cli: None,
             loop_mode: Loop::Queue,
+            shuffle: Shuffle::Off,
+            shuffle_hint: ShuffleHint::None,
             volume: 70,
             speed: 10,
             add_playlist_front: false,
+            radio: false,
+            radio_upcoming: 5,
+            radio_exclude_hours: 8,
+            rating_write_tags: false,
+            lyric_providers: ServiceProvider::ALL.to_vec(),
+            lyric_dir: String::new(),
+            lyric_scroll: true,
+            invidious_instances: Vec::new(),
             gapless: true,
             remember_last_played_position: LastPosition::Auto,
             enable_exit_confirmation: true,
+            enable_mouse: true,
             playlist_display_symbol: true,
             keys: Keys::default(),
             theme_selected: "default".to_string(),
+            layout_treeview: "default".to_string(),
+            layout_database: "default".to_string(),
+            layout_podcast: "podcast".to_string(),
+            layouts: default_layouts(),
+            playlist_columns: PlaylistColumns::default(),
             style_color_symbol: StyleColorSymbol::default(),
+            album_art_protocol: AlbumArtProtocol::Auto,
             album_photo_xywh: Xywh::default(),
             playlist_select_random_track_quantity: 20,
             playlist_select_random_album_quantity: 5,
             disable_album_art_from_cli: false,
             disable_discord_rpc_from_cli: false,
             max_depth_cli: 4,
+            mini_from_cli: false,
             podcast_simultanious_download: 3,
             podcast_dir: PODCAST_DIR.to_string(),
             podcast_max_retries: 3,
@@ -5,13 +5,18 @@ use crate::utils::{get_app_config_path, get_pin_yin, parse_hex_color};
 use anyhow::Result;
 use include_dir::{include_dir, Dir, DirEntry};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::read_to_string;
-use std::path::PathBuf;
-use tuirealm::props::{Color, PropPayload, PropValue, TableBuilder, TextSpan};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use tuirealm::props::{Color, PropPayload, PropValue, TableBuilder, TextModifiers, TextSpan};
+use tuirealm::tui::style::Style;
 use tuirealm::{AttrValue, Attribute};
 use yaml_rust::YamlLoader;
 
 static THEME_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/themes");
+/// How often the selected theme file is checked for edits.
+const THEME_WATCH_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Copy, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub enum ColorTermusic {
@@ -135,7 +140,14 @@ pub struct StyleColorSymbol {
     pub lyric_foreground: ColorTermusic,
     pub lyric_background: ColorTermusic,
     pub lyric_border: ColorTermusic,
+    pub visualizer_foreground: ColorTermusic,
+    pub visualizer_background: ColorTermusic,
+    pub visualizer_border: ColorTermusic,
     pub alacritty_theme: Alacritty,
+    /// Styles from a native theme: text modifiers, unfocused borders, and
+    /// the colors of the parts not listed above
+    #[serde(default)]
+    pub styles: ThemeStyles,
 }
 
 impl Default for StyleColorSymbol {
@@ -157,7 +169,11 @@ impl Default for StyleColorSymbol {
             lyric_foreground: ColorTermusic::Foreground,
             lyric_background: ColorTermusic::Reset,
             lyric_border: ColorTermusic::Blue,
+            visualizer_foreground: ColorTermusic::Cyan,
+            visualizer_background: ColorTermusic::Reset,
+            visualizer_border: ColorTermusic::Blue,
             alacritty_theme: Alacritty::default(),
+            styles: ThemeStyles::default(),
         }
     }
 }
@@ -206,6 +222,240 @@ impl StyleColorSymbol {
     pub fn lyric_border(&self) -> Option<Color> {
         self.lyric_border.color(&self.alacritty_theme)
     }
+    pub fn visualizer_foreground(&self) -> Option<Color> {
+        self.visualizer_foreground.color(&self.alacritty_theme)
+    }
+    pub fn visualizer_background(&self) -> Option<Color> {
+        self.visualizer_background.color(&self.alacritty_theme)
+    }
+    pub fn visualizer_border(&self) -> Option<Color> {
+        self.visualizer_border.color(&self.alacritty_theme)
+    }
+
+    /// `color` if the theme sets it, otherwise `fallback`.
+    fn styled(&self, color: Option<ColorTermusic>, fallback: Option<Color>) -> Option<Color> {
+        color.map_or(fallback, |color| color.color(&self.alacritty_theme))
+    }
+    pub fn database_foreground(&self) -> Option<Color> {
+        self.styled(self.styles.database.foreground, self.library_foreground())
+    }
+    pub fn database_background(&self) -> Option<Color> {
+        self.styled(self.styles.database.background, self.library_background())
+    }
+    pub fn database_border(&self) -> Option<Color> {
+        self.styled(self.styles.database.border, self.library_border())
+    }
+    pub fn database_highlight(&self) -> Option<Color> {
+        self.styled(self.styles.database.highlight, self.library_highlight())
+    }
+    pub fn podcast_foreground(&self) -> Option<Color> {
+        self.styled(self.styles.podcast.foreground, self.library_foreground())
+    }
+    pub fn podcast_background(&self) -> Option<Color> {
+        self.styled(self.styles.podcast.background, self.library_background())
+    }
+    pub fn podcast_border(&self) -> Option<Color> {
+        self.styled(self.styles.podcast.border, self.library_border())
+    }
+    pub fn podcast_highlight(&self) -> Option<Color> {
+        self.styled(self.styles.podcast.highlight, self.library_highlight())
+    }
+    pub fn popup_foreground(&self) -> Option<Color> {
+        self.styled(self.styles.popup.foreground, self.library_foreground())
+    }
+    pub fn popup_background(&self) -> Option<Color> {
+        self.styled(self.styles.popup.background, self.library_background())
+    }
+    pub fn popup_border(&self) -> Option<Color> {
+        self.styled(self.styles.popup.border, self.library_border())
+    }
+    pub fn popup_highlight(&self) -> Option<Color> {
+        self.styled(self.styles.popup.highlight, self.library_highlight())
+    }
+    pub fn tag_editor_foreground(&self) -> Option<Color> {
+        self.styled(self.styles.tag_editor.foreground, self.library_foreground())
+    }
+    pub fn tag_editor_background(&self) -> Option<Color> {
+        self.styled(self.styles.tag_editor.background, self.library_background())
+    }
+    pub fn tag_editor_border(&self) -> Option<Color> {
+        self.styled(self.styles.tag_editor.border, self.library_border())
+    }
+    pub fn tag_editor_highlight(&self) -> Option<Color> {
+        self.styled(self.styles.tag_editor.highlight, self.library_highlight())
+    }
+    pub fn footer_foreground(&self) -> Option<Color> {
+        self.styled(self.styles.footer.foreground, self.library_foreground())
+    }
+    pub fn footer_background(&self) -> Option<Color> {
+        self.styled(self.styles.footer.background, self.library_background())
+    }
+    pub fn footer_highlight(&self) -> Option<Color> {
+        self.styled(self.styles.footer.highlight, self.library_highlight())
+    }
+
+    /// Border of a component styled by `style` while it doesn't have the focus.
+    pub fn inactive_style(&self, style: &WidgetStyle) -> Style {
+        Style::default().fg(self
+            .styled(style.border_unfocused, None)
+            .unwrap_or(Color::Reset))
+    }
+
+    /// Takes the palette and styles of `theme`. The colors it sets for the
+    /// parts the config editor edits replace the ones set there.
+    pub fn apply_theme(&mut self, theme: Theme) {
+        let Theme {
+            palette,
+            mut styles,
+        } = theme;
+        self.alacritty_theme = palette;
+        for (style, foreground, background, border, highlight) in [
+            (
+                &mut styles.library,
+                &mut self.library_foreground,
+                &mut self.library_background,
+                &mut self.library_border,
+                Some(&mut self.library_highlight),
+            ),
+            (
+                &mut styles.playlist,
+                &mut self.playlist_foreground,
+                &mut self.playlist_background,
+                &mut self.playlist_border,
+                Some(&mut self.playlist_highlight),
+            ),
+            (
+                &mut styles.progress,
+                &mut self.progress_foreground,
+                &mut self.progress_background,
+                &mut self.progress_border,
+                None,
+            ),
+            (
+                &mut styles.lyric,
+                &mut self.lyric_foreground,
+                &mut self.lyric_background,
+                &mut self.lyric_border,
+                None,
+            ),
+            (
+                &mut styles.visualizer,
+                &mut self.visualizer_foreground,
+                &mut self.visualizer_background,
+                &mut self.visualizer_border,
+                None,
+            ),
+        ] {
+            if let Some(color) = style.foreground.take() {
+                *foreground = color;
+            }
+            if let Some(color) = style.background.take() {
+                *background = color;
+            }
+            if let Some(color) = style.border.take() {
+                *border = color;
+            }
+            if let (Some(color), Some(highlight)) = (style.highlight.take(), highlight) {
+                *highlight = color;
+            }
+        }
+        self.styles = styles;
+    }
+}
+
+/// Text modifiers a native theme can set, e.g. `modifiers = ["bold", "italic"]`.
+#[derive(Copy, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeModifier {
+    Bold,
+    Dim,
+    Italic,
+    Underlined,
+    SlowBlink,
+    RapidBlink,
+    Reversed,
+    Hidden,
+    CrossedOut,
+}
+
+impl From<ThemeModifier> for TextModifiers {
+    fn from(modifier: ThemeModifier) -> Self {
+        match modifier {
+            ThemeModifier::Bold => Self::BOLD,
+            ThemeModifier::Dim => Self::DIM,
+            ThemeModifier::Italic => Self::ITALIC,
+            ThemeModifier::Underlined => Self::UNDERLINED,
+            ThemeModifier::SlowBlink => Self::SLOW_BLINK,
+            ThemeModifier::RapidBlink => Self::RAPID_BLINK,
+            ThemeModifier::Reversed => Self::REVERSED,
+            ThemeModifier::Hidden => Self::HIDDEN,
+            ThemeModifier::CrossedOut => Self::CROSSED_OUT,
+        }
+    }
+}
+
+/// How a native theme draws one part of the interface. What it leaves unset
+/// keeps its default.
+#[derive(Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WidgetStyle {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<ColorTermusic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<ColorTermusic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<ColorTermusic>,
+    /// Border while another component has the focus
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_unfocused: Option<ColorTermusic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<ColorTermusic>,
+    /// Modifiers of the selected item of lists, or of the text
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<ThemeModifier>,
+}
+
+impl WidgetStyle {
+    pub fn modifiers(&self) -> TextModifiers {
+        self.modifiers
+            .iter()
+            .fold(TextModifiers::empty(), |modifiers, modifier| {
+                modifiers | TextModifiers::from(*modifier)
+            })
+    }
+}
+
+/// The sections of a native theme, one per part of the interface.
+#[derive(Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ThemeStyles {
+    pub library: WidgetStyle,
+    pub playlist: WidgetStyle,
+    pub progress: WidgetStyle,
+    pub lyric: WidgetStyle,
+    pub visualizer: WidgetStyle,
+    pub database: WidgetStyle,
+    pub podcast: WidgetStyle,
+    pub popup: WidgetStyle,
+    pub tag_editor: WidgetStyle,
+    pub footer: WidgetStyle,
+}
+
+/// A theme file: an Alacritty palette, plus styles for a native theme.
+pub struct Theme {
+    pub palette: Alacritty,
+    pub styles: ThemeStyles,
+}
+
+/// A native theme file. Colors in the styles name entries of the palette.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct NativeTheme {
+    name: Option<String>,
+    author: Option<String>,
+    palette: BTreeMap<String, String>,
+    #[serde(flatten)]
+    styles: ThemeStyles,
 }
 
 #[derive(Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -264,7 +514,46 @@ impl Default for Alacritty {
         }
     }
 }
+/// When the theme file was last changed, if it exists.
+pub fn theme_modified(style_color_symbol: &StyleColorSymbol) -> Option<SystemTime> {
+    std::fs::metadata(&style_color_symbol.alacritty_theme.path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
 impl Model {
+    /// Applies edits to the theme file while termusic runs.
+    pub fn theme_watch(&mut self) {
+        if self.theme_checked.elapsed() < THEME_WATCH_INTERVAL {
+            return;
+        }
+        self.theme_checked = Instant::now();
+        let modified = theme_modified(&self.config.style_color_symbol);
+        if modified.is_none() || modified == self.theme_modified {
+            return;
+        }
+        self.theme_modified = modified;
+        match load_theme(&self.config.style_color_symbol.alacritty_theme.path) {
+            Ok(theme) => {
+                self.config.style_color_symbol.apply_theme(theme);
+                self.styles_reload();
+            }
+            Err(e) => self.mount_error_popup(format!("theme reload error: {e}")),
+        }
+    }
+
+    /// Remounts the components of the main views with the current styles.
+    pub fn styles_reload(&mut self) {
+        self.library_reload_tree();
+        self.playlist_reload();
+        self.database_reload();
+        self.podcast_reload();
+        self.progress_reload();
+        self.mount_label_help();
+        self.lyric_reload();
+        self.visualizer_reload();
+    }
+
     pub fn theme_select_save() -> Result<()> {
         let mut path = get_app_config_path()?;
         path.push("themes");
@@ -355,6 +644,58 @@ impl Model {
     }
 }
 
+/// Loads a native theme from a `.toml` file, or an Alacritty palette from
+/// anything else.
+pub fn load_theme(path_str: &str) -> Result<Theme> {
+    if matches!(Path::new(path_str).extension(), Some(ext) if ext == "toml") {
+        return load_native(path_str);
+    }
+    Ok(Theme {
+        palette: load_alacritty(path_str)?,
+        styles: ThemeStyles::default(),
+    })
+}
+
+fn load_native(path_str: &str) -> Result<Theme> {
+    let theme: NativeTheme = toml::from_str(&read_to_string(path_str)?)?;
+    let default = Alacritty::default();
+    let color = |key: &str, default: &str| {
+        theme
+            .palette
+            .get(key)
+            .map_or_else(|| default.to_string(), ToString::to_string)
+    };
+    let palette = Alacritty {
+        path: path_str.to_string(),
+        name: theme.name.unwrap_or_else(|| "empty name".to_string()),
+        author: theme.author.unwrap_or_else(|| "empty author".to_string()),
+        background: color("background", &default.background),
+        foreground: color("foreground", &default.foreground),
+        cursor: color("cursor", &default.cursor),
+        text: color("text", &default.text),
+        black: color("black", &default.black),
+        red: color("red", &default.red),
+        green: color("green", &default.green),
+        yellow: color("yellow", &default.yellow),
+        blue: color("blue", &default.blue),
+        magenta: color("magenta", &default.magenta),
+        cyan: color("cyan", &default.cyan),
+        white: color("white", &default.white),
+        light_black: color("bright_black", &default.light_black),
+        light_red: color("bright_red", &default.light_red),
+        light_green: color("bright_green", &default.light_green),
+        light_yellow: color("bright_yellow", &default.light_yellow),
+        light_blue: color("bright_blue", &default.light_blue),
+        light_magenta: color("bright_magenta", &default.light_magenta),
+        light_cyan: color("bright_cyan", &default.light_cyan),
+        light_white: color("bright_white", &default.light_white),
+    };
+    Ok(Theme {
+        palette,
+        styles: theme.styles,
+    })
+}
+
 pub fn load_alacritty(path_str: &str) -> Result<Alacritty> {
     let path = PathBuf::from(path_str);
     let path = path.to_string_lossy().to_string();
@@ -448,3 +789,45 @@ pub fn load_alacritty(path_str: &str) -> Result<Alacritty> {
             .to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_load_native_theme() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/themes/termusic.toml");
+        let theme = load_theme(path).unwrap();
+        assert_eq!(theme.palette.light_black, "#565656");
+        assert_eq!(theme.palette.path, path);
+
+        let mut style_color_symbol = StyleColorSymbol {
+            library_border: ColorTermusic::Red,
+            ..StyleColorSymbol::default()
+        };
+        style_color_symbol.apply_theme(theme);
+        // taken into the fields the config editor edits
+        assert_eq!(
+            style_color_symbol.library_border().unwrap(),
+            Color::Rgb(0x47, 0xa0, 0xf3)
+        );
+        assert!(style_color_symbol.styles.library.border.is_none());
+        assert_eq!(
+            style_color_symbol.styles.library.modifiers(),
+            TextModifiers::BOLD
+        );
+        assert_eq!(
+            style_color_symbol.inactive_style(&style_color_symbol.styles.playlist),
+            Style::default().fg(Color::Rgb(0x56, 0x56, 0x56))
+        );
+        assert_eq!(
+            style_color_symbol.popup_border(),
+            style_color_symbol.library_border()
+        );
+
+        // saved with the config
+        let saved = toml::to_string(&style_color_symbol).unwrap();
+        assert!(toml::from_str::<StyleColorSymbol>(&saved).unwrap() == style_color_symbol);
+    }
+}
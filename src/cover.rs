@@ -0,0 +1,240 @@
+//! Album art, extracted once per album or image URL and cached on disk as a
+//! thumbnail, so that changing tracks doesn't decode or download full size
+//! pictures again.
+use crate::track::{MediaType, Track};
+use anyhow::{anyhow, Result};
+use image::io::Reader as ImageReader;
+use image::DynamicImage;
+use lofty::Picture;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::UNIX_EPOCH;
+
+/// Longest side of the cached thumbnails, enough for Sixel art in a large pane.
+const THUMBNAIL_SIZE: u32 = 800;
+/// The oldest thumbnails go once the cache grows past this many bytes.
+const CACHE_LIMIT: u64 = 64 << 20;
+/// Names of the images that hold the art of the album in its directory, the
+/// most likely first.
+const COVER_NAMES: [&str; 6] = [
+    "cover",
+    "folder",
+    "front",
+    "album",
+    "albumart",
+    "albumartsmall",
+];
+const COVER_EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "png"];
+
+pub enum CoverSource {
+    /// The picture embedded in the file
+    Embedded(Picture),
+    /// An image next to the file
+    File(PathBuf),
+    /// The image of an episode or of its feed
+    Url(String),
+}
+
+/// The art of a track and the key it is cached under.
+pub struct Cover {
+    key: String,
+    source: CoverSource,
+}
+
+impl Cover {
+    /// The art of `track`. Music is keyed by album and the art itself, so the
+    /// tracks of an album share one thumbnail until new art is put in, and
+    /// episodes by image URL. `feed_image` is the image of the feed of an
+    /// episode that has none of its own.
+    pub fn of_track(track: &Track, feed_image: Option<String>) -> Option<Self> {
+        match track.media_type {
+            Some(MediaType::Music) => {
+                let source = if let Some(picture) = track.picture() {
+                    CoverSource::Embedded(picture.clone())
+                } else {
+                    CoverSource::File(PathBuf::from(track.album_photo()?))
+                };
+                let key = match (track.album(), &source) {
+                    (Some(album), _) if !album.is_empty() => {
+                        format!("album\0{}\0{album}", track.artist().unwrap_or_default())
+                    }
+                    (_, CoverSource::File(path)) => format!("file\0{}", path.display()),
+                    _ => format!("file\0{}", track.file()?),
+                };
+                let version = match &source {
+                    CoverSource::Embedded(picture) => format!("{:x}", md5::compute(picture.data())),
+                    CoverSource::File(path) => std::fs::metadata(path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                        .map(|modified| modified.as_secs().to_string())
+                        .unwrap_or_default(),
+                    CoverSource::Url(_) => String::new(),
+                };
+                let key = format!("{key}\0{version}");
+                Some(Self { key, source })
+            }
+            Some(MediaType::Podcast) => {
+                let url = track
+                    .album_photo()
+                    .map(ToString::to_string)
+                    .or(feed_image)
+                    .filter(|url| !url.is_empty())?;
+                Some(Self {
+                    key: format!("url\0{url}"),
+                    source: CoverSource::Url(url),
+                })
+            }
+            None => None,
+        }
+    }
+
+    /// Where the thumbnail is cached.
+    pub fn cache_path(&self) -> PathBuf {
+        let mut path = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+        path.push("termusic");
+        path.push("covers");
+        path.push(format!("{:x}.jpg", md5::compute(&self.key)));
+        path
+    }
+
+    /// Whether loading has to download the image.
+    pub fn is_remote(&self) -> bool {
+        matches!(self.source, CoverSource::Url(_)) && !self.cache_path().exists()
+    }
+
+    /// The cached thumbnail, or the art made into one and cached.
+    pub fn load(&self) -> Result<DynamicImage> {
+        let path = self.cache_path();
+        if let Ok(image) = ImageReader::open(&path).and_then(ImageReader::with_guessed_format) {
+            if let Ok(image) = image.decode() {
+                return Ok(image);
+            }
+        }
+        let image = self.extract()?;
+        let image = if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+            image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        } else {
+            image
+        };
+        // jpeg has no alpha channel
+        let image = DynamicImage::ImageRgb8(image.to_rgb8());
+        // the art still shows if the cache can't be written
+        if Self::save(&image, &path).is_ok() {
+            if let Some(dir) = path.parent() {
+                Self::evict(dir).ok();
+            }
+        }
+        Ok(image)
+    }
+
+    /// Removes the thumbnails written longest ago until the cache fits in
+    /// `CACHE_LIMIT`.
+    fn evict(dir: &Path) -> Result<()> {
+        let mut thumbnails = Vec::new();
+        let mut size = 0;
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                size += metadata.len();
+                thumbnails.push((metadata.modified()?, metadata.len(), entry.path()));
+            }
+        }
+        if size <= CACHE_LIMIT {
+            return Ok(());
+        }
+        thumbnails.sort_unstable();
+        for (_, len, path) in thumbnails {
+            if size <= CACHE_LIMIT {
+                break;
+            }
+            if std::fs::remove_file(path).is_ok() {
+                size -= len;
+            }
+        }
+        Ok(())
+    }
+
+    fn save(image: &DynamicImage, path: &Path) -> Result<()> {
+        static PARTS: AtomicUsize = AtomicUsize::new(0);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // written aside and moved, so a thumbnail is never read half written,
+        // even when the prefetch and the current track write the same one
+        let partial = path.with_extension(format!(
+            "{}-{}.part",
+            std::process::id(),
+            PARTS.fetch_add(1, Ordering::Relaxed)
+        ));
+        image.save_with_format(&partial, image::ImageFormat::Jpeg)?;
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    fn extract(&self) -> Result<DynamicImage> {
+        match &self.source {
+            CoverSource::Embedded(picture) => Ok(image::load_from_memory(picture.data())?),
+            CoverSource::File(path) => Ok(ImageReader::open(path)?.decode()?),
+            CoverSource::Url(url) => {
+                let response = ureq::get(url)
+                    .call()
+                    .map_err(|e| anyhow!("Error in ureq get: {e}"))?;
+                let picture = Picture::from_reader(&mut response.into_reader())
+                    .map_err(|e| anyhow!("Error in picture from_reader: {e}"))?;
+                image::load_from_memory(picture.data())
+                    .map_err(|e| anyhow!("Error in load_from_memory: {e}"))
+            }
+        }
+    }
+}
+
+/// The image in `dir` most likely to be the album art: one named like
+/// `cover.jpg` or `folder.png`, or else the first image by name.
+pub fn find_cover_file(dir: &Path) -> Option<PathBuf> {
+    let mut images: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(std::ffi::OsStr::to_str),
+                Some(ext) if COVER_EXTENSIONS.contains(&ext.to_lowercase().as_str())
+            )
+        })
+        .collect();
+    let rank = |path: &PathBuf| {
+        let stem = path
+            .file_stem()
+            .and_then(std::ffi::OsStr::to_str)
+            .unwrap_or_default()
+            .to_lowercase();
+        COVER_NAMES
+            .iter()
+            .position(|name| *name == stem)
+            .unwrap_or(COVER_NAMES.len())
+    };
+    images.sort_by(|a, b| rank(a).cmp(&rank(b)).then_with(|| a.cmp(b)));
+    images.into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_cover_file() {
+        let dir = std::env::temp_dir().join(format!("termusic-cover-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["01 intro.mp3", "back.jpg", "booklet.png", "notes.txt"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        assert_eq!(find_cover_file(&dir), Some(dir.join("back.jpg")));
+        std::fs::write(dir.join("Folder.PNG"), "").unwrap();
+        assert_eq!(find_cover_file(&dir), Some(dir.join("Folder.PNG")));
+        std::fs::write(dir.join("cover.jpeg"), "").unwrap();
+        assert_eq!(find_cover_file(&dir), Some(dir.join("cover.jpeg")));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
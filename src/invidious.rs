@@ -71,6 +71,38 @@ pub struct YoutubeVideo {
     pub video_id: String,
 }
 
+/// A search result: a video, or a playlist or channel to browse.
+#[derive(Clone, PartialEq, Eq)]
+pub enum YoutubeItem {
+    Video(YoutubeVideo),
+    Playlist {
+        title: String,
+        playlist_id: String,
+        video_count: u64,
+    },
+    Channel {
+        author: String,
+        author_id: String,
+        video_count: u64,
+    },
+}
+
+/// The best audio-only format of a video, to play without downloading it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AudioStream {
+    pub title: String,
+    pub author: String,
+    pub length_seconds: u64,
+    pub url: String,
+}
+
+/// The audio formats the backend can play. Symphonia is built with AAC but
+/// not Opus, so the rusty backend only gets the mp4 ones.
+#[cfg(not(any(feature = "mpv", feature = "gst")))]
+const AUDIO_TYPES: &[&str] = &["audio/mp4"];
+#[cfg(any(feature = "mpv", feature = "gst"))]
+const AUDIO_TYPES: &[&str] = &["audio/"];
+
 impl Default for Instance {
     fn default() -> Self {
         let client = Agent::new();
@@ -87,86 +119,151 @@ impl Default for Instance {
 
 #[allow(unused)]
 impl Instance {
-    pub fn new(query: &str) -> Result<(Self, Vec<YoutubeVideo>)> {
-        let client = AgentBuilder::new().timeout(Duration::from_secs(10)).build();
+    pub fn new(query: &str, preferred: &[String]) -> Result<(Self, Vec<YoutubeItem>)> {
+        Self::connect(Some(query), preferred, |instance| {
+            instance.get_search_query(1)
+        })
+    }
 
-        let mut domain = String::new();
-        let mut domains = vec![];
+    /// Tries the `preferred` instances in order, then the public ones in
+    /// random order, and keeps the first one that answers `request`.
+    pub fn connect<T>(
+        query: Option<&str>,
+        preferred: &[String],
+        request: impl Fn(&Self) -> Result<T>,
+    ) -> Result<(Self, T)> {
+        let client = AgentBuilder::new().timeout(Duration::from_secs(10)).build();
+        let mut instance = Self {
+            domain: None,
+            client,
+            query: query.map(ToString::to_string),
+        };
 
-        // prefor fetch invidious instance from website, but will provide 7 backups
-        if let Ok(domain_list) = Self::get_invidious_instance_list(&client) {
-            domains = domain_list;
-        } else {
-            for item in &INVIDIOUS_INSTANCE_LIST {
-                domains.push((*item).to_string());
+        for domain in preferred {
+            instance.domain = Some(domain.trim_end_matches('/').to_string());
+            if let Ok(result) = request(&instance) {
+                return Ok((instance, result));
             }
         }
 
+        // prefor fetch invidious instance from website, but will provide 7 backups
+        let mut domains =
+            Self::get_invidious_instance_list(&instance.client).unwrap_or_else(|_| {
+                INVIDIOUS_INSTANCE_LIST
+                    .iter()
+                    .map(|item| (*item).to_string())
+                    .collect()
+            });
+        domains.retain(|domain| !preferred.contains(domain));
         domains.shuffle(&mut rand::thread_rng());
 
-        let mut video_result: Vec<YoutubeVideo> = Vec::new();
-        for v in domains {
-            let url = format!("{v}/api/v1/search");
-
-            if let Ok(result) = client
-                .get(&url)
-                .query("q", query)
-                .query("page", "1")
-                .query("type", "video")
-                .query("sort_by", "relevance")
-                .call()
-            {
-                if result.status() == 200 {
-                    if let Ok(text) = result.into_string() {
-                        if let Some(vr) = Self::parse_youtube_options(&text) {
-                            video_result = vr;
-                            domain = v;
-                            break;
-                        }
-                    }
-                }
+        for domain in domains {
+            instance.domain = Some(domain);
+            if let Ok(result) = request(&instance) {
+                return Ok((instance, result));
             }
         }
-        if domain.len() < 2 {
-            bail!("All 7 invidious servers are down? Please check your network connection first.");
-        }
-
-        let domain = Some(domain);
-        Ok((
-            Self {
-                domain,
-                client,
-                query: Some(query.to_string()),
-            },
-            video_result,
-        ))
+        bail!("All invidious servers are down? Please check your network connection first.");
     }
 
     // GetSearchQuery fetches query result from an Invidious instance.
-    pub fn get_search_query(&self, page: u32) -> Result<Vec<YoutubeVideo>> {
-        if self.domain.is_none() {
-            bail!("No server available");
+    pub fn get_search_query(&self, page: u32) -> Result<Vec<YoutubeItem>> {
+        let Some(query) = &self.query else {
+            bail!("No query string found");
+        };
+
+        let value = self.get_json(
+            "/api/v1/search",
+            &[
+                ("q", query),
+                ("page", &page.to_string()),
+                ("type", "all"),
+                ("sort_by", "relevance"),
+            ],
+        )?;
+        let array = value
+            .as_array()
+            .ok_or_else(|| anyhow!("Error during search"))?;
+        Ok(array.iter().filter_map(Self::parse_search_item).collect())
+    }
+
+    /// A page of the videos in a playlist, and the title of the playlist.
+    pub fn get_playlist(
+        &self,
+        playlist_id: &str,
+        page: u32,
+    ) -> Result<(String, Vec<YoutubeVideo>)> {
+        let value = self.get_json(
+            &format!("/api/v1/playlists/{playlist_id}"),
+            &[("page", &page.to_string())],
+        )?;
+        let title = value
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or(playlist_id)
+            .to_string();
+        let videos = value
+            .get("videos")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("No videos found in playlist {playlist_id}"))?;
+        Ok((title, Self::parse_videos(videos)))
+    }
+
+    /// A page of the latest videos of a channel, and the continuation to get
+    /// the page after it with, if there is one.
+    pub fn get_channel_videos(
+        &self,
+        author_id: &str,
+        continuation: Option<&str>,
+    ) -> Result<(Vec<YoutubeVideo>, Option<String>)> {
+        let mut query = Vec::new();
+        if let Some(continuation) = continuation {
+            query.push(("continuation", continuation));
+        }
+        let value = self.get_json(&format!("/api/v1/channels/{author_id}/videos"), &query)?;
+        // older instances answer with just the array of videos
+        if let Some(videos) = value.as_array() {
+            return Ok((Self::parse_videos(videos), None));
         }
-        let url = format!("{}/api/v1/search", self.domain.as_ref().unwrap());
+        let videos = value
+            .get("videos")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("No videos found in channel {author_id}"))?;
+        let continuation = value
+            .get("continuation")
+            .and_then(Value::as_str)
+            .map(ToString::to_string);
+        Ok((Self::parse_videos(videos), continuation))
+    }
 
-        let query = match &self.query {
-            Some(q) => q,
-            None => bail!("No query string found"),
-        };
+    /// The audio-only format of the video with the highest bitrate, played
+    /// through the instance.
+    pub fn get_audio_stream(&self, video_id: &str) -> Result<AudioStream> {
+        let value = self.get_json(
+            &format!("/api/v1/videos/{video_id}"),
+            &[("fields", "title,author,lengthSeconds,adaptiveFormats")],
+        )?;
+        let domain = self.domain.as_deref().unwrap_or_default();
+        Self::parse_audio_stream(&value, domain, video_id, AUDIO_TYPES)
+            .ok_or_else(|| anyhow!("No audio-only format found for {video_id}"))
+    }
 
-        let result = self
-            .client
-            .get(&url)
-            .query("q", query)
-            .query("page", &page.to_string())
-            .call()?;
+    fn get_json(&self, path: &str, query: &[(&str, &str)]) -> Result<Value> {
+        let Some(domain) = &self.domain else {
+            bail!("No server available");
+        };
+        let mut request = self.client.get(&format!("{domain}{path}"));
+        for (param, value) in query {
+            request = request.query(param, value);
+        }
+        let result = request.call()?;
 
         match result.status() {
             200 => match result.into_string() {
-                Ok(text) => Self::parse_youtube_options(&text).ok_or_else(|| anyhow!("None Error")),
-                Err(e) => bail!("Error during search: {}", e),
+                Ok(text) => Ok(serde_json::from_str(&text)?),
+                Err(e) => bail!("Error during request: {}", e),
             },
-            _ => bail!("Error during search"),
+            status => bail!("Error during request: status {status}"),
         }
     }
 
@@ -230,6 +327,87 @@ impl Instance {
         None
     }
 
+    fn parse_videos(array: &[Value]) -> Vec<YoutubeVideo> {
+        array
+            .iter()
+            .filter_map(Self::parse_youtube_item)
+            .map(|(title, video_id, length_seconds)| YoutubeVideo {
+                title,
+                length_seconds,
+                video_id,
+            })
+            .collect()
+    }
+
+    fn parse_search_item(value: &Value) -> Option<YoutubeItem> {
+        let item = match value.get("type")?.as_str()? {
+            "video" => {
+                let (title, video_id, length_seconds) = Self::parse_youtube_item(value)?;
+                YoutubeItem::Video(YoutubeVideo {
+                    title,
+                    length_seconds,
+                    video_id,
+                })
+            }
+            "playlist" => YoutubeItem::Playlist {
+                title: value.get("title")?.as_str()?.to_owned(),
+                playlist_id: value.get("playlistId")?.as_str()?.to_owned(),
+                video_count: value.get("videoCount")?.as_u64()?,
+            },
+            "channel" => YoutubeItem::Channel {
+                author: value.get("author")?.as_str()?.to_owned(),
+                author_id: value.get("authorId")?.as_str()?.to_owned(),
+                video_count: value.get("videoCount")?.as_u64().unwrap_or_default(),
+            },
+            _ => return None,
+        };
+        Some(item)
+    }
+
+    /// Invidious gives the bitrate and itag of a format as strings.
+    fn parse_number(value: &Value) -> Option<u64> {
+        match value {
+            Value::String(s) => s.parse().ok(),
+            _ => value.as_u64(),
+        }
+    }
+
+    fn parse_audio_stream(
+        value: &Value,
+        domain: &str,
+        video_id: &str,
+        types: &[&str],
+    ) -> Option<AudioStream> {
+        let format = value
+            .get("adaptiveFormats")?
+            .as_array()?
+            .iter()
+            .filter(|format| {
+                let mime = format.get("type").and_then(Value::as_str).unwrap_or("");
+                types.iter().any(|t| mime.starts_with(t))
+            })
+            .max_by_key(|format| format.get("bitrate").and_then(Self::parse_number))?;
+        // the googlevideo urls are often locked to the address of the
+        // instance, so the audio goes through the instance instead
+        let url = match format.get("itag").and_then(Self::parse_number) {
+            Some(itag) => format!("{domain}/latest_version?id={video_id}&itag={itag}&local=true"),
+            None => format.get("url")?.as_str()?.to_owned(),
+        };
+        Some(AudioStream {
+            title: value.get("title")?.as_str()?.to_owned(),
+            author: value
+                .get("author")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            length_seconds: value
+                .get("lengthSeconds")
+                .and_then(Value::as_u64)
+                .unwrap_or_default(),
+            url,
+        })
+    }
+
     fn parse_youtube_item(value: &Value) -> Option<(String, String, u64)> {
         let title = value.get("title")?.as_str()?.to_owned();
         let video_id = value.get("videoId")?.as_str()?.to_owned();
@@ -286,3 +464,123 @@ impl Instance {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Serves the body of the first route the path of a request starts with,
+    /// like an Invidious instance would.
+    fn mock_instance(routes: Vec<(&'static str, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let domain = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                reader.read_line(&mut request).ok();
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap_or_default() > 2 {
+                    header.clear();
+                }
+                let path = request.split(' ').nth(1).unwrap_or_default();
+                let response = match routes.iter().find(|(route, _)| path.starts_with(route)) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                };
+                stream.write_all(response.as_bytes()).ok();
+            }
+        });
+        domain
+    }
+
+    #[test]
+    fn test_search_preferred_instances() {
+        let domain = mock_instance(vec![(
+            "/api/v1/search?q=lofi",
+            r#"[
+                {"type": "video", "title": "Lofi beats", "videoId": "abc", "lengthSeconds": 120},
+                {"type": "playlist", "title": "Lofi mix", "playlistId": "PLxyz", "videoCount": 12},
+                {"type": "channel", "author": "Lofi Girl", "authorId": "UCxyz", "videoCount": 300},
+                {"type": "movie"}
+            ]"#,
+        )]);
+        let down = "http://127.0.0.1:1".to_string();
+        let (instance, items) = Instance::new("lofi", &[down, format!("{domain}/")]).unwrap();
+        assert_eq!(instance.domain, Some(domain));
+        assert_eq!(items.len(), 3);
+        assert!(matches!(&items[0], YoutubeItem::Video(v) if v.video_id == "abc"));
+        assert!(
+            matches!(&items[1], YoutubeItem::Playlist { playlist_id, .. } if playlist_id == "PLxyz")
+        );
+        assert!(
+            matches!(&items[2], YoutubeItem::Channel { author_id, .. } if author_id == "UCxyz")
+        );
+    }
+
+    #[test]
+    fn test_playlist_channel_and_audio_stream() {
+        let domain = mock_instance(vec![
+            (
+                "/api/v1/playlists/PLxyz",
+                r#"{"title": "Lofi mix", "videos": [
+                    {"title": "One", "videoId": "v1", "lengthSeconds": 60},
+                    {"title": "Two", "videoId": "v2", "lengthSeconds": 90}
+                ]}"#,
+            ),
+            (
+                "/api/v1/channels/UCxyz/videos?continuation=next",
+                r#"{"videos": [{"title": "Three", "videoId": "v3", "lengthSeconds": 30}]}"#,
+            ),
+            (
+                "/api/v1/channels/UCxyz/videos",
+                r#"{"videos": [{"title": "Two", "videoId": "v2", "lengthSeconds": 90}], "continuation": "next"}"#,
+            ),
+            (
+                "/api/v1/videos/v1",
+                r#"{"title": "One", "author": "Lofi Girl", "lengthSeconds": 60, "adaptiveFormats": [
+                    {"type": "video/mp4; codecs=\"avc1\"", "bitrate": "900000", "itag": "137"},
+                    {"type": "audio/mp4; codecs=\"mp4a.40.5\"", "bitrate": "50000", "itag": "139"},
+                    {"type": "audio/mp4; codecs=\"mp4a.40.2\"", "bitrate": "130000", "itag": "140"},
+                    {"type": "audio/webm; codecs=\"opus\"", "bitrate": "160000", "itag": "251"}
+                ]}"#,
+            ),
+        ]);
+        let (instance, (title, videos)) =
+            Instance::connect(None, std::slice::from_ref(&domain), |i| {
+                i.get_playlist("PLxyz", 1)
+            })
+            .unwrap();
+        assert_eq!(title, "Lofi mix");
+        assert_eq!(videos.len(), 2);
+
+        let (videos, continuation) = instance.get_channel_videos("UCxyz", None).unwrap();
+        assert_eq!(videos[0].video_id, "v2");
+        assert_eq!(continuation.as_deref(), Some("next"));
+        let (videos, continuation) = instance.get_channel_videos("UCxyz", Some("next")).unwrap();
+        assert_eq!(videos[0].video_id, "v3");
+        assert_eq!(continuation, None);
+
+        let value: Value = instance.get_json("/api/v1/videos/v1", &[]).unwrap();
+        let mp4 = Instance::parse_audio_stream(&value, &domain, "v1", &["audio/mp4"]).unwrap();
+        assert_eq!(
+            mp4,
+            AudioStream {
+                title: "One".to_string(),
+                author: "Lofi Girl".to_string(),
+                length_seconds: 60,
+                url: format!("{domain}/latest_version?id=v1&itag=140&local=true"),
+            }
+        );
+        let any = Instance::parse_audio_stream(&value, &domain, "v1", &["audio/"]).unwrap();
+        assert!(any.url.ends_with("itag=251&local=true"));
+        assert!(instance.get_audio_stream("v1").is_ok());
+    }
+}
@@ -29,6 +29,7 @@
  */
 mod cli;
 mod config;
+mod cover;
 #[cfg(feature = "discord")]
 mod discord;
 mod invidious;
@@ -43,6 +44,7 @@ mod track;
 mod ueberzug;
 mod ui;
 mod utils;
+mod waveform;
 
 use anyhow::Result;
 use clap::Parser;
@@ -64,6 +66,7 @@ fn main() -> Result<()> {
     }
     config.disable_album_art_from_cli = args.disable_cover;
     config.disable_discord_rpc_from_cli = args.disable_discord;
+    config.mini_from_cli = args.mini;
     if let Some(d) = args.max_depth {
         config.max_depth_cli = d;
     } else {
@@ -29,12 +29,15 @@ mod mpv_backend;
 pub mod playlist;
 #[cfg(not(any(feature = "mpv", feature = "gst")))]
 mod rusty_backend;
+mod tap;
 use crate::config::Settings;
 use anyhow::Result;
 #[cfg(feature = "mpv")]
 use mpv_backend::MpvBackend;
-pub use playlist::{Loop, Playlist, Status};
+pub use playlist::{Loop, Playlist, Shuffle, ShuffleHint, Status};
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+pub use tap::SampleTap;
 // #[cfg(not(any(feature = "mpv", feature = "gst")))]
 use std::time::Duration;
 
@@ -65,17 +68,26 @@ pub struct GeneralPlayer {
     pub message_tx: Sender<PlayerMsg>,
     pub message_rx: Receiver<PlayerMsg>,
     pub playlist: Playlist,
+    /// Speed for tracks without a per-feed speed, `Settings::speed`
+    pub speed_default: i32,
+    /// What is being played, for the visualizer. Only the rusty backend
+    /// fills it.
+    pub samples: Arc<SampleTap>,
+    /// Whether the outro of the current track was skipped, so that progress
+    /// coming in before the next track starts doesn't skip that one too
+    pub outro_skipped: bool,
 }
 
 impl GeneralPlayer {
     pub fn new(config: &Settings) -> Self {
         let (message_tx, message_rx): (Sender<PlayerMsg>, Receiver<PlayerMsg>) = mpsc::channel();
+        let samples = Arc::new(SampleTap::default());
         #[cfg(all(feature = "gst", not(feature = "mpv")))]
         let player = gstreamer_backend::GStreamer::new(config, message_tx.clone());
         #[cfg(feature = "mpv")]
         let player = MpvBackend::new(config, message_tx.clone());
         #[cfg(not(any(feature = "mpv", feature = "gst")))]
-        let player = rusty_backend::Player::new(config, message_tx.clone());
+        let player = rusty_backend::Player::new(config, message_tx.clone(), samples.clone());
         let mut playlist = Playlist::default();
         if let Ok(p) = Playlist::new(config) {
             playlist = p;
@@ -85,6 +97,9 @@ impl GeneralPlayer {
             message_tx,
             message_rx,
             playlist,
+            speed_default: config.speed,
+            samples,
+            outro_skipped: false,
         }
     }
     pub fn toggle_gapless(&mut self) -> bool {
@@ -113,10 +128,12 @@ impl GeneralPlayer {
                         .send(PlayerMsg::CurrentTrackUpdated)
                         .expect("fail to send track updated signal");
                 }
+                self.apply_track_playback();
                 return;
             }
 
             self.add_and_play(&file);
+            self.apply_track_playback();
             // eprintln!("completely new track added");
             #[cfg(not(any(feature = "mpv", feature = "gst")))]
             {
@@ -128,6 +145,51 @@ impl GeneralPlayer {
         }
     }
 
+    /// Speed for the current track: the speed of its feed for podcast
+    /// episodes, otherwise the global speed.
+    pub fn track_speed(&self) -> i32 {
+        self.playlist
+            .current_track()
+            .and_then(|track| track.playback)
+            .and_then(|playback| playback.speed)
+            .unwrap_or(self.speed_default)
+    }
+
+    /// Applies the speed and intro skip of the feed of the current track.
+    /// Music, and podcasts without their own speed, go back to the global
+    /// speed.
+    fn apply_track_playback(&mut self) {
+        let speed = self.track_speed();
+        if self.player.speed() != speed {
+            self.player.set_speed(speed);
+        }
+        if let Some(playback) = self.playlist.current_track().and_then(|t| t.playback) {
+            if playback.skip_intro > 0 {
+                self.player
+                    .seek_to(Duration::from_secs(playback.skip_intro));
+            }
+        }
+    }
+
+    /// Skips to the next track once the outro of a podcast episode is
+    /// reached, once per track. Returns true when it skipped.
+    pub fn skip_outro(&mut self, time_pos: i64, duration: i64) -> bool {
+        let Some(playback) = self.playlist.current_track().and_then(|t| t.playback) else {
+            return false;
+        };
+        let skip_outro = i64::try_from(playback.skip_outro).unwrap_or(i64::MAX);
+        if self.outro_skipped
+            || skip_outro == 0
+            || duration <= skip_outro
+            || duration - time_pos > skip_outro
+        {
+            return false;
+        }
+        self.outro_skipped = true;
+        self.skip();
+        true
+    }
+
     pub fn enqueue_next(&mut self) {
         if self.playlist.next_track().is_some() {
             return;
@@ -1,5 +1,5 @@
-use crate::podcast::{db::Database as DBPod, Episode};
-use crate::track::MediaType;
+use crate::podcast::{db::Database as DBPod, Episode, PodcastPlayback};
+use crate::track::{MediaType, Rating};
 use crate::{
     config::Settings,
     track::Track,
@@ -10,13 +10,24 @@ use pathdiff::diff_utf8_paths;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 #[cfg(not(any(feature = "mpv", feature = "gst")))]
 use std::time::Duration;
 
+const PLAYLIST_FILE: &str = "playlist.log";
+/// The tracks queued to play next, before the playlist goes on.
+const QUEUE_FILE: &str = "queue.log";
+/// The line of playlist.log that holds the index of the current track.
+const INDEX_PREFIX: &str = "#index ";
+/// The line of playlist.log that holds the name of the playlist.
+const NAME_PREFIX: &str = "#name ";
+/// The name of the playlist until it is renamed.
+pub const DEFAULT_NAME: &str = "default";
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Status {
     Running,
@@ -40,7 +51,7 @@ impl std::fmt::Display for Status {
     }
 }
 
-#[derive(Clone, Copy, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub enum Loop {
     Single,
     Playlist,
@@ -60,7 +71,7 @@ impl Loop {
             match self {
                 Self::Single => "single".to_string(),
                 Self::Playlist => "playlist".to_string(),
-                Self::Queue => "consume".to_string(),
+                Self::Queue => "queue".to_string(),
             }
         }
     }
@@ -72,58 +83,148 @@ impl Default for Loop {
     }
 }
 
+/// The order tracks play in. Shuffling leaves the playlist as it is listed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Shuffle {
+    #[default]
+    Off,
+    Tracks,
+    /// Albums play in random order, each one through in the listed order
+    Albums,
+}
+
+#[allow(clippy::non_ascii_literal)]
+impl Shuffle {
+    pub fn display(self, display_symbol: bool) -> String {
+        if display_symbol {
+            match self {
+                Self::Off => "\u{2192}".to_string(),
+                Self::Tracks => "🔀".to_string(),
+                Self::Albums => "🔀💿".to_string(),
+            }
+        } else {
+            match self {
+                Self::Off => "in order".to_string(),
+                Self::Tracks => "shuffle".to_string(),
+                Self::Albums => "album shuffle".to_string(),
+            }
+        }
+    }
+}
+
+/// What shuffle takes into account besides chance.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum ShuffleHint {
+    #[default]
+    None,
+    /// Keeps tracks, or albums, of the same artist apart
+    SeparateArtists,
+    /// Plays what the library played least often first
+    LessPlayed,
+}
+
 #[derive(Default)]
 pub struct Playlist {
+    /// Which of the named playlists this is
+    name: String,
     tracks: VecDeque<Track>,
+    /// Where in `tracks` playback is. Past the end once a `Loop::Queue`
+    /// playlist has played through.
+    current_track_index: usize,
+    /// The cursor already points at the track to play next, because the
+    /// current one was removed or previous was pressed.
+    cursor_on_next: bool,
+    /// Up next: played before the playlist goes on from the cursor
+    queue: VecDeque<Track>,
+    playing_from_queue: bool,
     current_track: Option<Track>,
     next_track: Option<Track>,
     #[cfg(not(any(feature = "mpv", feature = "gst")))]
     next_track_duration: Duration,
-    // pub index: Option<usize>,
     status: Status,
     loop_mode: Loop,
+    shuffle: Shuffle,
+    shuffle_hint: ShuffleHint,
+    /// The play order while shuffling: every index of `tracks` once
+    order: Vec<usize>,
+    /// How often the library played each file
+    play_counts: HashMap<String, u32>,
     add_playlist_front: bool,
 }
 
 // #[allow(unused)]
 impl Playlist {
     pub fn new(config: &Settings) -> Result<Self> {
-        let mut tracks = Self::load()?;
-        let mut current_track: Option<Track> = None;
-        if let Some(track) = tracks.pop_front() {
-            current_track = Some(track);
-        }
+        let (tracks, current_track_index, name) = Self::load()?;
+        let current_track = tracks.get(current_track_index).cloned();
+        let queue = Self::load_queue()?;
         let loop_mode = config.loop_mode;
         let add_playlist_front = config.add_playlist_front;
 
-        Ok(Self {
+        let mut playlist = Self {
+            name,
             tracks,
+            current_track_index,
+            cursor_on_next: false,
+            queue,
+            playing_from_queue: false,
             current_track,
             next_track: None,
             #[cfg(not(any(feature = "mpv", feature = "gst")))]
             next_track_duration: Duration::from_secs(0),
-            // index: Some(0),
             status: Status::Stopped,
             loop_mode,
+            shuffle: config.shuffle,
+            shuffle_hint: config.shuffle_hint,
+            order: Vec::new(),
+            play_counts: HashMap::new(),
             add_playlist_front,
-        })
+        };
+        playlist.reshuffle();
+        Ok(playlist)
     }
 
     pub fn reload(&mut self) -> Result<()> {
         self.save()?;
-        let mut tracks = Self::load()?;
-        let mut current_track: Option<Track> = None;
-        if let Some(track) = tracks.pop_front() {
-            current_track = Some(track);
+        let (tracks, current_track_index, _) = Self::load()?;
+        // picks up the changes to an episode that is playing, unless what
+        // plays is up next or the cursor already moved on
+        if let (Some(current), Some(track)) = (&self.current_track, tracks.get(current_track_index))
+        {
+            if current.file() == track.file() {
+                self.current_track = Some(track.clone());
+            }
         }
-        self.tracks = tracks;
-        self.current_track = current_track;
+        let old_tracks = std::mem::replace(&mut self.tracks, tracks);
+        self.current_track_index = current_track_index;
+        self.queue = Self::load_queue()?;
+        self.keep_order(&old_tracks);
         Ok(())
     }
 
-    pub fn load() -> Result<VecDeque<Track>> {
+    /// The tracks of the playlist, the index of the current one and the
+    /// name of the playlist.
+    pub fn load() -> Result<(VecDeque<Track>, usize, String)> {
+        let lines = Self::read_file(PLAYLIST_FILE)?;
+        let mut current_track_index = 0;
+        let mut name = DEFAULT_NAME.to_string();
+        for line in &lines {
+            if let Some(index) = line.strip_prefix(INDEX_PREFIX) {
+                current_track_index = index.trim().parse().unwrap_or_default();
+            } else if let Some(line) = line.strip_prefix(NAME_PREFIX) {
+                name = line.trim().to_string();
+            }
+        }
+        Ok((Self::tracks_from_lines(&lines)?, current_track_index, name))
+    }
+
+    fn load_queue() -> Result<VecDeque<Track>> {
+        Self::tracks_from_lines(&Self::read_file(QUEUE_FILE)?)
+    }
+
+    fn read_file(name: &str) -> Result<Vec<String>> {
         let mut path = get_app_config_path()?;
-        path.push("playlist.log");
+        path.push(name);
 
         let file = if let Ok(f) = File::open(path.as_path()) {
             f
@@ -132,18 +233,24 @@ impl Playlist {
             File::open(path)?
         };
         let reader = BufReader::new(file);
-        let lines: Vec<_> = reader
+        Ok(reader
             .lines()
             .map(|line| line.unwrap_or_else(|_| "Error".to_string()))
-            .collect();
+            .collect())
+    }
 
+    /// The tracks of files and podcast episode urls, skipping what is gone.
+    fn tracks_from_lines(lines: &[String]) -> Result<VecDeque<Track>> {
         let mut playlist_items = VecDeque::new();
         let db_path = get_app_config_path()?;
         let db_podcast = DBPod::connect(&db_path)?;
         let podcasts = db_podcast
             .get_podcasts()
             .expect("failed to get podcasts from db.");
-        for line in &lines {
+        for line in lines {
+            if line.starts_with(INDEX_PREFIX) || line.starts_with(NAME_PREFIX) {
+                continue;
+            }
             if let Ok(s) = Track::read_from_path(line, false) {
                 playlist_items.push_back(s);
                 continue;
@@ -152,7 +259,8 @@ impl Playlist {
                 'outer: for pod in &podcasts {
                     for ep in &pod.episodes {
                         if &ep.url == line {
-                            let track = Track::from_episode(ep);
+                            let mut track = Track::from_episode(ep);
+                            track.playback = Some(pod.playback);
                             playlist_items.push_back(track);
                             break 'outer;
                         }
@@ -165,23 +273,45 @@ impl Playlist {
     }
 
     pub fn save(&mut self) -> Result<()> {
+        let (files, index) = self.saved_files();
+        let header = format!("{NAME_PREFIX}{}\n{INDEX_PREFIX}{index}\n", self.name);
+        Self::save_file(PLAYLIST_FILE, &header, &files)?;
+        Self::save_file(QUEUE_FILE, "", &Self::kept_files(&self.queue))
+    }
+
+    /// The files of the playlist that are kept across restarts, and where
+    /// the current track is among them.
+    pub fn saved_files(&self) -> (Vec<String>, usize) {
+        let index = self
+            .tracks
+            .iter()
+            .take(self.current_track_index)
+            .filter(|track| !track.is_stream())
+            .count();
+        (Self::kept_files(&self.tracks), index)
+    }
+
+    /// Streams are left out, their urls expire.
+    fn kept_files(tracks: &VecDeque<Track>) -> Vec<String> {
+        tracks
+            .iter()
+            .filter(|track| !track.is_stream())
+            .filter_map(Track::file)
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    fn save_file(name: &str, header: &str, files: &[String]) -> Result<()> {
         let mut path = get_app_config_path()?;
-        path.push("playlist.log");
+        path.push(name);
 
         let file = File::create(path.as_path())?;
         let mut writer = BufWriter::new(file);
         let mut bytes = Vec::new();
-        if let Some(track) = &self.current_track {
-            if let Some(f) = track.file() {
-                bytes.extend(f.as_bytes());
-                bytes.extend("\n".as_bytes());
-            }
-        }
-        for i in &self.tracks {
-            if let Some(f) = i.file() {
-                bytes.extend(f.as_bytes());
-                bytes.extend("\n".as_bytes());
-            }
+        bytes.extend(header.as_bytes());
+        for f in files {
+            bytes.extend(f.as_bytes());
+            bytes.extend("\n".as_bytes());
         }
 
         writer.write_all(&bytes)?;
@@ -199,17 +329,36 @@ impl Playlist {
     }
 
     pub fn swap_down(&mut self, index: usize) {
-        if index < self.len() - 1 {
-            if let Some(track) = self.tracks.remove(index) {
-                self.tracks.insert(index + 1, track);
-            }
+        if index + 1 < self.len() {
+            self.tracks.swap(index, index + 1);
+            self.swap_cursor(index, index + 1);
+            self.swap_order(index, index + 1);
         }
     }
 
     pub fn swap_up(&mut self, index: usize) {
-        if index > 0 {
-            if let Some(track) = self.tracks.remove(index) {
-                self.tracks.insert(index - 1, track);
+        if index > 0 && index < self.len() {
+            self.tracks.swap(index, index - 1);
+            self.swap_cursor(index, index - 1);
+            self.swap_order(index, index - 1);
+        }
+    }
+
+    fn swap_cursor(&mut self, a: usize, b: usize) {
+        if self.current_track_index == a {
+            self.current_track_index = b;
+        } else if self.current_track_index == b {
+            self.current_track_index = a;
+        }
+    }
+
+    /// Keeps the play order of two tracks that swapped places.
+    fn swap_order(&mut self, a: usize, b: usize) {
+        for index in &mut self.order {
+            if *index == a {
+                *index = b;
+            } else if *index == b {
+                *index = a;
             }
         }
     }
@@ -240,8 +389,62 @@ impl Playlist {
         result
     }
 
+    /// The track that plays after the current one.
     pub fn fetch_next_track(&self) -> Option<&Track> {
-        self.tracks.get(0)
+        if let Some(track) = self.queue.front() {
+            return Some(track);
+        }
+        self.tracks.get(self.next_track_index()?)
+    }
+
+    pub fn current_track_index(&self) -> usize {
+        self.current_track_index
+    }
+
+    /// How many tracks are left to play after the current one before the
+    /// playlist ends, or starts over.
+    pub fn upcoming(&self) -> usize {
+        let played = usize::from(!self.cursor_on_next && self.current_track.is_some());
+        if self.is_shuffled() {
+            let position = self.order_position().unwrap_or_default();
+            return self.order.len().saturating_sub(position + played);
+        }
+        self.tracks
+            .len()
+            .saturating_sub(self.current_track_index + played)
+    }
+
+    /// Whether the current track came from up next rather than the playlist.
+    pub fn is_playing_from_queue(&self) -> bool {
+        self.playing_from_queue
+    }
+
+    /// Where the cursor goes when the current track ends, None at the end
+    /// of a `Loop::Queue` playlist.
+    fn next_track_index(&self) -> Option<usize> {
+        let len = self.tracks.len();
+        let index = self.current_track_index;
+        if self.cursor_on_next || self.current_track.is_none() {
+            return match self.loop_mode {
+                _ if index < len => Some(index),
+                Loop::Playlist | Loop::Single if len > 0 => Some(self.first_index()),
+                _ => None,
+            };
+        }
+        if self.is_shuffled() && self.loop_mode != Loop::Single {
+            let next = self.order_position().map_or(0, |position| position + 1);
+            return match self.order.get(next) {
+                Some(&index) => Some(index),
+                None if self.loop_mode == Loop::Playlist => self.order.first().copied(),
+                None => None,
+            };
+        }
+        match self.loop_mode {
+            Loop::Single if index < len => Some(index),
+            Loop::Single | Loop::Queue => (index + 1 < len).then_some(index + 1),
+            Loop::Playlist if len > 0 => Some((index + 1) % len),
+            Loop::Playlist => None,
+        }
     }
 
     pub fn set_status(&mut self, status: Status) {
@@ -260,41 +463,266 @@ impl Playlist {
         self.status
     }
 
+    /// Moves the cursor on to the track that plays after the current one,
+    /// or onto the cursor if nothing is playing. Up next goes first, and
+    /// leaves the cursor where it is.
     pub fn handle_current_track(&mut self) {
-        // eprintln!("handle current track");
-
-        if let Some(song) = self.tracks.pop_front() {
-            match self.loop_mode {
-                Loop::Playlist => self.tracks.push_back(song.clone()),
-                Loop::Single => self.tracks.push_front(song.clone()),
-                Loop::Queue => {}
+        if let Some(track) = self.queue.pop_front() {
+            // the track under the cursor hasn't played yet
+            if self.current_track.is_none() {
+                self.cursor_on_next = true;
+            }
+            self.current_track = Some(track);
+            self.playing_from_queue = true;
+            return;
+        }
+        self.playing_from_queue = false;
+        // a shuffled playlist that loops gets a new order each time round
+        let wraps = self.is_shuffled()
+            && self.loop_mode == Loop::Playlist
+            && self.current_track.is_some()
+            && !self.cursor_on_next
+            && self.order_position() == Some(self.order.len().saturating_sub(1));
+        if let Some(index) = self.next_track_index() {
+            self.current_track_index = index;
+            self.current_track = self.tracks.get(index).cloned();
+            if wraps {
+                self.reshuffle();
             }
-            self.current_track = Some(song);
         } else {
+            self.current_track_index = self.tracks.len();
             self.current_track = None;
             self.set_status(Status::Stopped);
         }
+        self.cursor_on_next = false;
     }
 
     pub fn cycle_loop_mode(&mut self) -> Loop {
-        match self.loop_mode {
-            Loop::Queue => {
-                self.loop_mode = Loop::Playlist;
+        self.loop_mode = match self.loop_mode {
+            Loop::Queue => Loop::Playlist,
+            Loop::Playlist => Loop::Single,
+            Loop::Single => Loop::Queue,
+        };
+        self.loop_mode
+    }
+
+    pub fn cycle_shuffle(&mut self) -> Shuffle {
+        self.shuffle = match self.shuffle {
+            Shuffle::Off => Shuffle::Tracks,
+            Shuffle::Tracks => Shuffle::Albums,
+            Shuffle::Albums => Shuffle::Off,
+        };
+        self.reshuffle();
+        self.shuffle
+    }
+
+    /// Takes the play counts of the library, for `ShuffleHint::LessPlayed`.
+    pub fn set_play_counts(&mut self, play_counts: HashMap<String, u32>) {
+        self.play_counts = play_counts;
+        if self.shuffle_hint == ShuffleHint::LessPlayed {
+            self.shuffle_upcoming();
+        }
+    }
+
+    pub fn add_play_count(&mut self, file: &str) {
+        *self.play_counts.entry(file.to_string()).or_default() += 1;
+    }
+
+    /// Gives the tracks their ratings from the library. Tracks not in it
+    /// yet keep the ratings of their tags.
+    pub fn set_ratings(&mut self, ratings: &HashMap<String, Rating>) {
+        let tracks = self.tracks.iter_mut().chain(self.queue.iter_mut());
+        for track in tracks.chain(self.current_track.iter_mut()) {
+            if let Some(rating) = track.file().and_then(|file| ratings.get(file)) {
+                track.set_rating(*rating);
             }
-            Loop::Playlist => {
-                self.loop_mode = Loop::Single;
-                if let Some(song) = self.tracks.pop_back() {
-                    self.tracks.push_front(song);
+        }
+    }
+
+    fn is_shuffled(&self) -> bool {
+        self.shuffle != Shuffle::Off
+    }
+
+    /// Where the cursor is in the play order.
+    fn order_position(&self) -> Option<usize> {
+        self.order
+            .iter()
+            .position(|&index| index == self.current_track_index)
+    }
+
+    /// The first track of the play order.
+    fn first_index(&self) -> usize {
+        if self.is_shuffled() {
+            self.order.first().copied().unwrap_or_default()
+        } else {
+            0
+        }
+    }
+
+    /// Draws a new play order that starts at the cursor, or drops it when
+    /// shuffle is off.
+    fn reshuffle(&mut self) {
+        self.order.clear();
+        if !self.is_shuffled() {
+            return;
+        }
+        let first =
+            (self.current_track_index < self.tracks.len()).then_some(self.current_track_index);
+        self.order = self.shuffled((0..self.tracks.len()).collect(), first);
+    }
+
+    /// Where the part of the play order that may still change starts: after
+    /// the played tracks and the one queued to play next for gapless.
+    fn upcoming_start(&self) -> usize {
+        let start = match self.order_position() {
+            Some(position) if self.current_track.is_some() && !self.cursor_on_next => position + 2,
+            Some(position) => position + 1,
+            None => 0,
+        };
+        start.min(self.order.len())
+    }
+
+    /// Draws a new order for the tracks that haven't played yet in this
+    /// round, leaving the ones that did where they are.
+    fn shuffle_upcoming(&mut self) {
+        if !self.is_shuffled() {
+            return;
+        }
+        self.order.truncate(self.upcoming_start());
+        let upcoming = (0..self.tracks.len())
+            .filter(|index| !self.order.contains(index))
+            .collect();
+        let upcoming = self.shuffled(upcoming, None);
+        self.order.extend(upcoming);
+    }
+
+    /// Puts `indices`, new to the playlist, at random places in the rest of
+    /// the round, leaving the order of the others as it is.
+    fn order_insert(&mut self, indices: Vec<usize>) {
+        if !self.is_shuffled() {
+            return;
+        }
+        let start = self.upcoming_start();
+        let mut units = self.units(indices);
+        units.shuffle(&mut thread_rng());
+        for unit in units {
+            // between albums, not inside one
+            let album = |index: usize| self.tracks[index].album();
+            let places: Vec<usize> = (start..=self.order.len())
+                .filter(|&at| {
+                    at == start
+                        || at == self.order.len()
+                        || self.shuffle != Shuffle::Albums
+                        || album(self.order[at - 1]) != album(self.order[at])
+                })
+                .collect();
+            let at = places.choose(&mut thread_rng()).copied().unwrap_or(start);
+            self.order.splice(at..at, unit);
+        }
+    }
+
+    /// Carries the play order over to the tracks loaded anew, matched by
+    /// file: the tracks that are gone leave it and new ones join the rest
+    /// of the round.
+    fn keep_order(&mut self, old_tracks: &VecDeque<Track>) {
+        if !self.is_shuffled() {
+            return;
+        }
+        let mut indices: HashMap<&str, VecDeque<usize>> = HashMap::new();
+        for (index, track) in self.tracks.iter().enumerate() {
+            let file = track.file().unwrap_or_default();
+            indices.entry(file).or_default().push_back(index);
+        }
+        let order: Vec<usize> = self
+            .order
+            .iter()
+            .filter_map(|&index| {
+                let file = old_tracks.get(index)?.file().unwrap_or_default();
+                indices.get_mut(file)?.pop_front()
+            })
+            .collect();
+        let mut added: Vec<usize> = indices.into_values().flatten().collect();
+        added.sort_unstable();
+        self.order = order;
+        self.order_insert(added);
+    }
+
+    /// `indices` grouped into what shuffles as one: single tracks, or albums
+    /// in the listed order.
+    fn units(&self, indices: Vec<usize>) -> Vec<Vec<usize>> {
+        if self.shuffle == Shuffle::Albums {
+            let mut albums: Vec<Vec<usize>> = Vec::new();
+            let mut positions: HashMap<&str, usize> = HashMap::new();
+            for index in indices {
+                let album = self.tracks[index].album().filter(|album| !album.is_empty());
+                if let Some(&position) = album.and_then(|album| positions.get(album)) {
+                    albums[position].push(index);
+                    continue;
+                }
+                if let Some(album) = album {
+                    positions.insert(album, albums.len());
                 }
+                albums.push(vec![index]);
             }
-            Loop::Single => {
-                self.loop_mode = Loop::Queue;
-                if let Some(song) = self.tracks.pop_front() {
-                    self.tracks.push_back(song);
+            albums
+        } else {
+            indices.into_iter().map(|index| vec![index]).collect()
+        }
+    }
+
+    /// `indices` in random order: one by one, or album by album in the
+    /// listed order. The track `first`, or its album from it on, leads.
+    fn shuffled(&self, indices: Vec<usize>, first: Option<usize>) -> Vec<usize> {
+        let mut units = self.units(indices);
+        units.shuffle(&mut thread_rng());
+        let mut lead = 0;
+        if let Some(first) = first {
+            if let Some(position) = units.iter().position(|unit| unit.contains(&first)) {
+                let unit = units.remove(position);
+                let (from, before): (Vec<usize>, Vec<usize>) =
+                    unit.into_iter().partition(|&index| index >= first);
+                if !before.is_empty() {
+                    units.push(before);
                 }
+                units.insert(0, from);
+                lead = 1;
             }
-        };
-        self.loop_mode
+        }
+        match self.shuffle_hint {
+            ShuffleHint::None => {}
+            ShuffleHint::SeparateArtists => self.separate_artists(&mut units),
+            ShuffleHint::LessPlayed => {
+                // stable, so equally played tracks stay in random order
+                units[lead..].sort_by_key(|unit| {
+                    let plays: u32 = unit.iter().map(|&index| self.play_count(index)).sum();
+                    plays / u32::try_from(unit.len()).unwrap_or(1)
+                });
+            }
+        }
+        units.into_iter().flatten().collect()
+    }
+
+    fn play_count(&self, index: usize) -> u32 {
+        self.tracks[index]
+            .file()
+            .and_then(|file| self.play_counts.get(file))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Moves units so that no two in a row have the same artist, where
+    /// there is another artist to put between them.
+    fn separate_artists(&self, units: &mut [Vec<usize>]) {
+        let artist = |unit: &Vec<usize>| self.tracks[unit[0]].artist().map(str::to_lowercase);
+        for i in 1..units.len() {
+            let previous = artist(&units[i - 1]);
+            if previous.is_none() || artist(&units[i]) != previous {
+                continue;
+            }
+            if let Some(j) = (i + 1..units.len()).find(|&j| artist(&units[j]) != previous) {
+                units.swap(i, j);
+            }
+        }
     }
 
     // export to M3U
@@ -331,27 +759,63 @@ impl Playlist {
         self.add_playlist_front
     }
 
-    pub fn add_episode(&mut self, ep: &Episode) {
-        let track = Track::from_episode(ep);
+    /// Where tracks added to the front go: right after the current track,
+    /// so they play next.
+    fn front_index(&self) -> usize {
+        let index = if self.current_track.is_some() && !self.cursor_on_next {
+            self.current_track_index + 1
+        } else {
+            self.current_track_index
+        };
+        index.min(self.tracks.len())
+    }
 
+    /// Adds `tracks` to the end, or after the current track with add front.
+    /// Shuffled, they play next with add front, or somewhere in the rest of
+    /// the round.
+    fn add_tracks(&mut self, tracks: Vec<Track>) {
+        let count = tracks.len();
         if self.add_playlist_front {
-            self.tracks.push_front(track);
+            let index = self.front_index();
+            let at = match self.order_position() {
+                Some(position) if self.current_track.is_some() && !self.cursor_on_next => {
+                    position + 1
+                }
+                Some(position) => position,
+                None => self.order.len(),
+            };
+            for (offset, track) in tracks.into_iter().enumerate() {
+                self.tracks.insert(index + offset, track);
+            }
+            if self.is_shuffled() {
+                for order_index in &mut self.order {
+                    if *order_index >= index {
+                        *order_index += count;
+                    }
+                }
+                self.order.splice(at..at, index..index + count);
+            }
             return;
         }
-        self.tracks.push_back(track);
+        let start = self.tracks.len();
+        self.tracks.extend(tracks);
+        self.order_insert((start..start + count).collect());
     }
-    pub fn add_playlist(&mut self, mut vec: Vec<&str>) -> Result<()> {
-        if self.add_playlist_front {
-            vec.reverse();
-            self.add_playlist_inside(vec)?;
-            return Ok(());
-        }
 
-        self.add_playlist_inside(vec)?;
+    pub fn add_episode(&mut self, ep: &Episode, playback: PodcastPlayback) {
+        let mut track = Track::from_episode(ep);
+        track.playback = Some(playback);
+        self.add_tracks(vec![track]);
+    }
+
+    pub fn add_playlist(&mut self, vec: Vec<&str>) -> Result<()> {
+        let tracks = Self::read_tracks(vec)?;
+        self.add_tracks(tracks);
         Ok(())
     }
 
-    fn add_playlist_inside(&mut self, vec: Vec<&str>) -> Result<()> {
+    fn read_tracks(vec: Vec<&str>) -> Result<Vec<Track>> {
+        let mut tracks = Vec::new();
         for item in vec {
             if !filetype_supported(item) {
                 continue;
@@ -359,54 +823,212 @@ impl Playlist {
             if !PathBuf::from(item).exists() {
                 continue;
             }
-            self.add_playlist_inside_inside(item)?;
+            tracks.push(Track::read_from_path(item, false)?);
         }
-        Ok(())
+        Ok(tracks)
     }
 
-    fn add_playlist_inside_inside(&mut self, item: &str) -> Result<()> {
-        let track = Track::read_from_path(item, false)?;
-        if self.add_playlist_front {
-            self.tracks.push_front(track);
-            return Ok(());
-        }
-        self.tracks.push_back(track);
+    /// Adds the files to up next.
+    pub fn add_queue(&mut self, vec: Vec<&str>) -> Result<()> {
+        let tracks = Self::read_tracks(vec)?;
+        self.queue.extend(tracks);
         Ok(())
     }
 
+    pub fn add_episode_queue(&mut self, ep: &Episode, playback: PodcastPlayback) {
+        let mut track = Track::from_episode(ep);
+        track.playback = Some(playback);
+        self.add_track_queue(track);
+    }
+
+    pub fn add_track_queue(&mut self, track: Track) {
+        self.queue.push_back(track);
+    }
+
+    pub fn queue(&self) -> &VecDeque<Track> {
+        &self.queue
+    }
+
+    pub fn queue_remove(&mut self, index: usize) -> Option<Track> {
+        self.queue.remove(index)
+    }
+
+    pub fn queue_clear(&mut self) {
+        self.queue.clear();
+    }
+
     pub fn tracks(&self) -> &VecDeque<Track> {
         &self.tracks
     }
 
+    pub fn files(&self) -> Vec<String> {
+        self.tracks
+            .iter()
+            .filter_map(Track::file)
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
+    /// Adds files and podcast episode urls, like a named playlist holds them.
+    pub fn add_files(&mut self, files: &[String]) -> Result<()> {
+        let tracks = Self::tracks_from_lines(files)?;
+        self.add_tracks(tracks.into());
+        Ok(())
+    }
+
+    /// Swaps in the named playlist `name`, to go on at the track `index`.
+    pub fn replace(&mut self, name: &str, files: &[String], index: usize) -> Result<()> {
+        self.tracks = Self::tracks_from_lines(files)?;
+        self.name = name.to_string();
+        self.current_track_index = index.min(self.tracks.len());
+        self.cursor_on_next = true;
+        self.reshuffle();
+        Ok(())
+    }
+
+    /// Updates the playback settings of queued episodes whose url is in
+    /// `episode_urls`, after their feed settings changed.
+    pub fn set_podcast_playback(&mut self, episode_urls: &[&str], playback: PodcastPlayback) {
+        let tracks = self
+            .tracks
+            .iter_mut()
+            .chain(self.queue.iter_mut())
+            .chain(self.current_track.iter_mut())
+            .chain(self.next_track.iter_mut());
+        for track in tracks {
+            if matches!(track.file(), Some(file) if episode_urls.contains(&file)) {
+                track.playback = Some(playback);
+            }
+        }
+    }
+
     pub fn remove(&mut self, index: usize) -> Option<Track> {
-        self.tracks.remove(index)
+        let track = self.tracks.remove(index)?;
+        let position = self.order.iter().position(|&i| i == index);
+        self.order.retain(|&i| i != index);
+        for order_index in &mut self.order {
+            if *order_index > index {
+                *order_index -= 1;
+            }
+        }
+        match index.cmp(&self.current_track_index) {
+            Ordering::Less => self.current_track_index -= 1,
+            // the next track moved under the cursor
+            Ordering::Equal => {
+                if let Some(position) = position {
+                    self.current_track_index = self
+                        .order
+                        .get(position)
+                        .copied()
+                        .unwrap_or(self.tracks.len());
+                }
+                self.cursor_on_next = true;
+            }
+            Ordering::Greater => {}
+        }
+        Some(track)
     }
 
     pub fn clear(&mut self) {
         self.tracks.clear();
+        self.order.clear();
+        self.current_track_index = 0;
+        self.cursor_on_next = true;
     }
 
+    /// Reorders the playlist itself at random, unlike `Shuffle`.
     pub fn shuffle(&mut self) {
+        let current = self.cursor_file();
         let mut rng = thread_rng();
         self.tracks.make_contiguous().shuffle(&mut rng);
+        self.locate(current);
+        self.reshuffle();
+    }
+
+    pub fn sort_by(&mut self, compare: impl FnMut(&Track, &Track) -> Ordering) {
+        let current = self.cursor_file();
+        self.tracks.make_contiguous().sort_by(compare);
+        self.locate(current);
+        self.reshuffle();
     }
 
     pub fn remove_deleted_items(&mut self) {
+        let mut index = 0;
+        while index < self.tracks.len() {
+            if matches!(self.tracks[index].file(), Some(file) if Path::new(file).exists()) {
+                index += 1;
+            } else {
+                self.remove(index);
+            }
+        }
+        self.queue.retain(|track| {
+            matches!(track.media_type, Some(MediaType::Podcast))
+                || matches!(track.file(), Some(file) if Path::new(file).exists())
+        });
+    }
+
+    fn cursor_file(&self) -> Option<String> {
         self.tracks
-            .retain(|x| x.file().map_or(false, |p| Path::new(p).exists()));
+            .get(self.current_track_index)
+            .and_then(Track::file)
+            .map(ToString::to_string)
+    }
+
+    /// Points the cursor back at its track after the tracks moved.
+    fn locate(&mut self, file: Option<String>) {
+        let Some(file) = file else {
+            return;
+        };
+        if let Some(index) = self
+            .tracks
+            .iter()
+            .position(|t| t.file() == Some(file.as_str()))
+        {
+            self.current_track_index = index;
+        }
     }
 
-    pub fn push_front(&mut self, track: &Track) {
-        self.tracks.push_front(track.clone());
+    /// Makes the track at `index` the one that plays when the current one
+    /// ends or is skipped.
+    pub fn set_next_index(&mut self, index: usize) {
+        if index < self.tracks.len() {
+            self.current_track_index = index;
+            self.cursor_on_next = true;
+        }
     }
 
+    /// Moves the cursor back to the track before the current one, so that
+    /// skipping plays it.
     pub fn handle_previous(&mut self) {
-        if let Some(song) = self.tracks.pop_back() {
-            self.tracks.push_front(song);
+        let len = self.tracks.len();
+        if len == 0 {
+            return;
         }
-        if let Some(song) = self.tracks.pop_back() {
-            self.tracks.push_front(song);
+        if self.is_shuffled() {
+            if let Some(position) = self.order_position() {
+                let previous = match position {
+                    0 if self.loop_mode == Loop::Playlist => self.order.len() - 1,
+                    0 => 0,
+                    position => position - 1,
+                };
+                self.set_next_index(self.order[previous]);
+                return;
+            }
         }
+        let index = match self.current_track_index {
+            0 if self.loop_mode == Loop::Playlist => len - 1,
+            0 => 0,
+            index => index.min(len) - 1,
+        };
+        self.set_next_index(index);
     }
 
     pub fn current_track(&self) -> Option<&Track> {
@@ -449,3 +1071,334 @@ impl Playlist {
         self.next_track_duration = d;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::DataBase;
+    use pretty_assertions::assert_eq;
+
+    fn abc(loop_mode: Loop) -> Playlist {
+        let tracks = ["a", "b", "c"]
+            .iter()
+            .map(|url| {
+                Track::from_episode(&Episode {
+                    url: (*url).to_string(),
+                    ..Episode::default()
+                })
+            })
+            .collect();
+        let mut playlist = Playlist {
+            loop_mode,
+            ..Playlist::default()
+        };
+        playlist.add_tracks(tracks);
+        playlist
+    }
+
+    fn current(playlist: &Playlist) -> Option<&str> {
+        playlist.current_track().and_then(Track::file)
+    }
+
+    fn files(playlist: &Playlist) -> Vec<&str> {
+        playlist.tracks().iter().filter_map(Track::file).collect()
+    }
+
+    #[test]
+    fn test_cursor_keeps_tracks() {
+        let mut playlist = abc(Loop::Queue);
+        let mut played = Vec::new();
+        playlist.handle_current_track();
+        while let Some(file) = current(&playlist) {
+            played.push(file.to_string());
+            playlist.handle_current_track();
+        }
+        assert_eq!(played, vec!["a", "b", "c"]);
+        assert!(playlist.is_stopped());
+        assert_eq!(files(&playlist), vec!["a", "b", "c"]);
+
+        let mut playlist = abc(Loop::Playlist);
+        playlist.handle_current_track();
+        playlist.handle_current_track();
+        assert_eq!(current(&playlist), Some("b"));
+        assert_eq!(playlist.cycle_loop_mode(), Loop::Single);
+        assert_eq!(playlist.cycle_loop_mode(), Loop::Queue);
+        assert_eq!(playlist.cycle_loop_mode(), Loop::Playlist);
+        assert_eq!(files(&playlist), vec!["a", "b", "c"]);
+        playlist.handle_current_track();
+        playlist.handle_current_track();
+        assert_eq!(current(&playlist), Some("a"));
+
+        // previous goes back through what played, and wraps in a loop
+        playlist.handle_previous();
+        assert_eq!(playlist.fetch_next_track().and_then(Track::file), Some("c"));
+        playlist.handle_current_track();
+        assert_eq!(current(&playlist), Some("c"));
+        playlist.handle_previous();
+        playlist.handle_previous();
+        playlist.handle_current_track();
+        assert_eq!(current(&playlist), Some("a"));
+    }
+
+    #[test]
+    fn test_cursor_follows_edits() {
+        let mut playlist = abc(Loop::Queue);
+        playlist.handle_current_track();
+        playlist.handle_current_track();
+        assert_eq!(current(&playlist), Some("b"));
+
+        playlist.toggle_add_front();
+        playlist.add_tracks(vec![Track::from_episode(&Episode {
+            url: "next".to_string(),
+            ..Episode::default()
+        })]);
+        assert_eq!(files(&playlist), vec!["a", "b", "next", "c"]);
+        assert_eq!(
+            playlist.fetch_next_track().and_then(Track::file),
+            Some("next")
+        );
+
+        playlist.swap_up(1);
+        assert_eq!(playlist.current_track_index(), 0);
+        playlist.remove(2);
+        assert_eq!(files(&playlist), vec!["b", "a", "c"]);
+        assert_eq!(playlist.current_track_index(), 0);
+
+        // the current track is removed, the one after it plays next
+        playlist.remove(0);
+        assert_eq!(files(&playlist), vec!["a", "c"]);
+        assert_eq!(current(&playlist), Some("b"));
+        playlist.handle_current_track();
+        assert_eq!(current(&playlist), Some("a"));
+
+        playlist.sort_by(|a, b| b.file().cmp(&a.file()));
+        assert_eq!(files(&playlist), vec!["c", "a"]);
+        assert_eq!(playlist.current_track_index(), 1);
+    }
+
+    #[test]
+    fn test_set_ratings_keeps_tags() {
+        let mut playlist = abc(Loop::Queue);
+        let rated = |stars| Rating {
+            stars,
+            favourite: false,
+        };
+        for track in &mut playlist.tracks {
+            track.set_rating(rated(4));
+        }
+        // a is in the library unrated, b only has its tags
+        let ratings = HashMap::from([
+            ("a".to_string(), Rating::default()),
+            ("c".to_string(), rated(3)),
+        ]);
+        playlist.set_ratings(&ratings);
+        let stars: Vec<u8> = playlist.tracks().iter().map(|t| t.rating().stars).collect();
+        assert_eq!(stars, vec![0, 4, 3]);
+    }
+
+    #[test]
+    fn test_queue_plays_first() {
+        let mut playlist = abc(Loop::Queue);
+        playlist.handle_current_track();
+        assert_eq!(current(&playlist), Some("a"));
+        for url in ["x", "y"] {
+            let episode = Episode {
+                url: url.to_string(),
+                ..Episode::default()
+            };
+            playlist.add_episode_queue(&episode, PodcastPlayback::default());
+        }
+        assert_eq!(playlist.fetch_next_track().and_then(Track::file), Some("x"));
+
+        let mut played = Vec::new();
+        playlist.handle_current_track();
+        while let Some(file) = current(&playlist) {
+            played.push((file.to_string(), playlist.is_playing_from_queue()));
+            playlist.handle_current_track();
+        }
+        let played: Vec<_> = played.iter().map(|(f, q)| (f.as_str(), *q)).collect();
+        assert_eq!(
+            played,
+            vec![("x", true), ("y", true), ("b", false), ("c", false)]
+        );
+        assert!(playlist.queue().is_empty());
+        assert_eq!(files(&playlist), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_upcoming() {
+        let mut playlist = abc(Loop::Queue);
+        assert_eq!(playlist.upcoming(), 3);
+        playlist.handle_current_track();
+        assert_eq!(playlist.upcoming(), 2);
+        // the cursor on the next track rather than the one played
+        playlist.set_next_index(1);
+        assert_eq!(playlist.upcoming(), 2);
+        playlist.handle_current_track();
+        assert_eq!(playlist.upcoming(), 1);
+
+        let mut playlist = abc(Loop::Queue);
+        while playlist.cycle_shuffle() != Shuffle::Tracks {}
+        let mut upcoming = Vec::new();
+        playlist.handle_current_track();
+        while current(&playlist).is_some() {
+            upcoming.push(playlist.upcoming());
+            playlist.handle_current_track();
+        }
+        assert_eq!(upcoming, vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_radio_records() {
+        let tracks = ["a", "b", "c"]
+            .iter()
+            .map(|url| {
+                Track::from_episode(&Episode {
+                    url: (*url).to_string(),
+                    ..Episode::default()
+                })
+            })
+            .collect();
+        let db = DataBase::in_memory(tracks);
+        db.add_play_count("a").unwrap();
+        db.add_play_count("a").unwrap();
+        let records = |within| {
+            let mut records: Vec<(String, u32)> = db
+                .get_radio_records(None, within, 10)
+                .unwrap()
+                .into_iter()
+                .map(|(record, plays)| (record.file, plays))
+                .collect();
+            records.sort();
+            records
+        };
+        let all = vec![
+            ("a".to_string(), 2),
+            ("b".to_string(), 0),
+            ("c".to_string(), 0),
+        ];
+        assert_eq!(records(None), all);
+        assert_eq!(records(Some(Duration::from_secs(10))), all[1..].to_vec());
+    }
+
+    #[test]
+    fn test_shuffle_keeps_listed_order() {
+        let tracks = (0..12)
+            .map(|i| {
+                let mut track = Track::from_episode(&Episode {
+                    url: i.to_string(),
+                    ..Episode::default()
+                });
+                track.set_album(&format!("album {}", i / 4));
+                track
+            })
+            .collect();
+        let mut playlist = Playlist {
+            loop_mode: Loop::Queue,
+            ..Playlist::default()
+        };
+        playlist.add_tracks(tracks);
+        let listed: Vec<String> = files(&playlist).iter().map(ToString::to_string).collect();
+
+        for shuffle in [Shuffle::Tracks, Shuffle::Albums] {
+            playlist.set_next_index(5);
+            while playlist.cycle_shuffle() != shuffle {}
+            let mut played = Vec::new();
+            playlist.handle_current_track();
+            while let Some(file) = current(&playlist) {
+                played.push(file.parse::<usize>().unwrap());
+                playlist.handle_current_track();
+            }
+            assert_eq!(played[0], 5);
+            if shuffle == Shuffle::Albums {
+                // the rest of the first album, then whole albums in order
+                assert_eq!(&played[..3], &[5, 6, 7]);
+                for album in played[3..].chunks(4).filter(|album| album.len() == 4) {
+                    assert!(album.windows(2).all(|pair| pair[1] == pair[0] + 1));
+                }
+            }
+            played.sort_unstable();
+            assert_eq!(played, (0..12).collect::<Vec<_>>());
+            assert_eq!(files(&playlist), listed);
+        }
+
+        assert_eq!(playlist.cycle_shuffle(), Shuffle::Off);
+        playlist.set_next_index(5);
+        playlist.handle_current_track();
+        playlist.handle_current_track();
+        assert_eq!(current(&playlist), Some("6"));
+    }
+
+    #[test]
+    fn test_shuffle_add_keeps_order() {
+        let episodes = |range: std::ops::Range<usize>| {
+            range
+                .map(|i| {
+                    Track::from_episode(&Episode {
+                        url: i.to_string(),
+                        ..Episode::default()
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut playlist = Playlist {
+            shuffle: Shuffle::Tracks,
+            ..Playlist::default()
+        };
+        playlist.add_tracks(episodes(0..8));
+        playlist.reshuffle();
+        playlist.handle_current_track();
+        let order = playlist.order.clone();
+        let next = playlist
+            .fetch_next_track()
+            .and_then(Track::file)
+            .map(String::from);
+
+        playlist.add_tracks(episodes(8..12));
+        // what played and what was queued for gapless stay put
+        let position = playlist.order_position().unwrap();
+        assert_eq!(&playlist.order[..position + 2], &order[..position + 2]);
+        assert_eq!(
+            playlist.fetch_next_track().and_then(Track::file),
+            next.as_deref()
+        );
+        let kept: Vec<usize> = playlist.order.iter().copied().filter(|&i| i < 8).collect();
+        assert_eq!(kept, order);
+        let mut all = playlist.order.clone();
+        all.sort_unstable();
+        assert_eq!(all, (0..12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shuffle_hints() {
+        let tracks = (0..8)
+            .map(|i| {
+                let mut track = Track::from_episode(&Episode {
+                    url: i.to_string(),
+                    ..Episode::default()
+                });
+                track.set_artist(if i < 4 { "a" } else { "b" });
+                track
+            })
+            .collect();
+        let mut playlist = Playlist {
+            shuffle: Shuffle::Tracks,
+            shuffle_hint: ShuffleHint::SeparateArtists,
+            ..Playlist::default()
+        };
+        playlist.add_tracks(tracks);
+        playlist.reshuffle();
+        let artists: Vec<_> = playlist
+            .order
+            .iter()
+            .map(|&i| playlist.tracks[i].artist())
+            .collect();
+        assert!(artists.windows(2).all(|pair| pair[0] != pair[1]));
+
+        playlist.shuffle_hint = ShuffleHint::LessPlayed;
+        let counts = (0..8).map(|i| (i.to_string(), 7 - i)).collect();
+        playlist.set_play_counts(counts);
+        // the cursor leads, then the least played
+        assert_eq!(playlist.order, vec![0, 7, 6, 5, 4, 3, 2, 1]);
+    }
+}
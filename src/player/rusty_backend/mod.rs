@@ -56,13 +56,14 @@ pub use stream::{OutputStream, OutputStreamHandle, PlayError, StreamError};
 
 // use self::source::SeekableRequest;
 
-use super::{PlayerMsg, PlayerTrait};
+use super::{PlayerMsg, PlayerTrait, SampleTap};
 use crate::config::Settings;
 use anyhow::Result;
 // use decoder::read_seek_source::ReadSeekSource;
 // use readable_receiver::ReadableReciever;
 use std::path::Path;
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
 use std::time::Duration;
 use std::{fs::File, io::Cursor};
 use symphonia::core::io::{MediaSource, MediaSourceStream, MediaSourceStreamOptions};
@@ -101,7 +102,7 @@ pub struct Player {
 )]
 impl Player {
     #[allow(clippy::too_many_lines)]
-    pub fn new(config: &Settings, tx: Sender<PlayerMsg>) -> Self {
+    pub fn new(config: &Settings, tx: Sender<PlayerMsg>, samples: Arc<SampleTap>) -> Self {
         let (command_tx, command_rx): (Sender<PlayerCmd>, Receiver<PlayerCmd>) = mpsc::channel();
         let volume = config.volume.try_into().unwrap();
         let speed = config.speed;
@@ -118,7 +119,7 @@ impl Player {
             let message_tx = tx.clone();
             let mut total_duration: Option<Duration> = None;
             let (_stream, handle) = OutputStream::try_default().unwrap();
-            let mut sink = Sink::try_new(&handle, gapless, tx).unwrap();
+            let mut sink = Sink::try_new(&handle, gapless, tx, samples.clone()).unwrap();
             let speed = speed as f32 / 10.0;
             sink.set_speed(speed);
             sink.set_volume(<f32 as From<u16>>::from(volume) / 100.0);
@@ -230,7 +231,13 @@ impl Player {
                             sink.set_speed(speed);
                         }
                         PlayerCmd::Stop => {
-                            sink = Sink::try_new(&handle, gapless, message_tx.clone()).unwrap();
+                            sink = Sink::try_new(
+                                &handle,
+                                gapless,
+                                message_tx.clone(),
+                                samples.clone(),
+                            )
+                            .unwrap();
                         }
                         PlayerCmd::Volume(volume) => {
                             sink.set_volume(volume as f32 / 100.0);
@@ -284,10 +291,11 @@ impl Player {
     fn cache(url: &str) -> Result<Cursor<Vec<u8>>> {
         let agent = ureq::AgentBuilder::new().build();
         let res = agent.get(url).call()?;
+        // proxied streams may come without a length
         let len = res
             .header("Content-Length")
             .and_then(|s| s.parse::<usize>().ok())
-            .unwrap();
+            .unwrap_or_default();
         let mut bytes: Vec<u8> = Vec::with_capacity(len);
         res.into_reader().read_to_end(&mut bytes)?;
         Ok(Cursor::new(bytes))
@@ -413,3 +421,34 @@ impl PlayerTrait for Player {
         self.stop();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_cache_without_content_length() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let domain = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or_default() > 2 {
+                line.clear();
+            }
+            // like an Invidious proxy, the body ends when the connection closes
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Type: audio/mp4\r\nConnection: close\r\n\r\naudio",
+                )
+                .ok();
+        });
+        let url = format!("{domain}/latest_version?id=v1&itag=140&local=true");
+        let cursor = Player::cache(&url).unwrap();
+        assert_eq!(cursor.into_inner(), b"audio".to_vec());
+    }
+}
@@ -5,7 +5,7 @@ use std::time::Duration;
 //     collections::VecDeque,
 //     sync::atomic::{AtomicBool, AtomicUsize, Ordering},
 // };
-use crate::player::PlayerMsg;
+use crate::player::{PlayerMsg, SampleTap};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::Sender;
 
@@ -41,15 +41,17 @@ struct Controls {
 
 #[allow(unused)]
 impl Sink {
-    /// Builds a new `Sink`, beginning playback on a stream.
+    /// Builds a new `Sink`, beginning playback on a stream. What is played is
+    /// also copied to `samples`.
     #[inline]
     pub fn try_new(
         stream: &OutputStreamHandle,
         gapless_playback: bool,
         tx: Sender<PlayerMsg>,
+        samples: Arc<SampleTap>,
     ) -> Result<Self, PlayError> {
         let (sink, queue_rx) = Self::new_idle(gapless_playback, tx);
-        stream.play_raw(queue_rx)?;
+        stream.play_raw(queue_rx.tap(samples))?;
         Ok(sink)
     }
 
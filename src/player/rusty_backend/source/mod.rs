@@ -1,8 +1,10 @@
 //! Sources of sound and various filters.
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use super::Sample;
+use crate::player::SampleTap;
 
 pub use self::amplify::Amplify;
 pub use self::done::Done;
@@ -16,6 +18,7 @@ pub use self::skippable::Skippable;
 pub use self::speed::Speed;
 pub use self::stoppable::Stoppable;
 pub use self::take::TakeDuration;
+pub use self::tap::Tap;
 pub use self::uniform::UniformSourceIterator;
 pub use self::zero::Zero;
 
@@ -31,6 +34,7 @@ mod skippable;
 mod speed;
 mod stoppable;
 mod take;
+mod tap;
 mod uniform;
 mod zero;
 
@@ -178,6 +182,15 @@ where
         periodic::periodic(self, period, access)
     }
 
+    /// Copies a mono mix of the samples to `samples` as they pass.
+    #[inline]
+    fn tap(self, samples: Arc<SampleTap>) -> Tap<Self>
+    where
+        Self: Sized,
+    {
+        tap::tap(self, samples)
+    }
+
     /// Converts the samples of this source to another type.
     #[inline]
     fn convert_samples<D>(self) -> SamplesConverter<Self, D>
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::super::CpalSample;
+use super::{Sample, Source};
+use crate::player::SampleTap;
+
+/// Internal function that builds a `Tap` object.
+pub fn tap<I>(input: I, samples: Arc<SampleTap>) -> Tap<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    Tap {
+        input,
+        samples,
+        frame_sum: 0.0,
+        frame_len: 0,
+    }
+}
+
+/// Passes samples through unchanged while copying a mono mix of each frame
+/// to a `SampleTap`.
+#[derive(Clone)]
+pub struct Tap<I> {
+    input: I,
+    samples: Arc<SampleTap>,
+    frame_sum: f32,
+    frame_len: u16,
+}
+
+impl<I> Iterator for Tap<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        let value = self.input.next()?;
+        self.frame_sum += CpalSample::to_f32(&value);
+        self.frame_len += 1;
+        if self.frame_len >= self.input.channels() {
+            self.samples
+                .push(self.frame_sum / <f32 as From<u16>>::from(self.frame_len));
+            self.samples.set_sample_rate(self.input.sample_rate());
+            self.frame_sum = 0.0;
+            self.frame_len = 0;
+        }
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> Source for Tap<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    #[inline]
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn elapsed(&mut self) -> Duration {
+        self.input.elapsed()
+    }
+
+    fn seek(&mut self, time: Duration) -> Option<Duration> {
+        self.input.seek(time)
+    }
+}
@@ -0,0 +1,86 @@
+//! The latest samples sent to the output, for the visualizer.
+//!
+//! The audio thread writes and the UI thread reads without taking a lock, so
+//! a read racing a write may mix samples of two buffers. That is harmless for
+//! drawing and keeps the audio thread from ever waiting on the UI.
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+/// A ring of mono samples, oldest overwritten first.
+pub struct SampleTap {
+    samples: Box<[AtomicU32]>,
+    write: AtomicUsize,
+    sample_rate: AtomicU32,
+}
+
+impl Default for SampleTap {
+    fn default() -> Self {
+        Self::new(Self::CAPACITY)
+    }
+}
+
+impl SampleTap {
+    pub const CAPACITY: usize = 4096;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: (0..capacity.max(1)).map(|_| AtomicU32::new(0)).collect(),
+            write: AtomicUsize::new(0),
+            sample_rate: AtomicU32::new(44100),
+        }
+    }
+
+    /// Appends a sample. Only one thread should push.
+    pub fn push(&self, sample: f32) {
+        let write = self.write.load(Ordering::Relaxed);
+        self.samples[write % self.samples.len()].store(sample.to_bits(), Ordering::Relaxed);
+        self.write.store(write.wrapping_add(1), Ordering::Release);
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate.load(Ordering::Relaxed)
+    }
+
+    pub fn set_sample_rate(&self, sample_rate: u32) {
+        self.sample_rate.store(sample_rate, Ordering::Relaxed);
+    }
+
+    /// Fills `out` with the latest samples, oldest first. Slots never written
+    /// read as silence.
+    pub fn latest(&self, out: &mut [f32]) {
+        let len = self.samples.len();
+        let write = self.write.load(Ordering::Acquire);
+        let count = out.len().min(len);
+        let start = write.wrapping_sub(count);
+        out.fill(0.0);
+        let offset = out.len() - count;
+        for (i, sample) in out[offset..].iter_mut().enumerate() {
+            let index = start.wrapping_add(i) % len;
+            *sample = f32::from_bits(self.samples[index].load(Ordering::Relaxed));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_sample_tap_wraps() {
+        let tap = SampleTap::new(4);
+        let mut out = [1.0; 3];
+        tap.latest(&mut out);
+        assert_eq!(out, [0.0; 3]);
+
+        for sample in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            tap.push(sample);
+        }
+        tap.latest(&mut out);
+        assert_eq!(out, [4.0, 5.0, 6.0]);
+
+        let mut out = [1.0; 6];
+        tap.latest(&mut out);
+        assert_eq!(out, [0.0, 0.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+}
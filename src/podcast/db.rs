@@ -10,7 +10,7 @@ use rusqlite::{params, Connection};
 use semver::Version;
 use std::time::Duration;
 
-use super::{Episode, EpisodeNoId, NewEpisode, Podcast, PodcastNoId};
+use super::{Episode, EpisodeNoId, NewEpisode, Podcast, PodcastNoId, PodcastPlayback};
 
 lazy_static! {
     /// Regex for removing "A", "An", and "The" from the beginning of
@@ -82,6 +82,24 @@ impl Database {
                 }
                 Err(_) => db_conn.update_version(&curr_ver, false)?,
             }
+
+            // columns added after the table was first created
+            for (table, column, kind) in [
+                ("episodes", "chapters_url", "TEXT"),
+                ("episodes", "transcript_url", "TEXT"),
+                ("episodes", "transcript_type", "TEXT"),
+                ("podcasts", "speed", "INTEGER"),
+                ("podcasts", "skip_intro", "INTEGER"),
+                ("podcasts", "skip_outro", "INTEGER"),
+            ] {
+                if !Self::has_column(conn, table, column)? {
+                    conn.execute(
+                        &format!("ALTER TABLE {table} ADD COLUMN {column} {kind};"),
+                        params![],
+                    )
+                    .with_context(|| "Could not run database migrations.")?;
+                }
+            }
         }
 
         Ok(db_conn)
@@ -103,7 +121,10 @@ impl Database {
                 author TEXT,
                 explicit INTEGER,
                 image_url TEXT,
-                last_checked INTEGER
+                last_checked INTEGER,
+                speed INTEGER,
+                skip_intro INTEGER,
+                skip_outro INTEGER
             );",
             params![],
         )
@@ -124,6 +145,9 @@ impl Database {
                 hidden INTEGER,
                 last_position INTERGER,
                 image_url TEXT,
+                chapters_url TEXT,
+                transcript_url TEXT,
+                transcript_type TEXT,
                 FOREIGN KEY(podcast_id) REFERENCES podcasts(id) ON DELETE CASCADE
             );",
             params![],
@@ -153,6 +177,13 @@ impl Database {
         Ok(())
     }
 
+    /// Checks whether `table` already has a column called `column`.
+    fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({table});"))?;
+        let mut names = stmt.query_map(params![], |row| row.get::<_, String>("name"))?;
+        Ok(names.any(|name| matches!(name, Ok(name) if name == column)))
+    }
+
     /// If version stored in database is less than the current version
     /// of the app, this updates the value stored in the database to
     /// match.
@@ -233,8 +264,9 @@ impl Database {
 
         let mut stmt = conn.prepare_cached(
             "INSERT INTO episodes (podcast_id, title, url, guid,
-                description, pubdate, duration, played, hidden, last_position, image_url)
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);",
+                description, pubdate, duration, played, hidden, last_position, image_url,
+                chapters_url, transcript_url, transcript_type)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);",
         )?;
         stmt.execute(params![
             podcast_id,
@@ -248,6 +280,9 @@ impl Database {
             false,
             0,
             episode.image_url,
+            episode.chapters_url,
+            episode.transcript_url,
+            episode.transcript_type,
         ])?;
         Ok(conn.last_insert_rowid())
     }
@@ -398,7 +433,8 @@ impl Database {
                     let mut stmt = tx.prepare_cached(
                         "UPDATE episodes SET title = ?, url = ?,
                                 guid = ?, description = ?, pubdate = ?,
-                                duration = ? WHERE id = ?;",
+                                duration = ?, chapters_url = ?, transcript_url = ?,
+                                transcript_type = ? WHERE id = ?;",
                     )?;
                     stmt.execute(params![
                         new_ep.title,
@@ -407,6 +443,9 @@ impl Database {
                         new_ep.description,
                         new_pd,
                         new_ep.duration,
+                        new_ep.chapters_url,
+                        new_ep.transcript_url,
+                        new_ep.transcript_type,
                         id,
                     ])?;
                     update_ep.push(id);
@@ -446,6 +485,8 @@ impl Database {
             && new_ep.guid == old_ep.guid
             && new_ep.description == old_ep.description
             && new_ep.duration == old_ep.duration
+            && new_ep.chapters_url == old_ep.chapters_url
+            && new_ep.transcript_url == old_ep.transcript_url
             && pd_match)
         {
             return true;
@@ -453,6 +494,22 @@ impl Database {
         false
     }
 
+    /// Updates the playback settings of a podcast.
+    pub fn set_podcast_playback(&self, podcast_id: i64, playback: &PodcastPlayback) -> Result<()> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+
+        let mut stmt = conn.prepare_cached(
+            "UPDATE podcasts SET speed = ?, skip_intro = ?, skip_outro = ? WHERE id = ?;",
+        )?;
+        stmt.execute(params![
+            playback.speed,
+            playback.skip_intro,
+            playback.skip_outro,
+            podcast_id
+        ])?;
+        Ok(())
+    }
+
     /// Updates an episode to mark it as played or unplayed.
     pub fn set_played_status(&self, episode_id: i64, played: bool) -> Result<()> {
         let conn = self.conn.as_ref().expect("Error connecting to database.");
@@ -515,6 +572,11 @@ impl Database {
                 last_checked: convert_date(&row.get("last_checked")).unwrap(),
                 image_url: row.get("image_url")?,
                 episodes,
+                playback: PodcastPlayback {
+                    speed: row.get("speed")?,
+                    skip_intro: row.get::<&str, Option<u64>>("skip_intro")?.unwrap_or(0),
+                    skip_outro: row.get::<&str, Option<u64>>("skip_outro")?.unwrap_or(0),
+                },
             })
         })?;
         let mut podcasts = Vec::new();
@@ -563,6 +625,9 @@ impl Database {
                 played: row.get("played")?,
                 last_position: row.get("last_position")?,
                 image_url: row.get("image_url")?,
+                chapters_url: row.get("chapters_url")?,
+                transcript_url: row.get("transcript_url")?,
+                transcript_type: row.get("transcript_type")?,
             })
         })?;
         let episodes = episode_iter.flatten().collect();
@@ -2,22 +2,27 @@
 
 #[allow(unused)]
 pub mod db;
+pub mod transcript;
 
 use crate::config::Settings;
+use crate::track::Chapter;
 use crate::ui::{Msg, PCMsg};
 use crate::utils::StringUtils;
 use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use db::Database;
 use lazy_static::lazy_static;
+use lofty::{Accessor, AudioFile, FileType, ItemKey, Picture, PictureType, TaggedFileExt};
 use opml::{Body, Head, Outline, OPML};
 use regex::{Match, Regex};
 use rfc822_sanitizer::parse_from_rfc2822_with_fallback;
 use rss::{Channel, Item};
 use sanitize_filename::{sanitize_with_options, Options};
+use serde::Deserialize;
 use std::cmp::Ordering;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{
     mpsc::{self, Sender},
@@ -71,6 +76,55 @@ pub struct Podcast {
     pub last_checked: DateTime<Utc>,
     pub episodes: Vec<Episode>,
     pub image_url: Option<String>,
+    pub playback: PodcastPlayback,
+}
+
+/// Per-feed playback settings, applied when one of its episodes starts.
+/// Written and parsed as `speed=1.5 intro=30 outro=60`, where the skips
+/// are in seconds and a missing speed follows the global speed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PodcastPlayback {
+    /// Speed in tenths, like `Settings::speed`
+    pub speed: Option<i32>,
+    pub skip_intro: u64,
+    pub skip_outro: u64,
+}
+
+impl std::fmt::Display for PodcastPlayback {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(speed) = self.speed {
+            write!(f, "speed={:.1} ", speed as f32 / 10.0)?;
+        }
+        write!(f, "intro={} outro={}", self.skip_intro, self.skip_outro)
+    }
+}
+
+impl std::str::FromStr for PodcastPlayback {
+    type Err = anyhow::Error;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_str(s: &str) -> Result<Self> {
+        let mut playback = Self::default();
+        for item in s.split_whitespace() {
+            let (key, value) = item
+                .split_once('=')
+                .ok_or_else(|| anyhow!("expected key=value, got {item}"))?;
+            match key {
+                "speed" => {
+                    let speed: f32 = value.parse()?;
+                    if !(0.1..=3.0).contains(&speed) {
+                        return Err(anyhow!("speed must be between 0.1 and 3.0"));
+                    }
+                    playback.speed = Some((speed * 10.0).round() as i32);
+                }
+                "intro" => playback.skip_intro = value.parse()?,
+                "outro" => playback.skip_outro = value.parse()?,
+                _ => return Err(anyhow!("unknown setting {key}")),
+            }
+        }
+        Ok(playback)
+    }
 }
 
 impl Podcast {
@@ -152,6 +206,9 @@ pub struct Episode {
     pub played: bool,
     pub last_position: Option<i64>,
     pub image_url: Option<String>,
+    pub chapters_url: Option<String>,
+    pub transcript_url: Option<String>,
+    pub transcript_type: Option<String>,
 }
 
 impl Episode {
@@ -254,6 +311,9 @@ pub struct EpisodeNoId {
     pub pubdate: Option<DateTime<Utc>>,
     pub duration: Option<i64>,
     pub image_url: Option<String>,
+    pub chapters_url: Option<String>,
+    pub transcript_url: Option<String>,
+    pub transcript_type: Option<String>,
 }
 
 /// Struct holding data about an individual podcast episode, specifically
@@ -315,6 +375,88 @@ pub fn check_feed(
     });
 }
 
+/// Spawns a new thread to fetch the Podcasting 2.0 chapters file of the
+/// episode playing now.
+pub fn fetch_chapters(url: String, tx_to_main: Sender<Msg>) {
+    thread::spawn(move || {
+        if let Ok(chapters) = get_chapters(&url) {
+            tx_to_main
+                .send(Msg::Podcast(PCMsg::ChaptersFetched(url, chapters)))
+                .ok();
+        }
+    });
+}
+
+/// Spawns a new thread to fetch the transcript of the episode playing now.
+/// Transcripts are cached on disk, so each one is only downloaded once.
+pub fn fetch_transcript(url: String, mime: String, tx_to_main: Sender<Msg>) {
+    thread::spawn(move || {
+        if let Ok(body) = get_transcript(&url) {
+            tx_to_main
+                .send(Msg::Podcast(PCMsg::TranscriptFetched(url, mime, body)))
+                .ok();
+        }
+    });
+}
+
+fn get_transcript(url: &str) -> Result<String> {
+    let cache = transcript::transcript_cache_path(url);
+    if let Ok(body) = std::fs::read_to_string(&cache) {
+        return Ok(body);
+    }
+    let agent = ureq::builder()
+        .timeout_connect(Duration::from_secs(5))
+        .timeout_read(Duration::from_secs(20))
+        .build();
+    let body = agent.get(url).call()?.into_string()?;
+    // a failed cache write only means fetching it again next time
+    if let Some(dir) = cache.parent() {
+        std::fs::create_dir_all(dir)
+            .and_then(|()| std::fs::write(&cache, &body))
+            .ok();
+    }
+    Ok(body)
+}
+
+#[derive(Deserialize)]
+struct JsonChapters {
+    chapters: Vec<JsonChapter>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonChapter {
+    start_time: f64,
+    title: Option<String>,
+    toc: Option<bool>,
+}
+
+fn get_chapters(url: &str) -> Result<Vec<Chapter>> {
+    let agent = ureq::builder()
+        .timeout_connect(Duration::from_secs(5))
+        .timeout_read(Duration::from_secs(20))
+        .build();
+    let body = agent.get(url).call()?.into_string()?;
+    parse_chapters(&body)
+}
+
+/// Parses a JSON chapters file. Chapters marked `"toc": false` are meant
+/// to be hidden from the table of contents, so they are skipped.
+fn parse_chapters(json: &str) -> Result<Vec<Chapter>> {
+    let parsed: JsonChapters = serde_json::from_str(json)?;
+    let mut chapters: Vec<Chapter> = parsed
+        .chapters
+        .into_iter()
+        .filter(|c| c.toc != Some(false) && c.start_time >= 0.0)
+        .map(|c| Chapter {
+            title: c.title.unwrap_or_default(),
+            start: Duration::from_secs_f64(c.start_time),
+        })
+        .collect();
+    chapters.sort_by_key(|c| c.start);
+    Ok(chapters)
+}
+
 /// Given a URL, this attempts to pull the data about a podcast and its
 /// episodes from an RSS feed.
 fn get_feed_data(url: &str, mut max_retries: usize) -> Result<PodcastNoId> {
@@ -441,6 +583,34 @@ fn parse_episode_data(item: &Item) -> EpisodeNoId {
         image_url = itunes.image().map(std::string::ToString::to_string);
     }
 
+    // Podcasting 2.0 namespace: <podcast:chapters url="..." type="application/json+chapters" />
+    let chapters_url = item
+        .extensions()
+        .get("podcast")
+        .and_then(|ext| ext.get("chapters"))
+        .and_then(|chapters| chapters.first())
+        .and_then(|chapters| chapters.attrs.get("url"))
+        .cloned();
+
+    // <podcast:transcript url="..." type="text/vtt" />, a feed may offer
+    // several formats so pick the one we handle best
+    let transcript = item
+        .extensions()
+        .get("podcast")
+        .and_then(|ext| ext.get("transcript"))
+        .and_then(|transcripts| {
+            transcripts
+                .iter()
+                .filter_map(|t| {
+                    let url = t.attrs.get("url")?;
+                    let mime = t.attrs.get("type")?;
+                    Some((transcript::transcript_type_rank(mime)?, url, mime))
+                })
+                .min_by_key(|(rank, _, _)| *rank)
+        });
+    let transcript_url = transcript.map(|(_, url, _)| url.clone());
+    let transcript_type = transcript.map(|(_, _, mime)| mime.clone());
+
     EpisodeNoId {
         title,
         url,
@@ -449,6 +619,9 @@ fn parse_episode_data(item: &Item) -> EpisodeNoId {
         pubdate,
         duration,
         image_url,
+        chapters_url,
+        transcript_url,
+        transcript_type,
     }
 }
 
@@ -806,11 +979,18 @@ pub struct EpData {
     pub id: i64,
     pub pod_id: i64,
     pub title: String,
+    pub pod_title: String,
     pub url: String,
     pub pubdate: Option<DateTime<Utc>>,
+    pub image_url: Option<String>,
     pub file_path: Option<PathBuf>,
 }
 
+/// Episode ids whose downloads should be aborted. Download workers check it
+/// between chunks and stop as soon as their id shows up. An id leaves it once
+/// the result of its download is handled.
+pub type DownloadCancel = Arc<Mutex<HashSet<i64>>>;
+
 /// This is the function the main controller uses to indicate new
 /// files to download. It uses the threadpool to start jobs
 /// for every episode to be downloaded. New jobs can be requested
@@ -821,15 +1001,17 @@ pub fn download_list(
     max_retries: usize,
     threadpool: &Threadpool,
     tx_to_main: &Sender<Msg>,
+    cancel: &DownloadCancel,
 ) {
     // parse episode details and push to queue
     for ep in episodes {
         let tx = tx_to_main.clone();
         let dest2 = dest.to_path_buf();
+        let cancel = Arc::clone(cancel);
         threadpool.execute(move || {
             tx.send(Msg::Podcast(PCMsg::DLStart(ep.clone())))
                 .expect("Thread messaging error when start download");
-            let result = download_file(ep, dest2, max_retries);
+            let result = download_file(ep, &dest2, max_retries, &tx, &cancel);
             tx.send(Msg::Podcast(result))
                 .expect("Thread messaging error");
         });
@@ -838,42 +1020,25 @@ pub fn download_list(
 
 /// Downloads a file to a local filepath, returning `DownloadMsg` variant
 /// indicating success or failure.
-#[allow(clippy::single_match_else)]
-fn download_file(mut ep_data: EpData, destination_path: PathBuf, mut max_retries: usize) -> PCMsg {
+///
+/// Data is streamed into a `.part` file next to the destination, which is
+/// only renamed to the final name once the transfer is complete. If the
+/// connection drops, the request is retried with a `Range` header so the
+/// bytes already on disk are kept. A `.part` file left behind by an earlier
+/// failed attempt is resumed the same way.
+#[allow(clippy::too_many_lines)]
+fn download_file(
+    mut ep_data: EpData,
+    destination_path: &Path,
+    mut max_retries: usize,
+    tx_to_main: &Sender<Msg>,
+    cancel: &DownloadCancel,
+) -> PCMsg {
     let agent = ureq::builder()
         .timeout_connect(Duration::from_secs(10))
         .timeout_read(Duration::from_secs(120))
         .build();
 
-    let request: Result<ureq::Response, ()> = loop {
-        let response = agent.get(&ep_data.url).call();
-        match response {
-            Ok(resp) => break Ok(resp),
-            Err(_) => {
-                max_retries -= 1;
-                if max_retries == 0 {
-                    break Err(());
-                }
-            }
-        }
-    };
-
-    if request.is_err() {
-        return PCMsg::DLResponseError(ep_data);
-    };
-
-    let response = request.unwrap();
-
-    // figure out the file type
-    let ext = match response.header("content-type") {
-        Some("audio/x-m4a") => "m4a",
-        // Some("audio/mpeg") => "mp3",
-        Some("video/quicktime") => "mov",
-        Some("video/mp4") => "mp4",
-        Some("video/x-m4v") => "m4v",
-        _ => "mp3", // assume .mp3 unless we figure out otherwise
-    };
-
     let mut file_name = sanitize_with_options(
         &ep_data.title,
         Options {
@@ -887,19 +1052,415 @@ fn download_file(mut ep_data: EpData, destination_path: PathBuf, mut max_retries
         file_name = format!("{file_name}_{}", pubdate.format("%Y%m%d_%H%M%S"));
     }
 
-    let mut file_path = destination_path;
-    file_path.push(format!("{file_name}.{ext}"));
+    let part_path = destination_path.join(format!("{file_name}.part"));
+    let mut content_type = None;
 
-    let dst = File::create(&file_path);
-    if dst.is_err() {
-        return PCMsg::DLFileCreateError(ep_data);
-    };
+    loop {
+        if is_cancelled(cancel, ep_data.id) {
+            std::fs::remove_file(&part_path).ok();
+            return PCMsg::DLCancelled(ep_data);
+        }
+
+        let offset = std::fs::metadata(&part_path).map_or(0, |m| m.len());
+        let mut request = agent.get(&ep_data.url);
+        if offset > 0 {
+            request = request.set("Range", &format!("bytes={offset}-"));
+        }
+
+        let response = match request.call() {
+            Ok(resp) => resp,
+            // everything is on disk already, the previous attempt only
+            // failed to rename the file
+            Err(ureq::Error::Status(416, _)) if offset > 0 => break,
+            Err(_) => {
+                max_retries = max_retries.saturating_sub(1);
+                if max_retries == 0 {
+                    return PCMsg::DLResponseError(ep_data);
+                }
+                continue;
+            }
+        };
+
+        content_type = response.header("content-type").map(str::to_string);
+
+        // servers ignoring the range header send the whole file again
+        let resume = offset > 0 && response.status() == 206;
+        let mut downloaded = if resume { offset } else { 0 };
+        let total = content_total(&response, downloaded);
+
+        let dst = if resume {
+            OpenOptions::new().append(true).open(&part_path)
+        } else {
+            File::create(&part_path)
+        };
+        let Ok(mut dst) = dst else {
+            return PCMsg::DLFileCreateError(ep_data);
+        };
+
+        let mut reader = response.into_reader();
+        let mut buf = vec![0_u8; 64 * 1024];
+        let mut last_report = None;
+        let completed = loop {
+            if is_cancelled(cancel, ep_data.id) {
+                drop(dst);
+                std::fs::remove_file(&part_path).ok();
+                return PCMsg::DLCancelled(ep_data);
+            }
+            match reader.read(&mut buf) {
+                Ok(0) => break !matches!(total, Some(t) if downloaded < t),
+                Ok(n) => {
+                    if dst.write_all(&buf[..n]).is_err() {
+                        return PCMsg::DLFileWriteError(ep_data);
+                    }
+                    downloaded += n as u64;
+                    // only report when the shown value would change
+                    let report = match total {
+                        Some(t) if t > 0 => downloaded * 100 / t,
+                        _ => downloaded >> 20,
+                    };
+                    if last_report != Some(report) {
+                        last_report = Some(report);
+                        tx_to_main
+                            .send(Msg::Podcast(PCMsg::DLProgress(
+                                ep_data.url.clone(),
+                                downloaded,
+                                total,
+                            )))
+                            .ok();
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => break false,
+            }
+        };
+
+        if dst.flush().is_err() {
+            return PCMsg::DLFileWriteError(ep_data);
+        }
+
+        if completed {
+            break;
+        }
+
+        // connection dropped mid-transfer, try again from where we stopped
+        max_retries = max_retries.saturating_sub(1);
+        if max_retries == 0 {
+            return PCMsg::DLResponseError(ep_data);
+        }
+    }
+
+    let ext = episode_file_ext(&part_path, content_type.as_deref(), &ep_data.url);
+    let file_path = destination_path.join(format!("{file_name}.{ext}"));
+    if std::fs::rename(&part_path, &file_path).is_err() {
+        return PCMsg::DLFileWriteError(ep_data);
+    }
+
+    // the episode is on disk already, missing tags are not worth failing for
+    tag_episode_file(&file_path, &ep_data, &agent).ok();
 
     ep_data.file_path = Some(file_path);
+    PCMsg::DLComplete(ep_data)
+}
 
-    let mut reader = response.into_reader();
-    match std::io::copy(&mut reader, &mut dst.unwrap()) {
-        Ok(_) => PCMsg::DLComplete(ep_data),
-        Err(_) => PCMsg::DLFileWriteError(ep_data),
+/// Picks the extension for a downloaded episode. The container is sniffed
+/// from the data itself, as feeds often serve `application/octet-stream` or
+/// a wrong `content-type`. The header and then the url are only consulted
+/// when probing fails.
+fn episode_file_ext(path: &Path, content_type: Option<&str>, url: &str) -> &'static str {
+    let probed = lofty::Probe::open(path)
+        .and_then(|probe| Ok(probe.guess_file_type()?))
+        .ok()
+        .and_then(|probe| probe.file_type());
+    if let Some(file_type) = probed {
+        match file_type {
+            FileType::AAC => return "aac",
+            FileType::AIFF => return "aiff",
+            FileType::APE => return "ape",
+            FileType::FLAC => return "flac",
+            FileType::MPEG => return "mp3",
+            FileType::MP4 => return "m4a",
+            FileType::Opus => return "opus",
+            FileType::Vorbis => return "ogg",
+            FileType::Speex => return "spx",
+            FileType::WAV => return "wav",
+            FileType::WavPack => return "wv",
+            _ => {}
+        }
+    }
+
+    match content_type.map(|c| c.split(';').next().unwrap_or(c).trim()) {
+        Some("audio/mpeg" | "audio/mp3") => return "mp3",
+        Some("audio/x-m4a" | "audio/mp4" | "audio/m4a") => return "m4a",
+        Some("audio/aac" | "audio/aacp") => return "aac",
+        Some("audio/ogg" | "application/ogg" | "audio/vorbis") => return "ogg",
+        Some("audio/opus") => return "opus",
+        Some("audio/flac" | "audio/x-flac") => return "flac",
+        Some("video/quicktime") => return "mov",
+        Some("video/mp4") => return "mp4",
+        Some("video/x-m4v") => return "m4v",
+        _ => {}
+    }
+
+    let url_path = url.split(['?', '#']).next().unwrap_or(url);
+    match Path::new(url_path)
+        .extension()
+        .and_then(std::ffi::OsStr::to_str)
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("m4a") => "m4a",
+        Some("aac") => "aac",
+        Some("ogg" | "oga") => "ogg",
+        Some("opus") => "opus",
+        Some("flac") => "flac",
+        Some("mp4") => "mp4",
+        Some("m4v") => "m4v",
+        Some("mov") => "mov",
+        _ => "mp3", // assume .mp3 unless we figure out otherwise
+    }
+}
+
+/// Writes podcast title, episode title, pubdate and artwork into the tags
+/// of a downloaded episode, so the file is still usable outside termusic.
+fn tag_episode_file(path: &Path, ep_data: &EpData, agent: &ureq::Agent) -> Result<()> {
+    let mut tagged_file = lofty::read_from_path(path)?;
+    if tagged_file.primary_tag_mut().is_none() {
+        let tag_type = tagged_file.primary_tag_type();
+        tagged_file.insert_tag(lofty::Tag::new(tag_type));
+    }
+    let tag = tagged_file
+        .primary_tag_mut()
+        .ok_or_else(|| anyhow!("no tag for episode file"))?;
+
+    tag.set_title(ep_data.title.clone());
+    tag.set_album(ep_data.pod_title.clone());
+    tag.set_artist(ep_data.pod_title.clone());
+    tag.set_genre("Podcast".to_string());
+    if let Some(pubdate) = ep_data.pubdate {
+        tag.insert_text(
+            ItemKey::RecordingDate,
+            pubdate.format("%Y-%m-%d").to_string(),
+        );
+        if let Ok(year) = u32::try_from(pubdate.year()) {
+            tag.set_year(year);
+        }
+    }
+
+    if let Some(url) = &ep_data.image_url {
+        if let Ok(mut picture) = agent
+            .get(url)
+            .call()
+            .map_err(|e| anyhow!(e))
+            .and_then(|resp| Ok(Picture::from_reader(&mut resp.into_reader())?))
+        {
+            picture.set_pic_type(PictureType::CoverFront);
+            tag.remove_picture_type(PictureType::CoverFront);
+            tag.push_picture(picture);
+        }
+    }
+
+    tagged_file.save_to_path(path)?;
+    Ok(())
+}
+
+fn is_cancelled(cancel: &DownloadCancel, id: i64) -> bool {
+    cancel.lock().is_ok_and(|list| list.contains(&id))
+}
+
+/// Works out the full size of the file being downloaded, from either the
+/// `Content-Range` header of a partial response or the `Content-Length`
+/// of the remaining body.
+fn content_total(response: &ureq::Response, offset: u64) -> Option<u64> {
+    if let Some(range) = response.header("content-range") {
+        if let Some(total) = range.rsplit('/').next().and_then(|t| t.parse().ok()) {
+            return Some(total);
+        }
+    }
+    response
+        .header("content-length")
+        .and_then(|len| len.parse::<u64>().ok())
+        .map(|len| len + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_parse_chapters() {
+        let json = r#"{
+            "version": "1.2.0",
+            "chapters": [
+                {"startTime": 95.5, "title": "Interview"},
+                {"startTime": 0, "title": "Intro"},
+                {"startTime": 60, "title": "Sponsor", "toc": false},
+                {"startTime": 1234}
+            ]
+        }"#;
+        let chapters = parse_chapters(json).unwrap();
+        assert_eq!(
+            chapters,
+            vec![
+                Chapter {
+                    title: "Intro".to_string(),
+                    start: Duration::ZERO,
+                },
+                Chapter {
+                    title: "Interview".to_string(),
+                    start: Duration::from_millis(95_500),
+                },
+                Chapter {
+                    title: String::new(),
+                    start: Duration::from_secs(1234),
+                },
+            ]
+        );
+        assert!(parse_chapters("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_episode_chapters_url() {
+        let feed = r#"<?xml version="1.0"?>
+            <rss version="2.0" xmlns:podcast="https://podcastindex.org/namespace/1.0">
+            <channel><title>Pod</title>
+            <item><title>Ep</title>
+                <podcast:chapters url="https://example.com/ep1.json" type="application/json+chapters"/>
+            </item>
+            <item><title>No chapters</title></item>
+            </channel></rss>"#;
+        let channel = Channel::read_from(feed.as_bytes()).unwrap();
+        let episodes: Vec<EpisodeNoId> = channel.items().iter().map(parse_episode_data).collect();
+        assert_eq!(
+            episodes[0].chapters_url.as_deref(),
+            Some("https://example.com/ep1.json")
+        );
+        assert_eq!(episodes[1].chapters_url, None);
+    }
+
+    #[test]
+    fn test_podcast_playback_round_trip() {
+        let playback: PodcastPlayback = "speed=1.5 intro=30 outro=60".parse().unwrap();
+        assert_eq!(
+            playback,
+            PodcastPlayback {
+                speed: Some(15),
+                skip_intro: 30,
+                skip_outro: 60,
+            }
+        );
+        assert_eq!(playback.to_string(), "speed=1.5 intro=30 outro=60");
+
+        let playback: PodcastPlayback = "outro=5".parse().unwrap();
+        assert_eq!(playback.speed, None);
+        assert_eq!(playback.to_string(), "intro=0 outro=5");
+
+        assert!("speed=5".parse::<PodcastPlayback>().is_err());
+        assert!("volume=5".parse::<PodcastPlayback>().is_err());
+        assert!("intro".parse::<PodcastPlayback>().is_err());
+    }
+
+    /// Serves `payload` over HTTP, honouring `Range` requests. The first
+    /// connection is closed after half of the body has been sent.
+    fn serve_dropping_first(payload: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for (count, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let mut offset = 0;
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(range) = line.to_lowercase().strip_prefix("range: bytes=") {
+                        offset = range.trim().trim_end_matches('-').parse().unwrap();
+                    }
+                }
+                let len = payload.len();
+                let header = if offset > 0 {
+                    format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Type: audio/x-m4a\r\nContent-Length: {}\r\nContent-Range: bytes {offset}-{}/{len}\r\n\r\n",
+                        len - offset,
+                        len - 1
+                    )
+                } else {
+                    format!("HTTP/1.1 200 OK\r\nContent-Type: audio/x-m4a\r\nContent-Length: {len}\r\n\r\n")
+                };
+                stream.write_all(header.as_bytes()).unwrap();
+                let end = if count == 0 { len / 2 } else { len };
+                stream.write_all(&payload[offset..end]).ok();
+            }
+        });
+        format!("http://{addr}/episode")
+    }
+
+    #[test]
+    fn test_episode_file_ext() {
+        let dir = std::env::temp_dir().join(format!("termusic-ext-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("episode.part");
+
+        // sniffed content wins over a generic content type
+        std::fs::write(&path, b"fLaC\0\0\0\x22").unwrap();
+        let ext = episode_file_ext(&path, Some("application/octet-stream"), "http://a/b.mp3");
+        assert_eq!(ext, "flac");
+
+        std::fs::write(&path, b"not audio").unwrap();
+        let ext = episode_file_ext(&path, Some("audio/ogg; codecs=opus"), "http://a/b");
+        assert_eq!(ext, "ogg");
+        let ext = episode_file_ext(
+            &path,
+            Some("application/octet-stream"),
+            "http://a/b.opus?c=d",
+        );
+        assert_eq!(ext, "opus");
+        let ext = episode_file_ext(&path, None, "http://a/b");
+        assert_eq!(ext, "mp3");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_download_resumes_after_dropped_connection() {
+        let payload: Vec<u8> = (0..300_000_u32).map(|i| (i % 251) as u8).collect();
+        let url = serve_dropping_first(payload.clone());
+        let dir = std::env::temp_dir().join(format!("termusic-dl-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let ep_data = EpData {
+            id: 1,
+            pod_id: 1,
+            title: "episode".to_string(),
+            pod_title: "podcast".to_string(),
+            url,
+            pubdate: None,
+            image_url: None,
+            file_path: None,
+        };
+        let (tx, rx) = mpsc::channel();
+        let cancel = DownloadCancel::default();
+        let result = download_file(ep_data, &dir, 3, &tx, &cancel);
+
+        let PCMsg::DLComplete(ep_data) = result else {
+            panic!("download did not complete");
+        };
+        let file_path = ep_data.file_path.unwrap();
+        assert_eq!(file_path, dir.join("episode.m4a"));
+        assert_eq!(std::fs::read(&file_path).unwrap(), payload);
+        assert!(!dir.join("episode.part").exists());
+
+        let last = rx.try_iter().last();
+        let Some(Msg::Podcast(PCMsg::DLProgress(_, downloaded, total))) = last else {
+            panic!("no progress reported");
+        };
+        assert_eq!(downloaded, payload.len() as u64);
+        assert_eq!(total, Some(payload.len() as u64));
+        std::fs::remove_dir_all(&dir).ok();
     }
 }
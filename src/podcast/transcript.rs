@@ -0,0 +1,246 @@
+//! Podcasting 2.0 transcripts (`<podcast:transcript>`).
+//!
+//! Timed formats (SRT, `WebVTT` and JSON) are turned into a [`Lyric`] so the
+//! lyric panel can show them line by line. HTML and plain text transcripts
+//! carry no timing and are kept as text.
+use crate::songtag::lrc::Lyric;
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+lazy_static! {
+    /// Regex for finding HTML tags, and WebVTT voice / class spans
+    static ref RE_TAGS: Regex = Regex::new(r"<[^<>]*>").expect("Regex error");
+}
+
+/// Transcript types in order of preference when a feed offers several.
+const TRANSCRIPT_TYPES: [&str; 7] = [
+    "application/srt",
+    "application/x-subrip",
+    "text/srt",
+    "text/vtt",
+    "application/json",
+    "text/html",
+    "text/plain",
+];
+
+#[derive(Clone)]
+pub enum Transcript {
+    Timed(Lyric),
+    Text(String),
+}
+
+/// Ranks a transcript mime type, lower is better. Unknown types return `None`.
+pub fn transcript_type_rank(mime: &str) -> Option<usize> {
+    let mime = mime.split(';').next().unwrap_or_default().trim();
+    TRANSCRIPT_TYPES
+        .iter()
+        .position(|t| t.eq_ignore_ascii_case(mime))
+}
+
+/// Parses a transcript body according to its mime type.
+pub fn parse_transcript(body: &str, mime: &str) -> Result<Transcript> {
+    let body = body.trim_start_matches('\u{feff}');
+    let captions = match transcript_type_rank(mime) {
+        Some(0..=3) => parse_cues(body),
+        Some(4) => parse_json(body)?,
+        Some(5) => return Ok(Transcript::Text(strip_tags(body))),
+        Some(_) => return Ok(Transcript::Text(body.to_string())),
+        None => return Err(anyhow!("unsupported transcript type: {mime}")),
+    };
+    if captions.is_empty() {
+        return Err(anyhow!("transcript has no captions"));
+    }
+    Ok(Transcript::Timed(Lyric::from_captions(captions)))
+}
+
+/// Parses SRT and `WebVTT`. Both are blocks separated by blank lines, where
+/// the cue timing line is the one containing `-->`, followed by the text.
+fn parse_cues(body: &str) -> Vec<(Duration, Duration, String)> {
+    let body = body.replace("\r\n", "\n");
+    let mut captions = Vec::new();
+    for block in body.split("\n\n") {
+        let mut lines = block.lines().skip_while(|l| !l.contains("-->"));
+        let Some(timing) = lines.next() else {
+            continue;
+        };
+        let mut times = timing.split("-->");
+        let Some(start) = times.next().and_then(parse_timestamp) else {
+            continue;
+        };
+        // WebVTT puts cue settings after the end time
+        let end = times
+            .next()
+            .and_then(|end| end.split_whitespace().next())
+            .and_then(parse_timestamp)
+            .unwrap_or(start);
+        let text = lines
+            .map(|l| strip_tags(l).trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !text.is_empty() {
+            captions.push((start, end, text));
+        }
+    }
+    captions
+}
+
+/// Parses `HH:MM:SS,mmm` (SRT) and `[HH:]MM:SS.mmm` (`WebVTT`).
+fn parse_timestamp(s: &str) -> Option<Duration> {
+    let s = s.trim().replace(',', ".");
+    let mut secs = 0.0;
+    for part in s.split(':') {
+        secs = secs * 60.0 + part.parse::<f64>().ok()?;
+    }
+    (secs >= 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
+#[derive(Deserialize)]
+struct JsonTranscript {
+    segments: Vec<JsonSegment>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSegment {
+    start_time: f64,
+    end_time: Option<f64>,
+    body: String,
+}
+
+fn parse_json(body: &str) -> Result<Vec<(Duration, Duration, String)>> {
+    let parsed: JsonTranscript = serde_json::from_str(body)?;
+    Ok(parsed
+        .segments
+        .into_iter()
+        .filter(|s| s.start_time >= 0.0 && !s.body.trim().is_empty())
+        .map(|s| {
+            let end = s.end_time.filter(|end| *end >= s.start_time);
+            (
+                Duration::from_secs_f64(s.start_time),
+                Duration::from_secs_f64(end.unwrap_or(s.start_time)),
+                s.body.trim().to_string(),
+            )
+        })
+        .collect())
+}
+
+fn strip_tags(s: &str) -> String {
+    let stripped = RE_TAGS.replace_all(s, "");
+    escaper::decode_html(&stripped).unwrap_or_else(|_| stripped.to_string())
+}
+
+/// Where the transcript of `url` is cached, so that it is only fetched once.
+pub fn transcript_cache_path(url: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    let mut path = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+    path.push("termusic");
+    path.push("transcripts");
+    path.push(format!("{:016x}", hasher.finish()));
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Lines of a timed transcript, as (start in seconds, text).
+    fn lines(transcript: &Transcript) -> Vec<(i64, String)> {
+        let Transcript::Timed(lyric) = transcript else {
+            panic!("transcript is not timed");
+        };
+        let mut lines = Vec::new();
+        let mut time = -1;
+        while let Some(start) = lyric.find_text("", time).filter(|s| *s > time) {
+            lines.push((start, lyric.get_text(start - 2).unwrap_or_default()));
+            time = start;
+        }
+        lines
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let srt = "1\r\n00:00:01,500 --> 00:00:04,000\r\nHello and welcome\r\nto the show\r\n\r\n\
+                   2\r\n01:02:03,000 --> 01:02:05,000\r\n<i>Goodbye</i>\r\n";
+        let transcript = parse_transcript(srt, "application/srt").unwrap();
+        assert_eq!(
+            lines(&transcript),
+            vec![
+                (1, "Hello and welcome to the show".to_string()),
+                (3723, "Goodbye".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_overlapping_cues() {
+        let srt = "1\n00:00:01,000 --> 00:00:03,000\nFirst\n\n\
+                   2\n00:00:02,000 --> 00:00:04,000\nOverlaps\n\n\
+                   3\n00:00:04,500 --> 00:00:05,000\nClose after\n";
+        let Transcript::Timed(lyric) = parse_transcript(srt, "application/srt").unwrap() else {
+            panic!("srt transcript should be timed");
+        };
+        let captions: Vec<(i64, &str)> = lyric
+            .unsynced_captions
+            .iter()
+            .map(|c| (c.time_stamp(), c.text()))
+            .collect();
+        assert_eq!(
+            captions,
+            vec![(1000, "First  Overlaps"), (4500, "Close after")]
+        );
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let vtt =
+            "WEBVTT\n\nintro\n00:05.250 --> 00:07.000 align:start\n<v Alice>Hi &amp; hello</v>\n\n\
+                   00:01:10.000 --> 00:01:12.000\nSecond cue\n";
+        let transcript = parse_transcript(vtt, "text/vtt").unwrap();
+        assert_eq!(
+            lines(&transcript),
+            vec![
+                (5, "Hi & hello".to_string()),
+                (70, "Second cue".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_and_html() {
+        let json = r#"{"version": "1.0.0", "segments": [
+            {"speaker": "Alice", "startTime": 0.5, "endTime": 1.0, "body": "First"},
+            {"startTime": 10, "endTime": 11, "body": "Second"}
+        ]}"#;
+        let transcript = parse_transcript(json, "application/json").unwrap();
+        assert_eq!(
+            lines(&transcript),
+            vec![(0, "First".to_string()), (10, "Second".to_string())]
+        );
+
+        let Transcript::Text(text) =
+            parse_transcript("<p>Alice: <b>hi</b></p>", "text/html").unwrap()
+        else {
+            panic!("html transcript should be untimed");
+        };
+        assert_eq!(text, "Alice: hi");
+        assert!(parse_transcript("", "application/pdf").is_err());
+    }
+
+    #[test]
+    fn test_transcript_type_rank() {
+        assert!(
+            transcript_type_rank("text/vtt").unwrap()
+                < transcript_type_rank("text/html; charset=utf-8").unwrap()
+        );
+        assert_eq!(transcript_type_rank("audio/mpeg"), None);
+    }
+}
@@ -24,6 +24,7 @@
 mod model;
 
 use super::encrypt::Crypto;
+use super::{LyricProvider, Query, SongTag};
 use anyhow::{anyhow, bail, Result};
 use lofty::Picture;
 use model::{to_lyric, to_lyric_id_accesskey, to_pic_url, to_song_info, to_song_url};
@@ -148,3 +149,38 @@ impl Api {
         Ok(picture)
     }
 }
+
+impl LyricProvider for Api {
+    fn search_tags(&mut self, query: &Query) -> Result<Vec<SongTag>> {
+        let results = self.search(&query.text, 1, 0, 30)?;
+        Ok(serde_json::from_str(&results)?)
+    }
+
+    fn lyric(&mut self, song_tag: &SongTag) -> Result<String> {
+        match &song_tag.lyric_id {
+            Some(lyric_id) => self.song_lyric(lyric_id),
+            None => Ok(String::new()),
+        }
+    }
+
+    fn picture(&mut self, song_tag: &SongTag) -> Result<Picture> {
+        let pic_id = song_tag
+            .pic_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("pic_id is missing for kugou"))?;
+        let album_id = song_tag
+            .album_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("album_id is missing for kugou"))?;
+        self.pic(pic_id, album_id)
+    }
+
+    fn download_url(&mut self, song_tag: &SongTag) -> Result<String> {
+        let song_id = song_tag
+            .song_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("error downloading because no song id is found"))?;
+        let album_id = song_tag.album_id.as_deref().unwrap_or("N/A");
+        self.song_url(song_id, album_id)
+    }
+}
@@ -0,0 +1,97 @@
+//! Lyrics kept in `.lrc` or `.txt` files next to the music, or in a folder
+//! of their own.
+use super::{LyricProvider, Query, ServiceProvider, SongTag};
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+pub struct Api;
+
+impl LyricProvider for Api {
+    fn search_tags(&mut self, query: &Query) -> Result<Vec<SongTag>> {
+        let Some(file) = &query.file else {
+            return Ok(Vec::new());
+        };
+        let tags = lyric_files(Path::new(file), &query.lyric_dir)
+            .into_iter()
+            .map(|path| SongTag {
+                artist: None,
+                title: None,
+                album: None,
+                lang_ext: path.extension().map(|e| e.to_string_lossy().to_string()),
+                service_provider: Some(ServiceProvider::Local),
+                song_id: None,
+                lyric_id: Some(path.to_string_lossy().to_string()),
+                url: Some(path.to_string_lossy().to_string()),
+                pic_id: None,
+                album_id: None,
+            })
+            .collect();
+        Ok(tags)
+    }
+
+    fn lyric(&mut self, song_tag: &SongTag) -> Result<String> {
+        match &song_tag.lyric_id {
+            Some(path) => Ok(std::fs::read_to_string(path)?),
+            None => Ok(String::new()),
+        }
+    }
+
+    fn download_url(&mut self, _song_tag: &SongTag) -> Result<String> {
+        bail!("Local lyrics have no song to download, please select another item.")
+    }
+}
+
+/// `<name>.lrc` and `<name>.txt` for the music file `<name>.<ext>`, looked up
+/// next to it first and then in `lyric_dir`.
+pub fn lyric_files(file: &Path, lyric_dir: &str) -> Vec<PathBuf> {
+    let Some(stem) = file.file_stem() else {
+        return Vec::new();
+    };
+    let mut dirs = Vec::new();
+    if let Some(parent) = file.parent() {
+        dirs.push(parent.to_path_buf());
+    }
+    if !lyric_dir.is_empty() {
+        dirs.push(PathBuf::from(shellexpand::tilde(lyric_dir).as_ref()));
+    }
+
+    let mut files = Vec::new();
+    for dir in dirs {
+        for ext in ["lrc", "txt"] {
+            // not with_extension, names like "01. Intro" have a dot already
+            let mut name = stem.to_os_string();
+            name.push(".");
+            name.push(ext);
+            let path = dir.join(name);
+            if path.is_file() && !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_lyric_files() {
+        let dir = std::env::temp_dir().join(format!("termusic-lyrics-{}", std::process::id()));
+        let lyric_dir = dir.join("lyrics");
+        std::fs::create_dir_all(&lyric_dir).unwrap();
+        let music = dir.join("01. Intro.mp3");
+        assert!(lyric_files(&music, "").is_empty());
+
+        std::fs::write(lyric_dir.join("01. Intro.lrc"), "").unwrap();
+        std::fs::write(dir.join("01. Intro.txt"), "").unwrap();
+        std::fs::write(dir.join("01.lrc"), "").unwrap();
+        assert_eq!(lyric_files(&music, ""), vec![dir.join("01. Intro.txt")]);
+        assert_eq!(
+            lyric_files(&music, &lyric_dir.to_string_lossy()),
+            vec![dir.join("01. Intro.txt"), lyric_dir.join("01. Intro.lrc")]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -40,33 +40,110 @@ use anyhow::Result;
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::cmp::Ordering;
+use std::fmt::Write;
 use std::str::FromStr;
 use std::time::Duration;
 
 lazy_static! {
-    static ref LYRICS_RE: Regex = Regex::new("^[^\x00-\x08\x0A-\x1F\x7F]*$").unwrap();
-    static ref TAG_RE: Regex = Regex::new(r"\[.*:.*\]").unwrap();
-    static ref LINE_STARTS_WITH_RE: Regex =
-        Regex::new("^\\[([^\x00-\x08\x0A-\x1F\x7F\\[\\]:]*):([^\x00-\x08\x0A-\x1F\x7F\\[\\]]*)\\]")
-            .unwrap();
+    /// ID tags like `[ar:Chubby Checker]`, as opposed to time stamps
+    static ref ID_TAG_RE: Regex = Regex::new(r"^\[([A-Za-z#]+):(.*)\]$").unwrap();
+    /// Enhanced LRC word time stamps like `<00:12.50>`
+    static ref WORD_TIME_RE: Regex = Regex::new(r"<(\d+:\d+(?:[.:]\d+)?)>").unwrap();
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Lyric {
-    pub offset: i64, // positive means delay lyric
+    /// In milliseconds, positive shows the lyrics earlier
+    pub offset: i64,
     pub lang_extension: Option<String>,
+    /// ID tags such as `ar` or `ti` in the order they appear, without `offset`
+    pub tags: Vec<(String, String)>,
     pub unsynced_captions: Vec<UnsyncedCaption>, // USLT captions
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UnsyncedCaption {
     time_stamp: i64,
     text: String,
+    /// Word time stamps from enhanced LRC, empty for plain lines
+    words: Vec<Word>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Word {
+    time_stamp: i64,
+    text: String,
 }
 
 const EOL: &str = "\n";
 
 impl Lyric {
+    /// Builds a lyric from captions that are already timed, such as a
+    /// podcast transcript, given as start, end and text. Captions that
+    /// overlap share a line, as only one line shows at a time.
+    pub fn from_captions(mut captions: Vec<(Duration, Duration, String)>) -> Self {
+        captions.sort_by_key(|(start, _, _)| *start);
+        let mut merged: Vec<(Duration, Duration, String)> = Vec::new();
+        for (start, end, text) in captions {
+            match merged.last_mut() {
+                Some((_, last_end, last_text)) if start < *last_end => {
+                    *last_end = (*last_end).max(end);
+                    *last_text += "  ";
+                    *last_text += &text;
+                }
+                _ => merged.push((start, end, text)),
+            }
+        }
+        let unsynced_captions = merged
+            .into_iter()
+            .map(|(start, _, text)| UnsyncedCaption {
+                time_stamp: start.as_millis().try_into().unwrap_or(i64::MAX),
+                text,
+                words: Vec::new(),
+            })
+            .collect();
+
+        Self {
+            offset: 0,
+            lang_extension: None,
+            tags: Vec::new(),
+            unsynced_captions,
+        }
+    }
+
+    /// The value of an ID tag like `ar` or `ti`.
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The text of the caption starting within half a second of
+    /// `time_stamp` milliseconds, to pair the lines of a translation with
+    /// the original ones.
+    pub fn text_at(&self, time_stamp: i64) -> Option<&str> {
+        self.unsynced_captions
+            .iter()
+            .find(|c| (c.time_stamp - time_stamp).abs() < 500)
+            .map(|c| c.text.as_str())
+    }
+
+    /// Finds the first caption containing `phrase` (case insensitive) that
+    /// starts after `time` seconds, wrapping around to the beginning.
+    /// Returns its start in seconds.
+    pub fn find_text(&self, phrase: &str, time: i64) -> Option<i64> {
+        let phrase = phrase.to_lowercase();
+        let start = |c: &UnsyncedCaption| (c.time_stamp - self.offset).max(0) / 1000;
+        let matches = |c: &&UnsyncedCaption| c.text.to_lowercase().contains(&phrase);
+        self.unsynced_captions
+            .iter()
+            .filter(|c| start(c) > time)
+            .find(matches)
+            .or_else(|| self.unsynced_captions.iter().find(matches))
+            .map(start)
+    }
+
     // GetText will fetch lyric by time in seconds
     pub fn get_text(&self, mut time: i64) -> Option<String> {
         if self.unsynced_captions.is_empty() {
@@ -93,6 +170,23 @@ impl Lyric {
         Some(text)
     }
 
+    /// The words of the line shown at `time` seconds, each with whether it
+    /// has been sung yet. `None` when the line has no word time stamps.
+    pub fn get_words(&self, time: i64) -> Option<Vec<(&str, bool)>> {
+        let caption = self.unsynced_captions.get(self.get_index(time)?)?;
+        if caption.words.is_empty() {
+            return None;
+        }
+        let time = time * 1000 + self.offset;
+        Some(
+            caption
+                .words
+                .iter()
+                .map(|w| (w.text.as_str(), w.time_stamp <= time))
+                .collect(),
+        )
+    }
+
     pub fn get_index(&self, mut time: i64) -> Option<usize> {
         if self.unsynced_captions.is_empty() {
             return None;
@@ -126,12 +220,17 @@ impl Lyric {
                 self.offset -= offset;
             } else {
                 // fine tuning each line after 10 seconds
-                let mut v = &mut self.unsynced_captions[index];
-                let adjusted_time_stamp = v.time_stamp + offset;
-                v.time_stamp = match adjusted_time_stamp.cmp(&0) {
-                    Ordering::Greater | Ordering::Equal => adjusted_time_stamp,
+                let v = &mut self.unsynced_captions[index];
+                let adjusted_time_stamp = match (v.time_stamp + offset).cmp(&0) {
+                    Ordering::Greater | Ordering::Equal => v.time_stamp + offset,
                     Ordering::Less => 0,
                 };
+                // the words move along with their line
+                let shift = adjusted_time_stamp - v.time_stamp;
+                for word in &mut v.words {
+                    word.time_stamp = (word.time_stamp + shift).max(0);
+                }
+                v.time_stamp = adjusted_time_stamp;
             }
         };
         // we sort the captions by time_stamp. This is to fix some lyrics downloaded are not sorted
@@ -141,8 +240,11 @@ impl Lyric {
 
     pub fn as_lrc_text(&self) -> String {
         let mut result: String = String::new();
+        for (key, value) in &self.tags {
+            result += format!("[{key}:{value}]{EOL}").as_ref();
+        }
         if self.offset != 0 {
-            let string_offset = format!("[offset:{}]\n", self.offset);
+            let string_offset = format!("[offset:{}]{EOL}", self.offset);
             result += string_offset.as_ref();
         }
 
@@ -161,8 +263,17 @@ impl Lyric {
             }
             if let Some(item) = unsynced_captions.get(i - offset) {
                 if v.time_stamp - item.time_stamp < 2000 {
-                    unsynced_captions[i - offset].text += "  ";
-                    unsynced_captions[i - offset].text += v.text.as_ref();
+                    let merged = &mut unsynced_captions[i - offset];
+                    if !merged.words.is_empty() || !v.words.is_empty() {
+                        let mut words = merged.words_or_line();
+                        if let Some(last) = words.last_mut() {
+                            last.text += "  ";
+                        }
+                        words.extend(v.words_or_line());
+                        merged.words = words;
+                    }
+                    merged.text += "  ";
+                    merged.text += v.text.as_ref();
                     unsynced_captions.remove(i - offset + 1);
                     offset += 1;
                 }
@@ -174,66 +285,136 @@ impl Lyric {
 }
 
 impl UnsyncedCaption {
-    fn parse_line(line: &mut String) -> Result<Self, ()> {
-        //[00:12.00]Line 1 lyrics
-        // !line.starts_with('[') | !line.contains(']')
-        // First, parse the time
-        let time_stamp = Self::parse_time(
-            line.get(line.find('[').ok_or(())? + 1..line.find(']').ok_or(())?)
-                .ok_or(())?,
-        )?;
-        let text = line
-            .drain(line.find(']').ok_or(())? + 1..)
-            .collect::<String>();
-        Ok(Self {
-            time_stamp: time_stamp.try_into().unwrap_or(0),
-            text,
-        })
+    pub const fn time_stamp(&self) -> i64 {
+        self.time_stamp
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Parses `[00:12.00]Line 1 lyrics`, with any number of time stamps in
+    /// front and optionally `<00:12.50>` word time stamps in the text. Gives
+    /// one caption per line time stamp.
+    fn parse_line(line: &str) -> Result<Vec<Self>, ()> {
+        let mut time_stamps = Vec::new();
+        let mut rest = line;
+        while let Some(stamp) = rest.strip_prefix('[') {
+            let end = stamp.find(']').ok_or(())?;
+            let Ok(time_stamp) = Self::parse_time(&stamp[..end]) else {
+                break;
+            };
+            time_stamps.push(i64::try_from(time_stamp).unwrap_or(0));
+            rest = &stamp[end + 1..];
+        }
+        let first = *time_stamps.first().ok_or(())?;
+
+        let mut text = String::new();
+        let mut words = Vec::new();
+        let mut word_start = first;
+        let mut segment_start = 0;
+        for capture in WORD_TIME_RE.captures_iter(rest) {
+            let (Some(tag), Some(time)) = (capture.get(0), capture.get(1)) else {
+                continue;
+            };
+            let segment = &rest[segment_start..tag.start()];
+            if segment_start > 0 || !segment.is_empty() {
+                words.push(Word {
+                    time_stamp: word_start,
+                    text: segment.to_string(),
+                });
+            }
+            text += segment;
+            word_start = i64::try_from(Self::parse_time(time.as_str())?).unwrap_or(0);
+            segment_start = tag.end();
+        }
+        let segment = &rest[segment_start..];
+        if segment_start > 0 {
+            words.push(Word {
+                time_stamp: word_start,
+                text: segment.to_string(),
+            });
+        }
+        text += segment;
+
+        // word time stamps are for the first time the line is sung, the
+        // lines repeated at other times get them moved along
+        Ok(time_stamps
+            .into_iter()
+            .map(|time_stamp| Self {
+                time_stamp,
+                text: text.clone(),
+                words: words
+                    .iter()
+                    .map(|w| Word {
+                        time_stamp: w.time_stamp + time_stamp - first,
+                        text: w.text.clone(),
+                    })
+                    .collect(),
+            })
+            .collect())
     }
 
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn parse_time(string: &str) -> Result<u64, ()> {
-        //mm:ss.xx or mm:ss.xxx
-        if !(string.contains(':')) | !(string.contains('.')) {
+        //mm:ss, mm:ss.xx, mm:ss.xxx or mm:ss:xx
+        let (minute, rest) = string.split_once(':').ok_or(())?;
+        let (second, fraction) = rest.split_once(['.', ':']).unwrap_or((rest, "0"));
+        let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if !is_number(minute) || !is_number(second) || !is_number(fraction) {
             return Err(());
         }
-        let (x, y) = (string.find(':').ok_or(())?, string.find('.').ok_or(())?);
-        let minute = string.get(0..x).ok_or(())?.parse::<u32>().map_err(|_| ())?;
-        let second = string
-            .get(x + 1..y)
-            .ok_or(())?
-            .parse::<u32>()
-            .map_err(|_| ())?;
-        let micros = &format!("0.{}", string.get(y + 1..).ok_or(())?)
-            .parse::<f64>()
-            .map_err(|_| ())?;
-        // let secs_u64 = u64::try_from(micros * 1000.0).ok();
-        // let secs = u64::from((micros * 1000.0).round());
-        let sum_milis = u64::from(minute) * 60 * 1000
-            + u64::from(second) * 1000
-            + (micros * 1000.0).abs() as u64;
-        Ok(sum_milis)
+        let minute = minute.parse::<u64>().map_err(|_| ())?;
+        let second = second.parse::<u64>().map_err(|_| ())?;
+        let fraction = format!("0.{fraction}").parse::<f64>().map_err(|_| ())?;
+        minute
+            .checked_mul(60 * 1000)
+            .and_then(|ms| ms.checked_add(second.checked_mul(1000)?))
+            .and_then(|ms| ms.checked_add((fraction * 1000.0).round() as u64))
+            .ok_or(())
+    }
+
+    /// The words, or the whole line as one word when it has no word timing.
+    fn words_or_line(&self) -> Vec<Word> {
+        if self.words.is_empty() {
+            return vec![Word {
+                time_stamp: self.time_stamp,
+                text: self.text.clone(),
+            }];
+        }
+        self.words.clone()
     }
 
     fn as_lrc(&self) -> String {
-        let line = format!(
-            "[{}]{}",
-            time_lrc(self.time_stamp.try_into().unwrap_or(0)),
-            self.text
-        );
+        let mut line = format!("[{}]", time_lrc(self.time_stamp.try_into().unwrap_or(0)));
+        if self.words.is_empty() {
+            line += &self.text;
+        }
+        for word in &self.words {
+            write!(
+                line,
+                "<{}>{}",
+                time_lrc(word.time_stamp.try_into().unwrap_or(0)),
+                word.text
+            )
+            .ok();
+        }
         line + EOL
     }
 }
 
+/// `mm:ss.xx`, or `mm:ss.xxx` when hundredths aren't precise enough.
+/// Minutes go past 59 rather than adding hours, as LRC has no hours.
 fn time_lrc(time_stamp: u64) -> String {
     let time_duration = Duration::from_millis(time_stamp);
-    let _h = time_duration.as_secs() / 3600;
-    let m = (time_duration.as_secs() / 60) % 60;
+    let m = time_duration.as_secs() / 60;
     let s = time_duration.as_secs() % 60;
-    let ms = time_duration.as_millis() % 60;
+    let ms = time_duration.subsec_millis();
 
-    let res = format!("{m:02}:{s:02}.{ms:02}");
-    res
+    match ms % 10 {
+        0 => format!("{m:02}:{s:02}.{:02}", ms / 10),
+        _ => format!("{m:02}:{s:02}.{ms:03}"),
+    }
 }
 
 impl FromStr for Lyric {
@@ -241,40 +422,31 @@ impl FromStr for Lyric {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // s = cleanLRC(s)
-        // lines := strings.Split(s, "\n")
         let mut offset: i64 = 0;
         let lang_extension = Some(String::new());
+        let mut tags = Vec::new();
         let mut unsynced_captions = vec![];
-        for line in s.split('\n') {
-            let mut line = line.to_string();
-            if line.ends_with('\n') {
-                line.pop();
-                if line.ends_with('\r') {
-                    line.pop();
-                }
-            }
+        for line in s.lines() {
             let line = line.trim();
-            let mut line = line.to_string();
             if line.is_empty() {
                 continue;
             }
 
-            if line.starts_with("[offset") {
-                let line = line.trim_start_matches("[offset:");
-                let line = line.trim_end_matches(']');
-                let line = line.replace(' ', "");
-                if let Ok(o) = line.parse() {
-                    offset = o;
+            if let Some(captures) = ID_TAG_RE.captures(line) {
+                let key = captures[1].to_lowercase();
+                let value = captures[2].trim();
+                if key == "offset" {
+                    if let Ok(o) = value.replace(' ', "").parse() {
+                        offset = o;
+                    }
+                } else {
+                    tags.push((key, value.to_string()));
                 }
-            }
-
-            if !LINE_STARTS_WITH_RE.is_match(line.as_ref()) {
                 continue;
             }
 
-            if let Ok(s) = UnsyncedCaption::parse_line(&mut line) {
-                unsynced_captions.push(s);
+            if let Ok(captions) = UnsyncedCaption::parse_line(line) {
+                unsynced_captions.extend(captions);
             };
         }
 
@@ -284,6 +456,7 @@ impl FromStr for Lyric {
         let mut lyric = Self {
             offset,
             lang_extension,
+            tags,
             unsynced_captions,
         };
 
@@ -292,3 +465,76 @@ impl FromStr for Lyric {
         Ok(lyric)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const ENHANCED: &str = "[ti:Let's Twist Again]
+[ar:Chubby Checker]
+[offset:+500]
+[00:12.00][01:02.50]Come on <00:13.00>let's <00:14.25>twist
+[00:20.00]Plain line
+[61:05.123]Late line
+";
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(UnsyncedCaption::parse_time("01:02"), Ok(62_000));
+        assert_eq!(UnsyncedCaption::parse_time("01:02.5"), Ok(62_500));
+        assert_eq!(UnsyncedCaption::parse_time("01:02:50"), Ok(62_500));
+        assert_eq!(UnsyncedCaption::parse_time("01:02.345"), Ok(62_345));
+        assert_eq!(UnsyncedCaption::parse_time("ar:02.00"), Err(()));
+        assert_eq!(UnsyncedCaption::parse_time("01:02."), Err(()));
+        assert_eq!(UnsyncedCaption::parse_time("99999999999999999:00"), Err(()));
+        assert_eq!(time_lrc(62_500), "01:02.50");
+        assert_eq!(time_lrc(3_665_123), "61:05.123");
+    }
+
+    #[test]
+    fn test_enhanced_lrc() {
+        let lyric = Lyric::from_str(ENHANCED).unwrap();
+        assert_eq!(lyric.offset, 500);
+        assert_eq!(lyric.tag("ti"), Some("Let's Twist Again"));
+        assert_eq!(lyric.tag("ar"), Some("Chubby Checker"));
+        assert_eq!(lyric.unsynced_captions.len(), 4);
+        assert_eq!(lyric.get_text(11), Some("Come on let's twist".to_string()));
+        assert_eq!(
+            lyric.get_words(13),
+            Some(vec![("Come on ", true), ("let's ", true), ("twist", false)])
+        );
+        assert_eq!(
+            lyric.get_words(63),
+            Some(vec![("Come on ", true), ("let's ", true), ("twist", false)])
+        );
+        assert_eq!(lyric.get_words(20), None);
+        assert_eq!(lyric.find_text("late", 0), Some(3664));
+        assert_eq!(lyric.text_at(20_300), Some("Plain line"));
+        assert_eq!(lyric.text_at(21_000), None);
+    }
+
+    #[test]
+    fn test_as_lrc_text_round_trip() {
+        let lyric = Lyric::from_str(ENHANCED).unwrap();
+        let text = lyric.as_lrc_text();
+        assert_eq!(
+            text,
+            "[ti:Let's Twist Again]
+[ar:Chubby Checker]
+[offset:500]
+[00:12.00]<00:12.00>Come on <00:13.00>let's <00:14.25>twist
+[00:20.00]Plain line
+[01:02.50]<01:02.50>Come on <01:03.50>let's <01:04.75>twist
+[61:05.123]Late line
+"
+        );
+        assert_eq!(Lyric::from_str(&text).unwrap(), lyric);
+
+        let mut adjusted = lyric;
+        adjusted.adjust_offset(63, 1000);
+        let text = adjusted.as_lrc_text();
+        assert!(text.contains("[01:03.50]<01:03.50>Come on <01:04.50>let's"));
+        assert_eq!(Lyric::from_str(&text).unwrap(), adjusted);
+    }
+}
@@ -23,6 +23,7 @@
  */
 mod model;
 
+use super::{LyricProvider, Query, SongTag};
 use anyhow::{anyhow, Result};
 use lofty::Picture;
 use model::{to_lyric, to_pic_url, to_song_info};
@@ -106,3 +107,25 @@ impl Api {
         // Ok(bytes)
     }
 }
+
+impl LyricProvider for Api {
+    fn search_tags(&mut self, query: &Query) -> Result<Vec<SongTag>> {
+        let results = self.search(&query.text, 1, 0, 30)?;
+        Ok(serde_json::from_str(&results)?)
+    }
+
+    fn lyric(&mut self, song_tag: &SongTag) -> Result<String> {
+        match &song_tag.lyric_id {
+            Some(lyric_id) => self.song_lyric(lyric_id),
+            None => Ok(String::new()),
+        }
+    }
+
+    fn picture(&mut self, song_tag: &SongTag) -> Result<Picture> {
+        let song_id = song_tag
+            .song_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("song_id is missing for migu"))?;
+        self.pic(song_id)
+    }
+}
@@ -23,22 +23,25 @@
  */
 pub mod encrypt;
 mod kugou;
+mod local;
 pub mod lrc;
 mod migu;
 mod netease;
 
 use crate::ui::{DLMsg, Msg, SearchLyricState};
 use crate::utils::get_parent_folder;
-use anyhow::{anyhow, bail, Result};
+use anyhow::{bail, Result};
 use lofty::id3::v2::{Frame, FrameFlags, FrameValue, ID3v2Tag, LanguageFrame};
 use lofty::{Accessor, Picture, TagExt, TextEncoding};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::mpsc::Sender;
 use std::thread::{self, sleep};
 use std::time::Duration;
 use ytd_rs::{Arg, YoutubeDL};
 
+pub use local::lyric_files;
+
 #[derive(Deserialize, Serialize)]
 pub struct SongTag {
     artist: Option<String>,
@@ -54,17 +57,60 @@ pub struct SongTag {
     // genre: Option<String>,
 }
 
-#[derive(Deserialize, Serialize)]
+/// What the tag editor searches lyrics for.
+#[derive(Clone, Default)]
+pub struct Query {
+    /// Artist and title, or the file name when the tags are empty
+    pub text: String,
+    /// The music file the lyrics are for
+    pub file: Option<String>,
+    /// Extra folder searched for lyric files, see `Settings::lyric_dir`
+    pub lyric_dir: String,
+}
+
+/// A place to search lyrics and tags from. Each [`ServiceProvider`] names
+/// one, and [`ServiceProvider::api`] gives its implementation.
+pub trait LyricProvider {
+    fn search_tags(&mut self, query: &Query) -> Result<Vec<SongTag>>;
+
+    fn lyric(&mut self, song_tag: &SongTag) -> Result<String>;
+
+    fn picture(&mut self, _song_tag: &SongTag) -> Result<Picture> {
+        bail!("no picture from this provider")
+    }
+
+    fn download_url(&mut self, song_tag: &SongTag) -> Result<String> {
+        Ok(song_tag.url.clone().unwrap_or_default())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[allow(clippy::use_self)]
 pub enum ServiceProvider {
+    Local,
     Netease,
     Kugou,
     Migu,
 }
 
+impl ServiceProvider {
+    /// The order searched when nothing is configured.
+    pub const ALL: [Self; 4] = [Self::Local, Self::Netease, Self::Migu, Self::Kugou];
+
+    pub fn api(self) -> Box<dyn LyricProvider> {
+        match self {
+            Self::Local => Box::new(local::Api),
+            Self::Netease => Box::new(netease::Api::new()),
+            Self::Kugou => Box::new(kugou::Api::new()),
+            Self::Migu => Box::new(migu::Api::new()),
+        }
+    }
+}
+
 impl std::fmt::Display for ServiceProvider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let service_provider = match self {
+            Self::Local => "Local",
             Self::Netease => "Netease",
             Self::Kugou => "Kugou",
             Self::Migu => "Migu",
@@ -73,58 +119,25 @@ impl std::fmt::Display for ServiceProvider {
     }
 }
 
-// Search function of 3 servers. Run in parallel to get results faster.
-pub fn search(search_str: &str, tx_tageditor: Sender<SearchLyricState>) {
-    let mut results: Vec<SongTag> = Vec::new();
-    let (tx, rx): (Sender<Vec<SongTag>>, Receiver<Vec<SongTag>>) = mpsc::channel();
-
-    let tx1 = tx.clone();
-    let search_str_netease = search_str.to_string();
-    let handle_netease = thread::spawn(move || -> Result<()> {
-        let mut netease_api = netease::Api::new();
-        if let Ok(results) = netease_api.search(&search_str_netease, 1, 0, 30) {
-            let result_new: Vec<SongTag> = serde_json::from_str(&results)?;
-            tx1.send(result_new).ok();
-        }
-        Ok(())
-    });
-
-    let tx2 = tx.clone();
-    let search_str_migu = search_str.to_string();
-    let handle_migu = thread::spawn(move || -> Result<()> {
-        let migu_api = migu::Api::new();
-        if let Ok(results) = migu_api.search(&search_str_migu, 1, 0, 30) {
-            let result_new: Vec<SongTag> = serde_json::from_str(&results)?;
-            tx2.send(result_new).ok();
-        }
-        Ok(())
-    });
-
-    let kugou_api = kugou::Api::new();
-    let search_str_kugou = search_str.to_string();
-    let handle_kugou = thread::spawn(move || -> Result<()> {
-        if let Ok(r) = kugou_api.search(&search_str_kugou, 1, 0, 30) {
-            let result_new: Vec<SongTag> = serde_json::from_str(&r)?;
-            tx.send(result_new).ok();
-        }
-        Ok(())
-    });
+/// Searches the providers in parallel to get results faster, and lists the
+/// results in the order the providers are given.
+pub fn search(
+    query: &Query,
+    providers: &[ServiceProvider],
+    tx_tageditor: Sender<SearchLyricState>,
+) {
+    let handles: Vec<_> = providers
+        .iter()
+        .map(|&provider| {
+            let query = query.clone();
+            thread::spawn(move || provider.api().search_tags(&query))
+        })
+        .collect();
 
     thread::spawn(move || {
-        if handle_netease.join().is_ok() {
-            if let Ok(result_new) = rx.try_recv() {
-                results.extend(result_new);
-            }
-        }
-
-        if handle_migu.join().is_ok() {
-            if let Ok(result_new) = rx.try_recv() {
-                results.extend(result_new);
-            }
-        }
-
-        if handle_kugou.join().is_ok() {
-            if let Ok(result_new) = rx.try_recv() {
+        let mut results: Vec<SongTag> = Vec::new();
+        for handle in handles {
+            if let Ok(Ok(result_new)) = handle.join() {
                 results.extend(result_new);
             }
         }
@@ -180,90 +193,23 @@ impl SongTag {
     }
     // get lyric by lyric_id
     pub fn fetch_lyric(&self) -> Result<String> {
-        let mut lyric_string = String::new();
-
         match self.service_provider {
-            Some(ServiceProvider::Kugou) => {
-                let kugou_api = kugou::Api::new();
-                if let Some(lyric_id) = &self.lyric_id {
-                    lyric_string = kugou_api.song_lyric(lyric_id)?;
-                }
-            }
-            Some(ServiceProvider::Netease) => {
-                let mut netease_api = netease::Api::new();
-                if let Some(lyric_id) = &self.lyric_id {
-                    lyric_string = netease_api.song_lyric(lyric_id)?;
-                }
-            }
-            Some(ServiceProvider::Migu) => {
-                let migu_api = migu::Api::new();
-                if let Some(lyric_id) = &self.lyric_id {
-                    lyric_string = migu_api.song_lyric(lyric_id)?;
-                }
-            }
-            None => {}
+            Some(provider) => provider.api().lyric(self),
+            None => Ok(String::new()),
         }
-
-        Ok(lyric_string)
     }
 
     // get photo by pic_id(kugou/netease) or song_id(migu)
     pub fn fetch_photo(&self) -> Result<Picture> {
-        // let mut encoded_image_bytes: Vec<u8> = Vec::new();
-
         match self.service_provider {
-            Some(ServiceProvider::Kugou) => {
-                let kugou_api = kugou::Api::new();
-                if let Some(p) = &self.pic_id {
-                    if let Some(album_id) = &self.album_id {
-                        Ok(kugou_api.pic(p, album_id)?)
-                    } else {
-                        bail!("album_id is missing for kugou")
-                    }
-                } else {
-                    bail!("pic_id is missing for kugou")
-                }
-            }
-            Some(ServiceProvider::Netease) => {
-                let mut netease_api = netease::Api::new();
-                if let Some(p) = &self.pic_id {
-                    Ok(netease_api.pic(p)?)
-                } else {
-                    bail!("pic_id is missing for netease")
-                }
-            }
-            Some(ServiceProvider::Migu) => {
-                let migu_api = migu::Api::new();
-                if let Some(p) = &self.song_id {
-                    Ok(migu_api.pic(p)?)
-                } else {
-                    bail!("song_id is missing for migu")
-                }
-            }
-            None => {
-                bail!("no servie provider given");
-            }
+            Some(provider) => provider.api().picture(self),
+            None => bail!("no service provider given"),
         }
-
-        // if encoded_image_bytes.is_empty() {
-        //     bail!("failed to fetch image");
-        // }
-
-        // Ok(Picture::new_unchecked(
-        //     PictureType::Other,
-        //     MimeType::Jpeg,
-        //     Some(String::from("Image")),
-        //     encoded_image_bytes,
-        // ))
     }
 
     #[allow(clippy::too_many_lines)]
     pub fn download(&self, file: &str, tx_tageditor: &Sender<Msg>) -> Result<()> {
         let p_parent = get_parent_folder(file);
-        let song_id = self
-            .song_id
-            .as_ref()
-            .ok_or_else(|| anyhow!("error downloading because no song id is found"))?;
         let artist = self
             .artist
             .clone()
@@ -276,7 +222,6 @@ impl SongTag {
         let album = self.album.clone().unwrap_or_else(|| String::from("N/A"));
         let lyric = self.fetch_lyric();
         let photo = self.fetch_photo();
-        let album_id = self.album_id.clone().unwrap_or_else(|| String::from("N/A"));
 
         let filename = format!("{artist}-{title}.%(ext)s");
 
@@ -294,21 +239,10 @@ impl SongTag {
         if mp3_url.starts_with("Copyright") {
             bail!("Copyright protected, please select another item.");
         }
-        let mut url = mp3_url;
-
-        if let Some(s) = &self.service_provider {
-            match s {
-                ServiceProvider::Netease => {
-                    let mut netease_api = netease::Api::new();
-                    url = netease_api.song_url(song_id)?;
-                }
-                ServiceProvider::Migu => {}
-                ServiceProvider::Kugou => {
-                    let kugou_api = kugou::Api::new();
-                    url = kugou_api.song_url(song_id, &album_id)?;
-                }
-            }
-        }
+        let url = match self.service_provider {
+            Some(provider) => provider.api().download_url(self)?,
+            None => mp3_url,
+        };
 
         if url.is_empty() {
             bail!("url fetch failed, please try another item.");
@@ -6,6 +6,7 @@
 mod model;
 
 use super::encrypt::Crypto;
+use super::{LyricProvider, Query, SongTag};
 use anyhow::{anyhow, bail, Result};
 use lazy_static::lazy_static;
 use lofty::Picture;
@@ -236,6 +237,36 @@ impl Api {
     }
 }
 
+impl LyricProvider for Api {
+    fn search_tags(&mut self, query: &Query) -> Result<Vec<SongTag>> {
+        let results = self.search(&query.text, 1, 0, 30)?;
+        Ok(serde_json::from_str(&results)?)
+    }
+
+    fn lyric(&mut self, song_tag: &SongTag) -> Result<String> {
+        match &song_tag.lyric_id {
+            Some(lyric_id) => self.song_lyric(lyric_id),
+            None => Ok(String::new()),
+        }
+    }
+
+    fn picture(&mut self, song_tag: &SongTag) -> Result<Picture> {
+        let pic_id = song_tag
+            .pic_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("pic_id is missing for netease"))?;
+        self.pic(pic_id)
+    }
+
+    fn download_url(&mut self, song_tag: &SongTag) -> Result<String> {
+        let song_id = song_tag
+            .song_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("error downloading because no song id is found"))?;
+        self.song_url(song_id)
+    }
+}
+
 fn choose_user_agent(ua: &str) -> &str {
     let index = if ua == "mobile" {
         rand::random::<usize>() % 7
@@ -23,18 +23,24 @@
  */
 // database
 use crate::config::Settings;
-use crate::track::Track;
+use crate::track::{Rating, Track};
 use crate::utils::{filetype_supported, get_app_config_path, get_pin_yin};
 use rusqlite::{params, Connection, Error, Result, Row};
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const DB_VERSION: u32 = 2;
 
+/// Ratings by file, shared as the library can be large
+pub type Ratings = Arc<HashMap<String, Rating>>;
+
 pub struct DataBase {
     conn: Arc<Mutex<Connection>>,
     max_depth: usize,
+    /// The ratings last read, dropped whenever tracks are written
+    ratings: Arc<Mutex<Option<Ratings>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -51,6 +57,17 @@ pub struct TrackForDB {
     pub directory: String,
     pub last_modified: String,
     pub last_position: Duration,
+    pub rating: Rating,
+}
+
+/// A named playlist, as kept between switches.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlaylistRecord {
+    pub files: Vec<String>,
+    /// Index of the current track
+    pub current: usize,
+    /// How far into the current track playback was
+    pub position: Duration,
 }
 
 #[derive(PartialEq, Eq)]
@@ -60,6 +77,8 @@ pub enum SearchCriteria {
     Genre,
     Directory,
     Playlist,
+    Rating,
+    Favourite,
 }
 
 impl From<usize> for SearchCriteria {
@@ -69,6 +88,8 @@ impl From<usize> for SearchCriteria {
             2 => Self::Genre,
             3 => Self::Directory,
             4 => Self::Playlist,
+            5 => Self::Rating,
+            6 => Self::Favourite,
             _ => Self::Artist,
             // 0 | _ => Self::Artist,
         }
@@ -83,6 +104,8 @@ impl std::fmt::Display for SearchCriteria {
             Self::Genre => write!(f, "genre"),
             Self::Directory => write!(f, "directory"),
             Self::Playlist => write!(f, "playlist"),
+            Self::Rating => write!(f, "rating"),
+            Self::Favourite => write!(f, "favourite"),
         }
     }
 }
@@ -92,7 +115,18 @@ impl DataBase {
         let mut db_path = get_app_config_path().expect("failed to get app configuration path");
         db_path.push("library.db");
         let conn = Connection::open(db_path).expect("open db failed");
+        Self::with_connection(conn, config.max_depth_cli)
+    }
 
+    /// An in-memory library holding `tracks`.
+    #[cfg(test)]
+    pub fn in_memory(tracks: Vec<Track>) -> Self {
+        let db = Self::with_connection(Connection::open_in_memory().expect("open db failed"), 1);
+        Self::add_records(&db.conn, &db.ratings, tracks).expect("add records failed");
+        db
+    }
+
+    fn with_connection(conn: Connection, max_depth: usize) -> Self {
         let user_version: u32 = conn
             .query_row("SELECT user_version FROM pragma_user_version", [], |r| {
                 r.get(0)
@@ -117,26 +151,114 @@ impl DataBase {
              ext TEXT,
              directory TEXT,
              last_modified TEXT,
-             last_position INTERGER
+             last_position INTERGER,
+             rating INTEGER,
+             favourite INTEGER
             )",
             [],
         )
         .expect("create table tracks failed");
+        // ratings came later
+        for column in ["rating", "favourite"] {
+            if !Self::has_column(&conn, "tracks", column).expect("read table tracks failed") {
+                conn.execute(
+                    &format!("ALTER TABLE tracks ADD COLUMN {column} INTEGER"),
+                    [],
+                )
+                .expect("add rating columns failed");
+            }
+        }
 
-        let max_depth = config.max_depth_cli;
+        // kept apart from tracks so a schema change there doesn't throw
+        // away envelopes that took a full decode to compute
+        conn.execute(
+            "create table if not exists waveforms(
+             file TEXT PRIMARY KEY,
+             last_modified INTEGER,
+             envelope BLOB
+            )",
+            [],
+        )
+        .expect("create table waveforms failed");
+
+        // kept apart from tracks for the same reason
+        conn.execute(
+            "create table if not exists plays(
+             file TEXT PRIMARY KEY,
+             count INTEGER,
+             last_played INTEGER
+            )",
+            [],
+        )
+        .expect("create table plays failed");
+        // plays didn't always have last_played
+        if !Self::has_column(&conn, "plays", "last_played").expect("read table plays failed") {
+            conn.execute("ALTER TABLE plays ADD COLUMN last_played INTEGER", [])
+                .expect("add last_played column failed");
+        }
+
+        conn.execute(
+            "create table if not exists playlists(
+             id integer primary key,
+             name TEXT UNIQUE NOT NULL,
+             current INTEGER,
+             position INTEGER
+            )",
+            [],
+        )
+        .expect("create table playlists failed");
+        conn.execute(
+            "create table if not exists playlist_tracks(
+             playlist_id INTEGER NOT NULL,
+             idx INTEGER,
+             file TEXT
+            )",
+            [],
+        )
+        .expect("create table playlist_tracks failed");
 
         let conn = Arc::new(Mutex::new(conn));
-        Self { conn, max_depth }
+        Self {
+            conn,
+            max_depth,
+            ratings: Arc::default(),
+        }
+    }
+
+    fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({table});"))?;
+        let mut names = stmt.query_map(params![], |row| row.get::<_, String>("name"))?;
+        Ok(names.any(|name| matches!(name, Ok(name) if name == column)))
     }
 
-    fn add_records(conn: &Arc<Mutex<Connection>>, tracks: Vec<Track>) -> Result<()> {
+    fn add_records(
+        conn: &Arc<Mutex<Connection>>,
+        ratings: &Mutex<Option<Ratings>>,
+        tracks: Vec<Track>,
+    ) -> Result<()> {
         let mut conn = conn.lock().expect("conn is not available for add records");
         let tx = conn.transaction()?;
 
         for track in tracks {
+            let file = track.file().unwrap_or("Unknown File");
+            // a changed file is read again, what was rated here wins over its tags
+            let tagged = track.rating();
+            let rating = tx
+                .query_row(
+                    "SELECT rating, favourite FROM tracks WHERE file = ?1",
+                    [file],
+                    |row| {
+                        Ok(Rating {
+                            stars: row.get::<_, Option<u8>>(0)?.unwrap_or(tagged.stars),
+                            favourite: row.get::<_, Option<bool>>(1)?.unwrap_or(tagged.favourite),
+                        })
+                    },
+                )
+                .unwrap_or(tagged);
+            tx.execute("DELETE FROM tracks WHERE file = ?1", [file])?;
             tx.execute(
-            "INSERT INTO tracks (artist, title, album, genre,  file, duration, name, ext, directory, last_modified, last_position) 
-            values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            "INSERT INTO tracks (artist, title, album, genre,  file, duration, name, ext, directory, last_modified, last_position, rating, favourite) 
+            values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 track.artist().unwrap_or("Unknown Artist").to_string(),
                 track.title().unwrap_or("Unknown Title").to_string(),
@@ -154,12 +276,16 @@ impl DataBase {
                     .as_secs()
                     .to_string(),
                 0,
-
+                rating.stars,
+                rating.favourite,
             ],
         )?;
         }
 
         tx.commit()?;
+        *ratings
+            .lock()
+            .expect("ratings are not available for add records") = None;
         Ok(())
     }
 
@@ -223,6 +349,7 @@ impl DataBase {
     pub fn sync_database(&mut self, path: &Path) {
         // add updated records
         let conn = self.conn.clone();
+        let ratings = self.ratings.clone();
         let mut track_vec: Vec<Track> = vec![];
         let all_items = walkdir::WalkDir::new(path)
             .follow_links(true)
@@ -248,7 +375,7 @@ impl DataBase {
                 }
             }
             if !track_vec.is_empty() {
-                Self::add_records(&conn, track_vec)?;
+                Self::add_records(&conn, &ratings, track_vec)?;
             }
 
             // delete records where local file are missing
@@ -286,7 +413,8 @@ impl DataBase {
         str: &str,
         cri: &SearchCriteria,
     ) -> Result<Vec<TrackForDB>> {
-        let search_str = format!("SELECT * FROM tracks WHERE {cri} = ?");
+        // same as in get_criterias, so unrated tracks are found under 0
+        let search_str = format!("SELECT * FROM tracks WHERE CAST(IFNULL({cri}, 0) AS TEXT) = ?");
         let conn = self
             .conn
             .lock()
@@ -324,11 +452,29 @@ impl DataBase {
             directory: row.get(9).unwrap(),
             last_modified: row.get(10).unwrap(),
             last_position: Duration::from_secs(last_position_u64),
+            rating: Self::rating(row, 12),
+        }
+    }
+
+    /// The rating in the columns from `index` on, unrated where they're NULL.
+    fn rating(row: &Row<'_>, index: usize) -> Rating {
+        Rating {
+            stars: row
+                .get::<_, Option<u8>>(index)
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
+            favourite: row
+                .get::<_, Option<bool>>(index + 1)
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
         }
     }
 
     pub fn get_criterias(&mut self, cri: &SearchCriteria) -> Vec<String> {
-        let search_str = format!("SELECT DISTINCT {cri} FROM tracks");
+        // ratings are numbers, and NULL until a track is rated
+        let search_str = format!("SELECT DISTINCT CAST(IFNULL({cri}, 0) AS TEXT) FROM tracks");
         let conn = self
             .conn
             .lock()
@@ -388,6 +534,267 @@ impl DataBase {
         // eprintln!("set last position as {}", last_position.as_secs());
     }
 
+    pub fn connection(&self) -> Arc<Mutex<Connection>> {
+        self.conn.clone()
+    }
+
+    /// The cached envelope of `file`, if it was computed since the file was
+    /// last modified.
+    pub fn get_waveform(&self, file: &str, last_modified: u64) -> Option<Vec<u8>> {
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for get waveform.");
+        conn.query_row(
+            "SELECT envelope FROM waveforms WHERE file = ?1 AND last_modified >= ?2",
+            params![file, last_modified],
+            |row| row.get(0),
+        )
+        .ok()
+    }
+
+    pub fn set_waveform(
+        conn: &Arc<Mutex<Connection>>,
+        file: &str,
+        last_modified: u64,
+        envelope: &[u8],
+    ) -> Result<()> {
+        let conn = conn
+            .lock()
+            .expect("conn is not available for set waveform.");
+        conn.execute(
+            "INSERT OR REPLACE INTO waveforms (file, last_modified, envelope) values (?1, ?2, ?3)",
+            params![file, last_modified, envelope],
+        )?;
+        Ok(())
+    }
+
+    /// How often each file was played.
+    pub fn get_play_counts(&self) -> HashMap<String, u32> {
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for get play counts.");
+        let Ok(mut stmt) = conn.prepare("SELECT file, count FROM plays") else {
+            return HashMap::new();
+        };
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map(|rows| rows.flatten().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn add_play_count(&self, file: &str) -> Result<()> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for add play count.");
+        conn.execute(
+            "INSERT INTO plays (file, count, last_played) values (?1, 1, ?2)
+             ON CONFLICT(file) DO UPDATE SET count = count + 1, last_played = ?2",
+            params![file, now],
+        )?;
+        Ok(())
+    }
+
+    /// At most `limit` random tracks with their play counts, those whose
+    /// `criteria` is `value` when given, leaving out the ones played
+    /// `within` that long when given.
+    pub fn get_radio_records(
+        &self,
+        criteria: Option<(&SearchCriteria, &str)>,
+        within: Option<Duration>,
+        limit: usize,
+    ) -> Result<Vec<(TrackForDB, u32)>> {
+        let since = within.map(|within| {
+            SystemTime::now()
+                .checked_sub(within)
+                .and_then(|since| since.duration_since(UNIX_EPOCH).ok())
+                .unwrap_or_default()
+                .as_secs()
+        });
+        let (filter, value) = match criteria {
+            Some((criteria, value)) => (format!("tracks.{criteria} = ?1"), value),
+            None => ("?1 IS NULL".to_string(), ""),
+        };
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for get radio records.");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT tracks.*, IFNULL(plays.count, 0) AS play_count FROM tracks
+             LEFT JOIN plays ON plays.file = tracks.file
+             WHERE {filter} AND (?2 IS NULL OR IFNULL(plays.last_played, 0) < ?2)
+             ORDER BY RANDOM() LIMIT ?3"
+        ))?;
+        let value = criteria.map(|_| value);
+        let records = stmt
+            .query_map(params![value, since, limit], |row| {
+                Ok((Self::track_db(row), row.get("play_count")?))
+            })?
+            .flatten()
+            .collect();
+        Ok(records)
+    }
+
+    /// Ratings of all tracks in the library, unrated ones included, read
+    /// again only after tracks are written.
+    pub fn get_ratings(&self) -> Ratings {
+        if let Some(ratings) = self
+            .ratings
+            .lock()
+            .expect("ratings are not available for get ratings.")
+            .as_ref()
+        {
+            return ratings.clone();
+        }
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for get ratings.");
+        let Ok(mut stmt) = conn.prepare("SELECT file, rating, favourite FROM tracks") else {
+            return Arc::default();
+        };
+        let read: Ratings = Arc::new(
+            stmt.query_map([], |row| Ok((row.get(0)?, Self::rating(row, 1))))
+                .map(|rows| rows.flatten().collect())
+                .unwrap_or_default(),
+        );
+        *self
+            .ratings
+            .lock()
+            .expect("ratings are not available for get ratings.") = Some(read.clone());
+        read
+    }
+
+    /// Rates `file`, adding it to the library first if a sync hasn't yet.
+    pub fn set_rating(&self, file: &str, rating: Rating) -> Result<()> {
+        let update = || {
+            let conn = self
+                .conn
+                .lock()
+                .expect("conn is not available for set rating.");
+            *self
+                .ratings
+                .lock()
+                .expect("ratings are not available for set rating.") = None;
+            conn.execute(
+                "UPDATE tracks SET rating = ?1, favourite = ?2 WHERE file = ?3",
+                params![rating.stars, rating.favourite, file],
+            )
+        };
+        if update()? > 0 {
+            return Ok(());
+        }
+        let track = Track::read_from_path(file, true).map_err(|_| Error::QueryReturnedNoRows)?;
+        Self::add_records(&self.conn, &self.ratings, vec![track])?;
+        update().map(|_| ())
+    }
+
+    /// The names of the named playlists, oldest first.
+    pub fn get_playlist_names(&self) -> Result<Vec<String>> {
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for get playlist names.");
+        let mut stmt = conn.prepare("SELECT name FROM playlists ORDER BY id")?;
+        let names = stmt.query_map([], |row| row.get(0))?.flatten().collect();
+        Ok(names)
+    }
+
+    pub fn get_playlist(&self, name: &str) -> Result<PlaylistRecord> {
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for get playlist.");
+        let (id, current, position): (i64, usize, u64) = conn.query_row(
+            "SELECT id, current, position FROM playlists WHERE name = ?1",
+            params![name],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        let mut stmt =
+            conn.prepare("SELECT file FROM playlist_tracks WHERE playlist_id = ?1 ORDER BY idx")?;
+        let files = stmt
+            .query_map(params![id], |row| row.get(0))?
+            .flatten()
+            .collect();
+        Ok(PlaylistRecord {
+            files,
+            current,
+            position: Duration::from_secs(position),
+        })
+    }
+
+    /// Creates or overwrites the playlist `name`.
+    pub fn save_playlist(&self, name: &str, record: &PlaylistRecord) -> Result<()> {
+        let mut conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for save playlist.");
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR IGNORE INTO playlists (name) values (?1)",
+            params![name],
+        )?;
+        let id: i64 = tx.query_row(
+            "SELECT id FROM playlists WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )?;
+        tx.execute(
+            "UPDATE playlists SET current = ?1, position = ?2 WHERE id = ?3",
+            params![record.current, record.position.as_secs(), id],
+        )?;
+        tx.execute(
+            "DELETE FROM playlist_tracks WHERE playlist_id = ?1",
+            params![id],
+        )?;
+        for (idx, file) in record.files.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO playlist_tracks (playlist_id, idx, file) values (?1, ?2, ?3)",
+                params![id, idx, file],
+            )?;
+        }
+        tx.commit()
+    }
+
+    /// Appends `files` to the playlist `name`.
+    pub fn add_playlist_files(&self, name: &str, files: &[String]) -> Result<()> {
+        let mut record = self.get_playlist(name)?;
+        record.files.extend_from_slice(files);
+        self.save_playlist(name, &record)
+    }
+
+    pub fn rename_playlist(&self, name: &str, new_name: &str) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for rename playlist.");
+        conn.execute(
+            "UPDATE playlists SET name = ?1 WHERE name = ?2",
+            params![new_name, name],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_playlist(&self, name: &str) -> Result<()> {
+        let mut conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for delete playlist.");
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM playlist_tracks WHERE playlist_id IN
+             (SELECT id FROM playlists WHERE name = ?1)",
+            params![name],
+        )?;
+        tx.execute("DELETE FROM playlists WHERE name = ?1", params![name])?;
+        tx.commit()
+    }
+
     pub fn get_record_by_path(&mut self, str: &str) -> Result<TrackForDB> {
         let search_str = "SELECT * FROM tracks WHERE file = ?";
         let conn = self
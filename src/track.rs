@@ -1,4 +1,6 @@
-use crate::podcast::Episode;
+use crate::cover::find_cover_file;
+use crate::invidious::AudioStream;
+use crate::podcast::{Episode, PodcastPlayback};
 /**
  * MIT License
  *
@@ -29,14 +31,15 @@ use id3::frame::Lyrics;
 use lofty::id3::v2::{Frame, FrameFlags, FrameValue, ID3v2Tag, LanguageFrame};
 use lofty::{
     mpeg::MPEGFile, Accessor, AudioFile, FileType, ItemKey, ItemValue, Picture, PictureType,
-    TagExt, TagItem, TaggedFileExt, TextEncoding,
+    TagExt, TagItem, TagType, TaggedFileExt, TextEncoding,
 };
+use std::collections::{HashMap, HashSet};
 use std::convert::From;
 use std::ffi::OsStr;
 use std::fs::rename;
 use std::fs::File;
 use std::io::BufReader;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 
@@ -66,7 +69,8 @@ pub struct Track {
     album_photo: Option<String>,
     file_type: Option<FileType>,
     // Date
-    // Track
+    /// Position of the song on its album
+    number: Option<u32>,
     genre: Option<String>,
     // Composer
     // Performer
@@ -74,6 +78,59 @@ pub struct Track {
     // Comment
     pub media_type: Option<MediaType>,
     pub podcast_localfile: Option<String>,
+    /// Chapters from CHAP frames or the podcast chapters file, sorted by start
+    chapters: Vec<Chapter>,
+    /// Url of the Podcasting 2.0 chapters file, fetched when the episode starts
+    pub chapters_url: Option<String>,
+    /// Url and mime type of the podcast transcript, fetched when the episode starts
+    pub transcript: Option<(String, String)>,
+    /// Playback settings of the feed this episode belongs to
+    pub playback: Option<PodcastPlayback>,
+    /// Sidecar `.lrc` or `.txt` file the lyrics were loaded from
+    pub lyric_file: Option<PathBuf>,
+    /// From the library, or the tags for tracks not in it yet
+    rating: Rating,
+}
+
+/// How much a track is liked: 0–5 stars, 0 for unrated, and a favourite flag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rating {
+    pub stars: u8,
+    pub favourite: bool,
+}
+
+impl Rating {
+    /// Stars like `★★★☆☆`, empty when unrated, with `♥` for favourites.
+    pub fn display(self) -> String {
+        let mut out = String::new();
+        if self.favourite {
+            out.push('\u{2665}');
+        }
+        if self.stars > 0 {
+            for star in 1..=5 {
+                out.push(if star <= self.stars {
+                    '\u{2605}'
+                } else {
+                    '\u{2606}'
+                });
+            }
+        }
+        out
+    }
+}
+
+/// Email the POPM frames are written under, the one most players read.
+const POPM_EMAIL: &str = "Windows Media Player 9 Series";
+/// POPM values for 0–5 stars, as Windows Media Player writes them.
+const POPM_STARS: [u8; 6] = [0, 1, 64, 128, 196, 255];
+const FMPS_RATING: &str = "FMPS_RATING";
+const RATING: &str = "RATING";
+
+/// A named section of a track, starting at `start`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chapter {
+    pub title: String,
+    pub start: Duration,
 }
 
 #[derive(Clone)]
@@ -87,9 +144,11 @@ impl Track {
     pub fn from_episode(ep: &Episode) -> Self {
         let lyric_frames: Vec<Lyrics> = Vec::new();
         let mut podcast_localfile: Option<String> = None;
+        let mut chapters = Vec::new();
         if let Some(path) = &ep.path {
             if path.exists() {
                 podcast_localfile = Some(path.to_string_lossy().to_string());
+                chapters = read_id3_chapters(path);
             }
         }
 
@@ -110,9 +169,54 @@ impl Track {
             picture: None,
             album_photo: ep.image_url.clone(),
             file_type: None,
+            number: None,
             genre: None,
             media_type: Some(MediaType::Podcast),
             podcast_localfile,
+            chapters,
+            chapters_url: ep.chapters_url.clone(),
+            transcript: ep.transcript_url.clone().zip(ep.transcript_type.clone()),
+            playback: None,
+            lyric_file: None,
+            rating: Rating::default(),
+        }
+    }
+
+    /// Whether this is a youtube audio stream rather than a file or an
+    /// episode.
+    pub fn is_stream(&self) -> bool {
+        matches!(self.media_type, Some(MediaType::Music))
+            && matches!(&self.file, Some(file) if file.starts_with("http"))
+    }
+
+    /// A youtube video played from its audio stream, without a file on disk.
+    pub fn from_audio_stream(stream: &AudioStream) -> Self {
+        Self {
+            artist: Some(stream.author.clone()),
+            album: None,
+            title: Some(stream.title.clone()),
+            file: Some(stream.url.clone()),
+            duration: Duration::from_secs(stream.length_seconds),
+            name: None,
+            ext: None,
+            directory: None,
+            last_modified: SystemTime::now(),
+            lyric_frames: Vec::new(),
+            lyric_selected_index: 0,
+            parsed_lyric: None,
+            picture: None,
+            album_photo: None,
+            file_type: None,
+            number: None,
+            genre: None,
+            media_type: Some(MediaType::Music),
+            podcast_localfile: None,
+            chapters: Vec::new(),
+            chapters_url: None,
+            transcript: None,
+            playback: None,
+            lyric_file: None,
+            rating: Rating::default(),
         }
     }
 
@@ -139,7 +243,9 @@ impl Track {
                 song.album = tag.album().map(std::borrow::Cow::into_owned);
                 song.title = tag.title().map(std::borrow::Cow::into_owned);
                 song.genre = tag.genre().map(std::borrow::Cow::into_owned);
+                song.number = tag.track();
                 song.media_type = Some(MediaType::Music);
+                song.rating.stars = read_rating(tag).unwrap_or_default();
 
                 if for_db {
                     return Ok(song);
@@ -149,6 +255,7 @@ impl Track {
                 let mut lyric_frames: Vec<Lyrics> = Vec::new();
                 match file_type {
                     Some(FileType::MPEG) => {
+                        song.chapters = read_id3_chapters(path);
                         let mut reader = BufReader::new(File::open(path)?);
                         // let file = MPEGFile::read_from(&mut reader, false)?;
                         let file = MPEGFile::read_from(&mut reader, lofty::ParseOptions::new())?;
@@ -194,16 +301,8 @@ impl Track {
 
         let parent_folder = get_parent_folder(&path.to_string_lossy());
 
-        if let Ok(files) = std::fs::read_dir(parent_folder) {
-            for f in files.flatten() {
-                let path = f.path();
-                if let Some(extension) = path.extension() {
-                    if extension == "jpg" || extension == "png" {
-                        song.album_photo = Some(path.to_string_lossy().to_string());
-                    }
-                }
-            }
-        }
+        song.album_photo = find_cover_file(Path::new(&parent_folder))
+            .map(|path| path.to_string_lossy().to_string());
 
         Ok(song)
     }
@@ -243,9 +342,16 @@ impl Track {
             picture,
             album_photo,
             last_modified,
+            number: None,
             genre,
             media_type: Some(MediaType::Music),
             podcast_localfile: None,
+            chapters: Vec::new(),
+            chapters_url: None,
+            transcript: None,
+            playback: None,
+            lyric_file: None,
+            rating: Rating::default(),
         }
     }
 
@@ -254,11 +360,27 @@ impl Track {
             lyric.adjust_offset(time_pos, offset);
             let text = lyric.as_lrc_text();
             self.set_lyric(&text, "Adjusted");
-            self.save_tag()?;
+            match &self.lyric_file {
+                Some(path) => std::fs::write(path, text)?,
+                None => self.save_tag()?,
+            }
         }
         Ok(())
     }
 
+    /// Uses the lyrics in a sidecar file, for tracks without embedded ones.
+    pub fn load_lyric_file(&mut self, path: &Path) -> Result<()> {
+        let text = std::fs::read_to_string(path)?;
+        let description = path.extension().map_or_else(
+            || "Sidecar".to_string(),
+            |e| e.to_string_lossy().to_string(),
+        );
+        self.set_lyric(&text, &description);
+        self.parsed_lyric = Lyric::from_str(&text).ok();
+        self.lyric_file = Some(path.to_path_buf());
+        Ok(())
+    }
+
     pub fn cycle_lyrics(&mut self) -> Result<&Lyrics> {
         if self.lyric_frames_is_empty() {
             bail!("no lyrics embedded");
@@ -309,6 +431,13 @@ impl Track {
         None
     }
 
+    /// A lyric frame in another language than the selected one, shown next
+    /// to it as a translation.
+    pub fn lyric_translation(&self) -> Option<&Lyrics> {
+        let selected = self.lyric_selected()?;
+        self.lyric_frames.iter().find(|f| f.lang != selected.lang)
+    }
+
     pub fn lyric_frames_is_empty(&self) -> bool {
         self.lyric_frames.is_empty()
     }
@@ -354,6 +483,10 @@ impl Track {
         self.album = Some(album.to_string());
     }
 
+    pub const fn track_number(&self) -> Option<u32> {
+        self.number
+    }
+
     pub fn genre(&self) -> Option<&str> {
         self.genre.as_deref()
     }
@@ -409,6 +542,43 @@ impl Track {
         self.name.as_deref()
     }
 
+    pub const fn rating(&self) -> Rating {
+        self.rating
+    }
+
+    pub fn set_rating(&mut self, rating: Rating) {
+        self.rating = rating;
+    }
+
+    /// Writes the stars into the tags of the file, leaving the other tags
+    /// as they are, unlike `save_tag`.
+    pub fn save_rating(&self) -> Result<()> {
+        let Some(file_path) = self.file() else {
+            return Ok(());
+        };
+        if self.file_type == Some(FileType::MPEG) {
+            let mut reader = BufReader::new(File::open(file_path)?);
+            let file = MPEGFile::read_from(&mut reader, lofty::ParseOptions::new())?;
+            let mut tag = file.id3v2().cloned().unwrap_or_default();
+            tag.remove("POPM");
+            if let Some(frame) = popm_frame(self.rating.stars) {
+                tag.insert(frame);
+            }
+            tag.save_to_path(file_path)?;
+            return Ok(());
+        }
+
+        let mut tagged_file = lofty::read_from_path(file_path)?;
+        if tagged_file.primary_tag().is_none() {
+            tagged_file.insert_tag(lofty::Tag::new(tagged_file.primary_tag_type()));
+        }
+        if let Some(tag) = tagged_file.primary_tag_mut() {
+            write_rating(tag, self.rating.stars);
+            tag.save_to_path(file_path)?;
+        }
+        Ok(())
+    }
+
     pub fn save_tag(&mut self) -> Result<()> {
         match self.file_type {
             Some(FileType::MPEG) => {
@@ -454,6 +624,10 @@ impl Track {
                         tag.insert_picture(any_picture);
                     }
 
+                    if let Some(frame) = popm_frame(self.rating.stars) {
+                        tag.insert(frame);
+                    }
+
                     tag.save_to_path(file_path)?;
                 }
             }
@@ -483,6 +657,8 @@ impl Track {
                         tag.push_picture(any_picture);
                     }
 
+                    write_rating(&mut tag, self.rating.stars);
+
                     tag.save_to_path(file_path)?;
                 }
             }
@@ -543,6 +719,41 @@ impl Track {
         self.picture = Some(picture);
     }
 
+    pub fn chapters(&self) -> &[Chapter] {
+        &self.chapters
+    }
+
+    pub fn set_chapters(&mut self, mut chapters: Vec<Chapter>) {
+        chapters.sort_by_key(|c| c.start);
+        self.chapters = chapters;
+    }
+
+    /// The chapter playing at `time_pos`, if any.
+    pub fn chapter_at(&self, time_pos: Duration) -> Option<&Chapter> {
+        self.chapters.iter().rev().find(|c| c.start <= time_pos)
+    }
+
+    /// Start of the first chapter after `time_pos`.
+    pub fn chapter_next_start(&self, time_pos: Duration) -> Option<Duration> {
+        self.chapters
+            .iter()
+            .find(|c| c.start > time_pos)
+            .map(|c| c.start)
+    }
+
+    /// Start of the chapter before the current one. Like the previous track
+    /// button, this restarts the current chapter instead when more than a few
+    /// seconds of it have played.
+    pub fn chapter_previous_start(&self, time_pos: Duration) -> Option<Duration> {
+        let current = self.chapters.iter().rposition(|c| c.start <= time_pos)?;
+        if time_pos.saturating_sub(self.chapters[current].start) > Duration::from_secs(3)
+            || current == 0
+        {
+            return Some(self.chapters[current].start);
+        }
+        Some(self.chapters[current - 1].start)
+    }
+
     fn update_tag<T: Accessor>(&self, tag: &mut T) {
         tag.set_artist(
             self.artist()
@@ -559,6 +770,157 @@ impl Track {
     }
 }
 
+/// Stars from the rating in `tag`: a POPM frame, or a `FMPS_RATING` (0.0–1.0)
+/// or `RATING` (stars, or 0–100) comment.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn read_rating(tag: &lofty::Tag) -> Option<u8> {
+    if let Some(popm) = tag.get_binary(&ItemKey::Popularimeter, false) {
+        // the email comes first, NUL terminated
+        let rating = popm
+            .iter()
+            .position(|b| *b == 0)
+            .and_then(|end| popm.get(end + 1))?;
+        return Some(match rating {
+            0 => 0,
+            1..=31 => 1,
+            32..=95 => 2,
+            96..=159 => 3,
+            160..=223 => 4,
+            _ => 5,
+        });
+    }
+    let comment = |key: &str| tag.get_string(&ItemKey::Unknown(key.to_string()));
+    if let Some(fmps) = comment(FMPS_RATING).and_then(|v| v.trim().parse::<f64>().ok()) {
+        return Some((fmps.clamp(0.0, 1.0) * 5.0).round() as u8);
+    }
+    let rating = comment(RATING)?.trim().parse::<u8>().ok()?;
+    Some(if rating <= 5 {
+        rating
+    } else {
+        (rating.min(100) + 10) / 20
+    })
+}
+
+/// The content of a POPM frame for `stars`, None when unrated.
+fn popm(stars: u8) -> Option<Vec<u8>> {
+    let rating = *POPM_STARS.get(usize::from(stars)).filter(|r| **r > 0)?;
+    let mut content = POPM_EMAIL.as_bytes().to_vec();
+    // NUL after the email, then the rating and a zero play counter
+    content.extend([0, rating, 0, 0, 0, 0]);
+    Some(content)
+}
+
+fn popm_frame(stars: u8) -> Option<Frame<'static>> {
+    Frame::new(
+        "POPM",
+        FrameValue::Binary(popm(stars)?),
+        FrameFlags::default(),
+    )
+    .ok()
+}
+
+/// Sets the rating comments of `tag` to `stars`, removing them when unrated.
+fn write_rating(tag: &mut lofty::Tag, stars: u8) {
+    let fmps = ItemKey::Unknown(FMPS_RATING.to_string());
+    let rating = ItemKey::Unknown(RATING.to_string());
+    if tag.tag_type() == TagType::ID3v2 {
+        tag.remove_key(&ItemKey::Popularimeter);
+        if let Some(popm) = popm(stars) {
+            tag.insert_item(TagItem::new(
+                ItemKey::Popularimeter,
+                ItemValue::Binary(popm),
+            ));
+        }
+        return;
+    }
+    if stars == 0 {
+        tag.remove_key(&fmps);
+        tag.remove_key(&rating);
+        return;
+    }
+    // unknown keys are only kept by tags with free form keys, like Vorbis comments
+    let fmps_value = (f64::from(stars) / 5.0).to_string();
+    tag.insert_item_unchecked(TagItem::new(fmps, ItemValue::Text(fmps_value)));
+    let rating_value = (u16::from(stars) * 20).to_string();
+    tag.insert_item_unchecked(TagItem::new(rating, ItemValue::Text(rating_value)));
+}
+
+fn read_id3_chapters(path: &Path) -> Vec<Chapter> {
+    id3::Tag::read_from_path(path)
+        .map(|tag| id3_chapters(&tag))
+        .unwrap_or_default()
+}
+
+/// The CHAP frames of an `ID3v2` tag. With a top-level CTOC frame only the
+/// chapters it lists, directly or through nested tables, are kept. Chapter
+/// titles come from the embedded TIT2 frame, falling back to the element id.
+fn id3_chapters(tag: &id3::Tag) -> Vec<Chapter> {
+    // id3 doesn't decode CTOC, its frames come as unknown content
+    let tables: HashMap<String, (bool, Vec<String>)> = tag
+        .frames()
+        .filter(|f| f.id() == "CTOC")
+        .filter_map(|f| parse_ctoc(&f.content().to_unknown().ok()?.data))
+        .collect();
+    let listed = tables
+        .iter()
+        .find(|(_, (top_level, _))| *top_level)
+        .map(|(id, _)| {
+            let mut listed = HashSet::new();
+            let mut pending = vec![id.clone()];
+            while let Some(id) = pending.pop() {
+                if !listed.insert(id.clone()) {
+                    continue;
+                }
+                if let Some((_, children)) = tables.get(&id) {
+                    pending.extend(children.iter().cloned());
+                }
+            }
+            listed
+        })
+        // a table that lists none of them is broken rather than hiding all
+        .filter(|listed| tag.chapters().any(|chap| listed.contains(&chap.element_id)));
+    let mut chapters: Vec<Chapter> = tag
+        .chapters()
+        .filter(|chap| match &listed {
+            Some(listed) => listed.contains(&chap.element_id),
+            None => true,
+        })
+        .map(|chap| {
+            let title = chap
+                .frames
+                .iter()
+                .find(|f| f.id() == "TIT2")
+                .and_then(|f| f.content().text())
+                .map_or_else(|| chap.element_id.clone(), str::to_string);
+            Chapter {
+                title,
+                start: Duration::from_millis(u64::from(chap.start_time)),
+            }
+        })
+        .collect();
+    chapters.sort_by_key(|c| c.start);
+    chapters
+}
+
+/// The element id of a CTOC frame, whether it is the top-level table, and the
+/// element ids of its entries.
+fn parse_ctoc(data: &[u8]) -> Option<(String, (bool, Vec<String>))> {
+    let mut strings = data.split(|b| *b == 0);
+    let id = strings.next()?;
+    let rest = data.get(id.len() + 1..)?;
+    let (flags, count) = (*rest.first()?, *rest.get(1)?);
+    let children = rest
+        .get(2..)?
+        .split(|b| *b == 0)
+        .take(count.into())
+        .map(|child| String::from_utf8_lossy(child).into_owned())
+        .collect();
+    Some((
+        String::from_utf8_lossy(id).into_owned(),
+        (flags & 0b10 != 0, children),
+    ))
+}
+
 fn create_lyrics(tag: &mut lofty::Tag, lyric_frames: &mut Vec<Lyrics>) {
     let lyrics = tag.take(&ItemKey::Lyrics);
     for lyric in lyrics {
@@ -572,3 +934,68 @@ fn create_lyrics(tag: &mut lofty::Tag, lyric_frames: &mut Vec<Lyrics>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_rating_tags() {
+        for tag_type in [TagType::ID3v2, TagType::VorbisComments] {
+            let mut tag = lofty::Tag::new(tag_type);
+            assert_eq!(read_rating(&tag), None);
+            for stars in 0..=5 {
+                write_rating(&mut tag, stars);
+                assert_eq!(read_rating(&tag).unwrap_or_default(), stars);
+            }
+        }
+
+        let mut tag = lofty::Tag::new(TagType::VorbisComments);
+        let rating = ItemKey::Unknown(RATING.to_string());
+        for (value, stars) in [("4", 4), ("60", 3), ("100", 5)] {
+            tag.insert_item_unchecked(TagItem::new(
+                rating.clone(),
+                ItemValue::Text(value.to_string()),
+            ));
+            assert_eq!(read_rating(&tag), Some(stars));
+        }
+        let fmps = ItemKey::Unknown(FMPS_RATING.to_string());
+        tag.insert_item_unchecked(TagItem::new(fmps, ItemValue::Text("0.4".to_string())));
+        assert_eq!(read_rating(&tag), Some(2));
+    }
+
+    #[test]
+    fn test_id3_chapters_table_of_contents() {
+        use id3::TagLike;
+
+        let mut tag = id3::Tag::new();
+        for (element_id, start_time) in [("ch1", 0), ("hidden", 500), ("ch2", 1000)] {
+            tag.add_frame(id3::frame::Chapter {
+                element_id: element_id.to_string(),
+                start_time,
+                end_time: start_time + 500,
+                start_offset: u32::MAX,
+                end_offset: u32::MAX,
+                frames: Vec::new(),
+            });
+        }
+        let titles = |tag: &id3::Tag| -> Vec<String> {
+            id3_chapters(tag).into_iter().map(|c| c.title).collect()
+        };
+        assert_eq!(titles(&tag), vec!["ch1", "hidden", "ch2"]);
+
+        // a top-level table listing ch1 and a nested table listing ch2
+        let tables: [&[u8]; 2] = [b"toc\0\x03\x02ch1\0sub\0", b"sub\0\x01\x01ch2\0"];
+        for data in tables {
+            tag.add_frame(id3::Frame::with_content(
+                "CTOC",
+                id3::Content::Unknown(id3::frame::Unknown {
+                    data: data.to_vec(),
+                    version: id3::Version::Id3v24,
+                }),
+            ));
+        }
+        assert_eq!(titles(&tag), vec!["ch1", "ch2"]);
+    }
+}
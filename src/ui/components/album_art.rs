@@ -0,0 +1,269 @@
+//! Album art for terminals without the Kitty or iTerm protocols: Sixel
+//! graphics printed over the interface, or half blocks drawn in it.
+use image::{DynamicImage, GenericImageView, RgbaImage};
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::io::Write;
+use tuirealm::tui::buffer::Buffer;
+use tuirealm::tui::layout::Rect;
+use tuirealm::tui::style::Color;
+use tuirealm::tui::widgets::Widget;
+use tuirealm::Frame;
+
+/// Cell size assumed when the terminal doesn't report its pixel size.
+const CELL_SIZE: (u32, u32) = (10, 20);
+/// Levels of red, green and blue in the Sixel palette, 252 colors in all.
+const LEVELS: (u32, u32, u32) = (6, 7, 6);
+/// Pixels with less opacity are left transparent.
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// Whether the terminal is known to show Sixel graphics. Xterm only does
+/// when started as a VT340, so it has to be chosen in the config.
+pub fn sixel_supported() -> bool {
+    let term = std::env::var("TERM").unwrap_or_default();
+    ["foot", "mlterm", "yaft", "contour"]
+        .iter()
+        .any(|name| term.starts_with(name))
+        || term.contains("sixel")
+}
+
+/// Size of a terminal cell in pixels.
+pub fn cell_size() -> (u32, u32) {
+    #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd"))]
+    {
+        // SAFETY: TIOCGWINSZ only writes the winsize it is given.
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+        if ok && size.ws_col > 0 && size.ws_row > 0 && size.ws_xpixel > 0 && size.ws_ypixel > 0 {
+            return (
+                u32::from(size.ws_xpixel / size.ws_col),
+                u32::from(size.ws_ypixel / size.ws_row),
+            );
+        }
+    }
+    CELL_SIZE
+}
+
+/// Album art drawn by termusic itself, as opposed to viuer or ueberzug.
+#[derive(Default)]
+pub struct AlbumArt {
+    /// The image resized to two pixels per cell and where it goes
+    half_blocks: Option<(Rect, RgbaImage)>,
+    /// Sixel data and where it goes
+    sixel: Option<(Rect, String)>,
+    /// The Sixel image has to be printed after the next draw, which would
+    /// otherwise paint over it
+    sixel_pending: bool,
+}
+
+impl AlbumArt {
+    /// Fits `image` to `width` cells and `height` half rows at `x`, `y`.
+    pub fn set_half_blocks(
+        &mut self,
+        image: &DynamicImage,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+    ) {
+        let image = image
+            .resize_exact(
+                u32::from(width),
+                u32::from(height),
+                image::imageops::FilterType::Triangle,
+            )
+            .to_rgba8();
+        self.half_blocks = Some((Rect::new(x, y, width, height - height / 2), image));
+    }
+
+    /// Fits `image` to `width` cells at `x`, `y`, as tall as its aspect ratio
+    /// asks for.
+    pub fn set_sixel(&mut self, image: &DynamicImage, x: u16, y: u16, width: u16) {
+        let (cell_width, cell_height) = cell_size();
+        let (image_width, image_height) = image.dimensions();
+        let pixel_width = u32::from(width) * cell_width;
+        let pixel_height = pixel_width * image_height / image_width.max(1);
+        let rows = pixel_height / cell_height + u32::from(pixel_height % cell_height > 0);
+        let image = image
+            .resize_exact(
+                pixel_width.max(1),
+                pixel_height.max(1),
+                image::imageops::FilterType::Triangle,
+            )
+            .to_rgba8();
+        let area = Rect::new(x, y, width, u16::try_from(rows).unwrap_or(u16::MAX));
+        self.sixel = Some((area, encode_sixel(&image)));
+        self.sixel_pending = true;
+    }
+
+    /// Forgets the image. True if a Sixel image was shown, which stays on
+    /// screen until the cells under it are drawn again.
+    pub fn clear(&mut self) -> bool {
+        self.half_blocks = None;
+        self.sixel_pending = false;
+        self.sixel.take().is_some()
+    }
+
+    pub fn render(&self, frame: &mut Frame<'_>) {
+        if let Some((area, image)) = &self.half_blocks {
+            let area = area.intersection(frame.size());
+            frame.render_widget(HalfBlocks { image }, area);
+        }
+    }
+
+    /// Prints the Sixel image if it hasn't been since it was set.
+    pub fn print_sixel<W: Write>(&mut self, out: &mut W) -> std::io::Result<()> {
+        if !self.sixel_pending {
+            return Ok(());
+        }
+        self.sixel_pending = false;
+        if let Some((area, data)) = &self.sixel {
+            crossterm::queue!(out, crossterm::cursor::MoveTo(area.x, area.y))?;
+            out.write_all(data.as_bytes())?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// An image with two pixels per cell, the upper one in the foreground of a
+/// half block and the lower one in its background.
+struct HalfBlocks<'a> {
+    image: &'a RgbaImage,
+}
+
+impl Widget for HalfBlocks<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let color = |x: u32, y: u32| {
+            if x >= self.image.width() || y >= self.image.height() {
+                return Color::Reset;
+            }
+            let [r, g, b, a] = self.image.get_pixel(x, y).0;
+            if a < ALPHA_THRESHOLD {
+                Color::Reset
+            } else {
+                Color::Rgb(r, g, b)
+            }
+        };
+        for row in 0..area.height {
+            for column in 0..area.width {
+                let (x, y) = (u32::from(column), u32::from(row) * 2);
+                buf.get_mut(area.x + column, area.y + row)
+                    .set_symbol("▀")
+                    .set_fg(color(x, y))
+                    .set_bg(color(x, y + 1));
+            }
+        }
+    }
+}
+
+/// Index of the palette color nearest to a pixel, None if it is transparent.
+#[allow(clippy::cast_possible_truncation)]
+fn palette_index([r, g, b, a]: [u8; 4]) -> Option<u8> {
+    if a < ALPHA_THRESHOLD {
+        return None;
+    }
+    let level = |value: u8, levels: u32| (u32::from(value) * (levels - 1) + 127) / 255;
+    let (r, g, b) = (level(r, LEVELS.0), level(g, LEVELS.1), level(b, LEVELS.2));
+    Some(((r * LEVELS.1 + g) * LEVELS.2 + b) as u8)
+}
+
+/// The red, green and blue of a palette color, in percent as Sixel wants.
+fn palette_color(index: u8) -> (u32, u32, u32) {
+    let index = u32::from(index);
+    let percent = |level: u32, levels: u32| level * 100 / (levels - 1);
+    (
+        percent(index / (LEVELS.1 * LEVELS.2), LEVELS.0),
+        percent(index / LEVELS.2 % LEVELS.1, LEVELS.1),
+        percent(index % LEVELS.2, LEVELS.2),
+    )
+}
+
+/// Encodes the image as a Sixel escape sequence. Each band of six pixel rows
+/// is written once per color in it, with runs of the same sixel compressed.
+pub fn encode_sixel(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let indices: Vec<Option<u8>> = image.pixels().map(|pixel| palette_index(pixel.0)).collect();
+    let used: BTreeSet<u8> = indices.iter().flatten().copied().collect();
+
+    // transparent background, 1:1 pixels
+    let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    for index in &used {
+        let (r, g, b) = palette_color(*index);
+        write!(out, "#{index};2;{r};{g};{b}").ok();
+    }
+    let width = width as usize;
+    for band in indices.chunks(width * 6) {
+        let colors: BTreeSet<u8> = band.iter().flatten().copied().collect();
+        for (n, color) in colors.iter().enumerate() {
+            if n > 0 {
+                // back to the start of the band
+                out.push('$');
+            }
+            write!(out, "#{color}").ok();
+            let sixels = (0..width).map(|x| {
+                band.chunks(width)
+                    .enumerate()
+                    .filter(|(_, row)| row[x] == Some(*color))
+                    .fold(0_u8, |bits, (y, _)| bits | 1 << y)
+            });
+            push_runs(&mut out, sixels);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_runs(out: &mut String, sixels: impl Iterator<Item = u8>) {
+    let mut run: Option<(u8, usize)> = None;
+    let flush = |out: &mut String, (bits, count): (u8, usize)| {
+        let c = char::from(63 + bits);
+        if count > 3 {
+            write!(out, "!{count}{c}").ok();
+        } else {
+            for _ in 0..count {
+                out.push(c);
+            }
+        }
+    };
+    for bits in sixels {
+        run = match run {
+            Some((last, count)) if last == bits => Some((last, count + 1)),
+            Some(last) => {
+                flush(out, last);
+                Some((bits, 1))
+            }
+            None => Some((bits, 1)),
+        };
+    }
+    // trailing empty sixels draw nothing
+    if let Some(last) = run.filter(|(bits, _)| *bits != 0) {
+        flush(out, last);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_encode_sixel() {
+        // a red column over two rows next to five transparent ones
+        let mut image = RgbaImage::new(6, 2);
+        image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        image.put_pixel(0, 1, Rgba([255, 0, 0, 255]));
+        let red = palette_index([255, 0, 0, 255]).unwrap();
+        assert_eq!(palette_color(red), (100, 0, 0));
+        assert_eq!(
+            encode_sixel(&image),
+            format!("\x1bP0;1;0q\"1;1;6;2#{red};2;100;0;0#{red}B-\x1b\\")
+        );
+
+        let mut out = String::new();
+        push_runs(&mut out, [1, 1, 1, 1, 1, 2, 0, 0].into_iter());
+        assert_eq!(out, "!5@A");
+    }
+}
@@ -0,0 +1,766 @@
+//! The command palette: every action by name, fuzzy filtered as you type,
+//! with the key it is bound to. Some take an argument, like `volume 40`,
+//! `seek 1:30`, `loop single` or `save-playlist mix`.
+use super::{CPMsg, GSMsg, Msg, PLMsg, XYWHMsg, YSMsg};
+use crate::config::{BindingForEvent, Keys, Settings};
+use crate::player::{Loop, PlayerTrait, Shuffle};
+use crate::ui::{ConfigEditorMsg, Id, Model, PCMsg, TEMsg};
+use anyhow::{anyhow, bail, Result};
+use std::time::Duration;
+use tui_realm_stdlib::{Input, Table};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
+use tuirealm::props::{
+    Alignment, BorderType, Borders, Color, InputType, PropPayload, PropValue, TableBuilder,
+    TextSpan,
+};
+use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent, State, StateValue};
+
+/// What a command does once its argument is parsed.
+pub enum Action {
+    Msg(Msg),
+    Volume(i32),
+    Speed(i32),
+    /// Seconds from the current position
+    Seek(i64),
+    SeekTo(Duration),
+    Loop(Loop),
+    Shuffle(Shuffle),
+    /// Opens the tag editor on the library selection
+    TagEditor,
+}
+
+pub struct Command {
+    pub name: &'static str,
+    /// The argument it takes, like `<0-100>`
+    pub args: &'static str,
+    pub description: &'static str,
+    key: Option<fn(&Keys) -> BindingForEvent>,
+    /// What it does without an argument
+    msg: Option<fn() -> Msg>,
+    /// What it does with one
+    action: Option<fn(&str) -> Result<Action>>,
+}
+
+impl Command {
+    const fn new(name: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            args: "",
+            description,
+            key: None,
+            msg: None,
+            action: None,
+        }
+    }
+
+    const fn msg(mut self, msg: fn() -> Msg) -> Self {
+        self.msg = Some(msg);
+        self
+    }
+
+    const fn args(mut self, args: &'static str, action: fn(&str) -> Result<Action>) -> Self {
+        self.args = args;
+        self.action = Some(action);
+        self
+    }
+
+    const fn key(mut self, key: fn(&Keys) -> BindingForEvent) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn binding(&self, keys: &Keys) -> Option<BindingForEvent> {
+        self.key.map(|key| key(keys))
+    }
+
+    pub fn run(&self, args: &str) -> Result<Action> {
+        let args = args.trim();
+        match (args.is_empty(), self.msg, self.action) {
+            (true, Some(msg), _) => Ok(Action::Msg(msg())),
+            (_, _, Some(action)) => action(args),
+            (false, Some(_), None) => bail!("{} takes no argument", self.name),
+            (_, None, None) => bail!("{} does nothing", self.name),
+        }
+    }
+}
+
+/// Every command, in the order they are listed before anything is typed.
+#[allow(clippy::too_many_lines)]
+pub fn commands() -> Vec<Command> {
+    vec![
+        Command::new("play-pause", "Pause or resume playback")
+            .msg(|| Msg::PlayerTogglePause)
+            .key(|keys| keys.global_player_toggle_pause),
+        Command::new("next", "Play the next track")
+            .msg(|| Msg::Playlist(PLMsg::NextSong))
+            .key(|keys| keys.global_player_next),
+        Command::new("previous", "Play the previous track")
+            .msg(|| Msg::Playlist(PLMsg::PrevSong))
+            .key(|keys| keys.global_player_previous),
+        Command::new("volume", "Set the volume").args("<0-100>", |args| {
+            let volume: i32 = args.parse().map_err(|_| anyhow!("not a volume: {args}"))?;
+            if !(0..=100).contains(&volume) {
+                bail!("volume is between 0 and 100");
+            }
+            Ok(Action::Volume(volume))
+        }),
+        Command::new("volume-up", "Turn the volume up")
+            .msg(|| Msg::PlayerVolumeUp)
+            .key(|keys| keys.global_player_volume_plus_2),
+        Command::new("volume-down", "Turn the volume down")
+            .msg(|| Msg::PlayerVolumeDown)
+            .key(|keys| keys.global_player_volume_minus_2),
+        Command::new("speed", "Set the playback speed").args("<0.1-3.0>", |args| {
+            let speed: f64 = args.parse().map_err(|_| anyhow!("not a speed: {args}"))?;
+            if !(0.1..=3.0).contains(&speed) {
+                bail!("speed is between 0.1 and 3.0");
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            Ok(Action::Speed((speed * 10.0).round() as i32))
+        }),
+        Command::new("speed-up", "Play faster")
+            .msg(|| Msg::PlayerSpeedUp)
+            .key(|keys| keys.global_player_speed_up),
+        Command::new("speed-down", "Play slower")
+            .msg(|| Msg::PlayerSpeedDown)
+            .key(|keys| keys.global_player_speed_down),
+        Command::new("seek", "Seek to a time, or by seconds with + or -")
+            .args("<[+-]m:ss>", parse_seek),
+        Command::new("seek-forward", "Seek forward")
+            .msg(|| Msg::PlayerSeekForward)
+            .key(|keys| keys.global_player_seek_forward),
+        Command::new("seek-backward", "Seek backward")
+            .msg(|| Msg::PlayerSeekBackward)
+            .key(|keys| keys.global_player_seek_backward),
+        Command::new("chapter-next", "Seek to the next chapter")
+            .msg(|| Msg::PlayerChapterNext)
+            .key(|keys| keys.global_player_chapter_next),
+        Command::new("chapter-previous", "Seek to the previous chapter")
+            .msg(|| Msg::PlayerChapterPrevious)
+            .key(|keys| keys.global_player_chapter_previous),
+        Command::new(
+            "section-next",
+            "Seek to where the track next turns loud or quiet",
+        )
+        .msg(|| Msg::PlayerSectionNext)
+        .key(|keys| keys.global_player_section_next),
+        Command::new(
+            "section-previous",
+            "Seek to where the track last turned loud or quiet",
+        )
+        .msg(|| Msg::PlayerSectionPrevious)
+        .key(|keys| keys.global_player_section_previous),
+        Command::new("gapless", "Toggle gapless playback")
+            .msg(|| Msg::PlayerToggleGapless)
+            .key(|keys| keys.global_player_toggle_gapless),
+        Command::new("loop", "Cycle or set the loop mode")
+            .msg(|| Msg::Playlist(PLMsg::LoopModeCycle))
+            .args("[single|playlist|queue]", |args| {
+                match args.to_lowercase().as_str() {
+                    "single" => Ok(Action::Loop(Loop::Single)),
+                    "playlist" => Ok(Action::Loop(Loop::Playlist)),
+                    "queue" => Ok(Action::Loop(Loop::Queue)),
+                    _ => bail!("loop is single, playlist or queue"),
+                }
+            })
+            .key(|keys| keys.playlist_mode_cycle),
+        Command::new("shuffle", "Shuffle the playlist")
+            .msg(|| Msg::Playlist(PLMsg::Shuffle))
+            .key(|keys| keys.playlist_shuffle),
+        Command::new("shuffle-mode", "Cycle the play order, or set it")
+            .msg(|| Msg::Playlist(PLMsg::ShuffleModeCycle))
+            .args("[off|tracks|albums]", |args| {
+                match args.to_lowercase().as_str() {
+                    "off" => Ok(Action::Shuffle(Shuffle::Off)),
+                    "tracks" => Ok(Action::Shuffle(Shuffle::Tracks)),
+                    "albums" => Ok(Action::Shuffle(Shuffle::Albums)),
+                    _ => bail!("shuffle mode is off, tracks or albums"),
+                }
+            })
+            .key(|keys| keys.playlist_shuffle_mode),
+        Command::new("sort", "Sort the playlist by the next column")
+            .msg(|| Msg::Playlist(PLMsg::Sort))
+            .key(|keys| keys.playlist_sort),
+        Command::new("clear-playlist", "Remove every track from the playlist")
+            .msg(|| Msg::Playlist(PLMsg::DeleteAll))
+            .key(|keys| keys.playlist_delete_all),
+        Command::new("playlist", "Switch to a named playlist")
+            .msg(|| Msg::Playlist(PLMsg::ListNext))
+            .args("<name>", |args| {
+                Ok(Action::Msg(Msg::Playlist(PLMsg::ListSwitch(
+                    args.to_string(),
+                ))))
+            })
+            .key(|keys| keys.playlist_tab_next),
+        Command::new("playlist-new", "Create an empty playlist and switch to it")
+            .args("<name>", |args| {
+                Ok(Action::Msg(Msg::Playlist(PLMsg::ListNew(args.to_string()))))
+            }),
+        Command::new("playlist-rename", "Rename the playlist").args("<name>", |args| {
+            Ok(Action::Msg(Msg::Playlist(PLMsg::ListRename(
+                args.to_string(),
+            ))))
+        }),
+        Command::new("playlist-duplicate", "Save a copy of the playlist").args("<name>", |args| {
+            Ok(Action::Msg(Msg::Playlist(PLMsg::ListDuplicate(
+                args.to_string(),
+            ))))
+        }),
+        Command::new(
+            "playlist-delete",
+            "Delete the playlist and switch to the next one",
+        )
+        .msg(|| Msg::Playlist(PLMsg::ListDelete)),
+        Command::new("add-to", "Add the selection to a named playlist").args("<name>", |args| {
+            Ok(Action::Msg(Msg::Playlist(PLMsg::ListAddTo(
+                args.to_string(),
+            ))))
+        }),
+        Command::new("rate", "Give the selection a star more, or 0 to 5 stars")
+            .msg(|| Msg::RatingUp)
+            .args("[0-5]", |args| match args.parse::<u8>() {
+                Ok(stars) if stars <= 5 => Ok(Action::Msg(Msg::RatingSet(stars))),
+                _ => bail!("rate with 0 to 5 stars"),
+            })
+            .key(|keys| keys.global_rating_up),
+        Command::new("favourite", "Make the selection favourites, or no longer")
+            .msg(|| Msg::FavouriteToggle)
+            .key(|keys| keys.global_favourite_toggle),
+        Command::new(
+            "radio",
+            "Keep the playlist going with tracks like the last ones",
+        )
+        .msg(|| Msg::Playlist(PLMsg::RadioToggle))
+        .key(|keys| keys.playlist_radio),
+        Command::new("clear-queue", "Remove every track from up next")
+            .msg(|| Msg::Playlist(PLMsg::QueueClear)),
+        Command::new(
+            "add-front",
+            "Toggle adding tracks to the front of the playlist",
+        )
+        .msg(|| Msg::Playlist(PLMsg::AddFront))
+        .key(|keys| keys.playlist_add_front),
+        Command::new("random-tracks", "Add random tracks to the playlist")
+            .msg(|| Msg::Playlist(PLMsg::CmusTQueue))
+            .key(|keys| keys.playlist_cmus_tqueue),
+        Command::new("random-album", "Add a random album to the playlist")
+            .msg(|| Msg::Playlist(PLMsg::CmusLQueue))
+            .key(|keys| keys.playlist_cmus_lqueue),
+        Command::new("save-playlist", "Save the playlist as an m3u file")
+            .msg(|| Msg::SavePlaylistPopupShow)
+            .args("[name]", |args| {
+                Ok(Action::Msg(Msg::SavePlaylistPopupCloseOk(args.to_string())))
+            })
+            .key(|keys| keys.global_save_playlist),
+        Command::new("search-library", "Search in the library")
+            .msg(|| Msg::GeneralSearch(GSMsg::PopupShowLibrary))
+            .key(|keys| keys.library_search),
+        Command::new("search-playlist", "Search in the playlist")
+            .msg(|| Msg::GeneralSearch(GSMsg::PopupShowPlaylist))
+            .key(|keys| keys.playlist_search),
+        Command::new("search-database", "Search in the database")
+            .msg(|| Msg::GeneralSearch(GSMsg::PopupShowDatabase)),
+        Command::new("search-youtube", "Search or download a track from youtube")
+            .msg(|| Msg::YoutubeSearch(YSMsg::InputPopupShow))
+            .key(|keys| keys.library_search_youtube),
+        Command::new("add-feed", "Search for a podcast or add a feed by url")
+            .msg(|| Msg::Podcast(PCMsg::PodcastAddPopupShow))
+            .key(|keys| keys.podcast_search_add_feed),
+        Command::new("refresh-feeds", "Check every podcast for new episodes")
+            .msg(|| Msg::Podcast(PCMsg::PodcastRefreshAll))
+            .key(|keys| keys.podcast_refresh_all_feeds),
+        Command::new("tag-editor", "Edit the tags of the library selection")
+            .args("", |args| match args {
+                "" => Ok(Action::TagEditor),
+                _ => bail!("tag-editor takes no argument"),
+            })
+            .key(|keys| keys.library_tag_editor_open),
+        Command::new("delete", "Delete the library selection from disk")
+            .msg(|| Msg::DeleteConfirmShow)
+            .key(|keys| keys.library_delete),
+        Command::new("lyric-cycle", "Show the next embedded lyric")
+            .msg(|| Msg::LyricCycle)
+            .key(|keys| keys.global_lyric_cycle),
+        Command::new("lyric-forward", "Show the lyric a second earlier")
+            .msg(|| Msg::LyricAdjustDelay(1000))
+            .key(|keys| keys.global_lyric_adjust_forward),
+        Command::new("lyric-backward", "Show the lyric a second later")
+            .msg(|| Msg::LyricAdjustDelay(-1000))
+            .key(|keys| keys.global_lyric_adjust_backward),
+        Command::new(
+            "lyric-scroll",
+            "Show all lyric lines, or only the current one",
+        )
+        .msg(|| Msg::LyricScrollToggle),
+        Command::new("layout-library", "Switch to the music library")
+            .msg(|| Msg::LayoutTreeView)
+            .key(|keys| keys.global_layout_treeview),
+        Command::new("layout-database", "Switch to the database")
+            .msg(|| Msg::LayoutDataBase)
+            .key(|keys| keys.global_layout_database),
+        Command::new("layout-podcast", "Switch to podcasts")
+            .msg(|| Msg::LayoutPodCast)
+            .key(|keys| keys.global_layout_podcast),
+        Command::new("layout-cycle", "Cycle the layouts of the current view")
+            .msg(|| Msg::LayoutCycle)
+            .key(|keys| keys.global_layout_cycle),
+        Command::new("mini", "Toggle the mini player")
+            .msg(|| Msg::LayoutMiniToggle)
+            .key(|keys| keys.global_mini_toggle),
+        Command::new("album-art", "Hide or show the album art")
+            .msg(|| Msg::Xywh(XYWHMsg::Hide))
+            .key(|keys| keys.global_xywh_hide),
+        Command::new("config", "Open the configuration editor")
+            .msg(|| Msg::ConfigEditor(ConfigEditorMsg::Open))
+            .key(|keys| keys.global_config_open),
+        Command::new("help", "Show the key bindings")
+            .msg(|| Msg::HelpPopupShow)
+            .key(|keys| keys.global_help),
+        Command::new("quit", "Quit termusic")
+            .msg(|| Msg::QuitPopupShow)
+            .key(|keys| keys.global_quit),
+    ]
+}
+
+/// `1:30` or `90` seeks to that time, `+10` or `-1:00` by that much.
+fn parse_seek(args: &str) -> Result<Action> {
+    let (sign, time) = match args.chars().next() {
+        Some('+') => (1, &args[1..]),
+        Some('-') => (-1, &args[1..]),
+        _ => (0, args),
+    };
+    let mut seconds = 0_u64;
+    for part in time.split(':') {
+        let part: u64 = part.parse().map_err(|_| anyhow!("not a time: {args}"))?;
+        seconds = seconds * 60 + part;
+    }
+    if sign == 0 {
+        return Ok(Action::SeekTo(Duration::from_secs(seconds)));
+    }
+    let seconds = i64::try_from(seconds)?;
+    Ok(Action::Seek(sign * seconds))
+}
+
+/// How well `query` matches `text`, None if its characters don't all appear
+/// in order. Characters at the start of words and right after the previous
+/// match count more.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last: Option<usize> = None;
+    for c in query.to_lowercase().chars() {
+        let index = from + text[from..].iter().position(|t| *t == c)?;
+        score += 1;
+        if index == 0 || matches!(text[index - 1], ' ' | '-' | '_') {
+            score += 8;
+        }
+        if last.map(|last| last + 1) == Some(index) {
+            score += 10;
+        }
+        last = Some(index);
+        from = index + 1;
+    }
+    Some(score)
+}
+
+/// The commands matching `query`, best first. A match in the name beats one
+/// in the description.
+pub fn filter(commands: &[Command], query: &str) -> Vec<usize> {
+    let mut matches: Vec<(usize, usize)> = commands
+        .iter()
+        .enumerate()
+        .filter_map(|(index, command)| {
+            let name = fuzzy_score(query, command.name).map(|score| score * 2);
+            let description = fuzzy_score(query, command.description);
+            name.max(description).map(|score| (index, score))
+        })
+        .collect();
+    // stable, so equal scores keep the order of the list
+    matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    matches.into_iter().map(|(index, _)| index).collect()
+}
+
+#[derive(MockComponent)]
+pub struct CPInputPopup {
+    component: Input,
+}
+
+impl CPInputPopup {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .background(
+                    config
+                        .style_color_symbol
+                        .popup_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .popup_foreground()
+                        .unwrap_or(Color::Magenta),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .popup_border()
+                                .unwrap_or(Color::Magenta),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .title("Command: (Enter: run/Tab: choose)", Alignment::Left),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for CPInputPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::CommandPalette(CPMsg::PopupCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.perform(Cmd::Submit),
+            Event::Keyboard(KeyEvent {
+                code: Key::Tab | Key::Down,
+                ..
+            }) => return Some(Msg::CommandPalette(CPMsg::InputBlur)),
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::Changed(State::One(StateValue::String(input))) => {
+                Some(Msg::CommandPalette(CPMsg::PopupUpdate(input)))
+            }
+            CmdResult::Submit(State::One(StateValue::String(input))) => {
+                Some(Msg::CommandPalette(CPMsg::PopupCloseOk(input)))
+            }
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct CPTablePopup {
+    component: Table,
+    keys: Keys,
+}
+
+impl CPTablePopup {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .popup_border()
+                                .unwrap_or(Color::Magenta),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .popup_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .popup_foreground()
+                        .unwrap_or(Color::Magenta),
+                )
+                .title("Commands:(Enter: run)", Alignment::Left)
+                .scroll(true)
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .popup_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&["Command", "Key", "Description"])
+                .column_spacing(2)
+                .widths(&[35, 15, 50])
+                .table(
+                    TableBuilder::default()
+                        .add_col(TextSpan::from("No command matches."))
+                        .build(),
+                ),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for CPTablePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::CommandPalette(CPMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_quit.key_event() => {
+                return Some(Msg::CommandPalette(CPMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                return Some(Msg::CommandPalette(CPMsg::TableBlur))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => return Some(Msg::CommandPalette(CPMsg::PopupCloseOkSelected)),
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+impl Model {
+    /// Lists the commands matching the first word of `input`.
+    pub fn command_palette_update(&mut self, input: &str) {
+        let query = input.split_whitespace().next().unwrap_or_default();
+        let commands = commands();
+        let mut table = TableBuilder::default();
+        for (row, index) in filter(&commands, query).into_iter().enumerate() {
+            if row > 0 {
+                table.add_row();
+            }
+            let command = &commands[index];
+            let name = format!("{} {}", command.name, command.args);
+            let key = command
+                .binding(&self.config.keys)
+                .map(|binding| binding.to_string())
+                .unwrap_or_default();
+            table
+                .add_col(TextSpan::new(name.trim_end()).bold())
+                .add_col(TextSpan::new(key).fg(Color::Cyan))
+                .add_col(TextSpan::new(command.description));
+        }
+        let mut table = table.build();
+        if table.is_empty() || table[0].is_empty() {
+            table = TableBuilder::default()
+                .add_col(TextSpan::from("No command matches."))
+                .build();
+        }
+        self.app
+            .attr(
+                &Id::CommandPaletteTable,
+                Attribute::Content,
+                AttrValue::Table(table),
+            )
+            .ok();
+        // back to the best match
+        self.app
+            .attr(
+                &Id::CommandPaletteTable,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(0))),
+            )
+            .ok();
+    }
+
+    /// The command chosen in the list, if the list has the focus.
+    pub fn command_palette_selected(&self) -> Option<String> {
+        let Ok(State::One(StateValue::Usize(index))) = self.app.state(&Id::CommandPaletteTable)
+        else {
+            return None;
+        };
+        let Ok(Some(AttrValue::Table(table))) =
+            self.app.query(&Id::CommandPaletteTable, Attribute::Content)
+        else {
+            return None;
+        };
+        let name = table.get(index)?.first()?.content.clone();
+        name.split_whitespace().next().map(ToString::to_string)
+    }
+
+    /// Runs the command typed in `input`, or `selected` with the argument
+    /// typed after the command. A name that isn't complete runs the best
+    /// match.
+    pub fn command_palette_run(&mut self, input: &str, selected: Option<&str>) -> Option<Msg> {
+        let mut words = input.trim().splitn(2, char::is_whitespace);
+        let query = words.next().unwrap_or_default();
+        let args = words.next().unwrap_or_default();
+        if query.is_empty() && selected.is_none() {
+            return None;
+        }
+        let commands = commands();
+        let command = match selected {
+            Some(name) => commands.iter().find(|command| command.name == name),
+            None => commands
+                .iter()
+                .find(|command| command.name == query)
+                .or_else(|| {
+                    filter(&commands, query)
+                        .first()
+                        .map(|index| &commands[*index])
+                }),
+        }?;
+        let action = match command.run(args) {
+            Ok(action) => action,
+            Err(e) => {
+                self.mount_error_popup(format!("{}: {e}", command.name));
+                return None;
+            }
+        };
+        match action {
+            Action::Msg(msg) => return Some(msg),
+            Action::Volume(volume) => {
+                self.player.set_volume(volume);
+                self.config.volume = self.player.volume();
+                self.progress_update_title();
+            }
+            Action::Speed(speed) => {
+                self.player.set_speed(speed);
+                self.player_speed_changed();
+            }
+            Action::Seek(offset) => self.player_seek(offset),
+            Action::SeekTo(position) => {
+                self.player.seek_to(position);
+            }
+            Action::Loop(mode) => {
+                // three modes, so at most two steps away
+                for _ in 0..2 {
+                    if self.config.loop_mode == mode {
+                        break;
+                    }
+                    self.config.loop_mode = self.player.playlist.cycle_loop_mode();
+                }
+                self.playlist_sync();
+            }
+            Action::Shuffle(mode) => {
+                for _ in 0..2 {
+                    if self.config.shuffle == mode {
+                        break;
+                    }
+                    self.config.shuffle = self.player.playlist.cycle_shuffle();
+                }
+                self.playlist_update_title();
+            }
+            Action::TagEditor => {
+                if let Ok(State::One(StateValue::String(node))) = self.app.state(&Id::Library) {
+                    return Some(Msg::TagEditor(TEMsg::TagEditorRun(node)));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_filter_and_parse_commands() {
+        assert_eq!(fuzzy_score("vol", "volume"), Some(3 + 8 + 20));
+        assert_eq!(fuzzy_score("vd", "volume-down"), Some(2 + 16));
+        assert_eq!(fuzzy_score("xyz", "volume"), None);
+
+        let commands = commands();
+        let names = |query: &str| -> Vec<&str> {
+            filter(&commands, query)
+                .into_iter()
+                .map(|index| commands[index].name)
+                .take(3)
+                .collect()
+        };
+        assert_eq!(names("vol"), vec!["volume", "volume-up", "volume-down"]);
+        assert_eq!(names("sp")[0], "speed");
+        assert_eq!(names("secp")[0], "section-previous");
+        assert_eq!(filter(&commands, "").len(), commands.len());
+
+        let seek = |args: &str| match parse_seek(args) {
+            Ok(Action::SeekTo(position)) => format!("to {}", position.as_secs()),
+            Ok(Action::Seek(offset)) => format!("by {offset}"),
+            _ => "error".to_string(),
+        };
+        assert_eq!(seek("1:30"), "to 90");
+        assert_eq!(seek("1:00:05"), "to 3605");
+        assert_eq!(seek("+10"), "by 10");
+        assert_eq!(seek("-1:00"), "by -60");
+        assert_eq!(seek("soon"), "error");
+
+        let volume = commands.iter().find(|c| c.name == "volume").unwrap();
+        assert!(matches!(volume.run(" 40 "), Ok(Action::Volume(40))));
+        assert!(volume.run("140").is_err());
+        assert!(volume.run("").is_err());
+        let shuffle = commands.iter().find(|c| c.name == "shuffle").unwrap();
+        assert!(matches!(
+            shuffle.run(""),
+            Ok(Action::Msg(Msg::Playlist(PLMsg::Shuffle)))
+        ));
+        assert!(shuffle.run("now").is_err());
+        let save = commands.iter().find(|c| c.name == "save-playlist").unwrap();
+        assert!(matches!(
+            save.run("road trip"),
+            Ok(Action::Msg(Msg::SavePlaylistPopupCloseOk(name))) if name == "road trip"
+        ));
+        assert!(matches!(
+            commands
+                .iter()
+                .find(|c| c.name == "loop")
+                .unwrap()
+                .run("Single"),
+            Ok(Action::Loop(Loop::Single))
+        ));
+    }
+}
@@ -1117,6 +1117,7 @@ impl KEModifierSelect {
         let keys = &config.keys;
         match id {
             IdKey::DatabaseAddAll => keys.database_add_all.mod_key(),
+            IdKey::DatabaseAddQueue => keys.database_add_queue.mod_key(),
             IdKey::GlobalConfig => keys.global_config_open.mod_key(),
             IdKey::GlobalDown => keys.global_down.mod_key(),
             IdKey::GlobalGotoBottom => keys.global_goto_bottom.mod_key(),
@@ -1160,9 +1161,15 @@ impl KEModifierSelect {
             IdKey::PlaylistSwapUp => keys.playlist_swap_up.mod_key(),
             IdKey::PlaylistLqueue => keys.playlist_cmus_lqueue.mod_key(),
             IdKey::PlaylistTqueue => keys.playlist_cmus_tqueue.mod_key(),
+            IdKey::PlaylistSort => keys.playlist_sort.mod_key(),
+            IdKey::PlaylistShuffleMode => keys.playlist_shuffle_mode.mod_key(),
+            IdKey::PlaylistTabNext => keys.playlist_tab_next.mod_key(),
+            IdKey::PlaylistTabPrevious => keys.playlist_tab_previous.mod_key(),
+            IdKey::PlaylistRadio => keys.playlist_radio.mod_key(),
             IdKey::LibrarySwitchRoot => keys.library_switch_root.mod_key(),
             IdKey::LibraryAddRoot => keys.library_add_root.mod_key(),
             IdKey::LibraryRemoveRoot => keys.library_remove_root.mod_key(),
+            IdKey::LibraryAddQueue => keys.library_add_queue.mod_key(),
             IdKey::GlobalLayoutPodcast => keys.global_layout_podcast.mod_key(),
             IdKey::GlobalXywhMoveLeft => keys.global_xywh_move_left.mod_key(),
             IdKey::GlobalXywhMoveRight => keys.global_xywh_move_right.mod_key(),
@@ -1171,15 +1178,28 @@ impl KEModifierSelect {
             IdKey::GlobalXywhZoomIn => keys.global_xywh_zoom_in.mod_key(),
             IdKey::GlobalXywhZoomOut => keys.global_xywh_zoom_out.mod_key(),
             IdKey::GlobalXywhHide => keys.global_xywh_hide.mod_key(),
+            IdKey::GlobalLayoutCycle => keys.global_layout_cycle.mod_key(),
+            IdKey::GlobalMiniToggle => keys.global_mini_toggle.mod_key(),
+            IdKey::GlobalCommandPalette => keys.global_command_palette.mod_key(),
             IdKey::PodcastMarkPlayed => keys.podcast_mark_played.mod_key(),
             IdKey::PodcastMarkAllPlayed => keys.podcast_mark_all_played.mod_key(),
             IdKey::PodcastEpDownload => keys.podcast_episode_download.mod_key(),
             IdKey::PodcastEpDeleteFile => keys.podcast_episode_delete_file.mod_key(),
+            IdKey::PodcastAddQueue => keys.podcast_add_queue.mod_key(),
+            IdKey::LyricSearch => keys.lyric_search.mod_key(),
             IdKey::PodcastDeleteFeed => keys.podcast_delete_feed.mod_key(),
             IdKey::PodcastDeleteAllFeeds => keys.podcast_delete_all_feeds.mod_key(),
             IdKey::PodcastSearchAddFeed => keys.podcast_search_add_feed.mod_key(),
             IdKey::PodcastRefreshFeed => keys.podcast_refresh_feed.mod_key(),
             IdKey::PodcastRefreshAllFeeds => keys.podcast_refresh_all_feeds.mod_key(),
+            IdKey::GlobalPlayerChapterNext => keys.global_player_chapter_next.mod_key(),
+            IdKey::GlobalPlayerChapterPrevious => keys.global_player_chapter_previous.mod_key(),
+            IdKey::GlobalPlayerSectionNext => keys.global_player_section_next.mod_key(),
+            IdKey::GlobalPlayerSectionPrevious => keys.global_player_section_previous.mod_key(),
+            IdKey::GlobalRatingUp => keys.global_rating_up.mod_key(),
+            IdKey::GlobalRatingDown => keys.global_rating_down.mod_key(),
+            IdKey::GlobalFavouriteToggle => keys.global_favourite_toggle.mod_key(),
+            IdKey::PodcastFeedSettings => keys.podcast_feed_settings.mod_key(),
         }
     }
 
@@ -2780,6 +2800,31 @@ impl Component<Msg, NoUserEvent> for ConfigGlobalXywhHide {
     }
 }
 
+#[derive(MockComponent)]
+pub struct ConfigGlobalLayoutCycle {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalLayoutCycle {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Cycle layouts ",
+                IdKey::GlobalLayoutCycle,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalLayoutCycleBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalLayoutCycleBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalLayoutCycle {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
 #[derive(MockComponent)]
 pub struct ConfigPodcastMarkPlayed {
     component: KEModifierSelect,
@@ -3018,3 +3063,506 @@ impl Component<Msg, NoUserEvent> for ConfigPodcastRefreshAllFeeds {
         self.component.on(ev)
     }
 }
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalPlayerChapterNext {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalPlayerChapterNext {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Next chapter ",
+                IdKey::GlobalPlayerChapterNext,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerChapterNextBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerChapterNextBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalPlayerChapterNext {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalPlayerChapterPrevious {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalPlayerChapterPrevious {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Previous chapter ",
+                IdKey::GlobalPlayerChapterPrevious,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerChapterPreviousBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerChapterPreviousBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalPlayerChapterPrevious {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPodcastFeedSettings {
+    component: KEModifierSelect,
+}
+
+impl ConfigPodcastFeedSettings {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Feed settings ",
+                IdKey::PodcastFeedSettings,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PodcastFeedSettingsBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastFeedSettingsBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPodcastFeedSettings {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalMiniToggle {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalMiniToggle {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Mini player ",
+                IdKey::GlobalMiniToggle,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalMiniToggleBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalMiniToggleBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalMiniToggle {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalPlayerSectionNext {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalPlayerSectionNext {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Section Next ",
+                IdKey::GlobalPlayerSectionNext,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerSectionNextBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerSectionNextBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalPlayerSectionNext {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalPlayerSectionPrevious {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalPlayerSectionPrevious {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Section Previous ",
+                IdKey::GlobalPlayerSectionPrevious,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerSectionPreviousBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerSectionPreviousBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalPlayerSectionPrevious {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistSort {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistSort {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Playlist Sort ",
+                IdKey::PlaylistSort,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistSortBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistSortBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPlaylistSort {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalCommandPalette {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalCommandPalette {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Command palette ",
+                IdKey::GlobalCommandPalette,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalCommandPaletteBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalCommandPaletteBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalCommandPalette {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigLibraryAddQueue {
+    component: KEModifierSelect,
+}
+
+impl ConfigLibraryAddQueue {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Library Add Up Next ",
+                IdKey::LibraryAddQueue,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryAddQueueBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryAddQueueBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigLibraryAddQueue {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigDatabaseAddQueue {
+    component: KEModifierSelect,
+}
+
+impl ConfigDatabaseAddQueue {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Database Add Up Next ",
+                IdKey::DatabaseAddQueue,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::DatabaseAddQueueBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::DatabaseAddQueueBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigDatabaseAddQueue {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPodcastAddQueue {
+    component: KEModifierSelect,
+}
+
+impl ConfigPodcastAddQueue {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Podcast Add Up Next ",
+                IdKey::PodcastAddQueue,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastAddQueueBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastAddQueueBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPodcastAddQueue {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigLyricSearch {
+    component: KEModifierSelect,
+}
+
+impl ConfigLyricSearch {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Lyric Search Transcript ",
+                IdKey::LyricSearch,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LyricSearchBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LyricSearchBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigLyricSearch {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistShuffleMode {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistShuffleMode {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                "  Playlist Shuffle Mode  ",
+                IdKey::PlaylistShuffleMode,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PlaylistShuffleModeBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistShuffleModeBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPlaylistShuffleMode {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistTabNext {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistTabNext {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                "  Playlist Tab Next  ",
+                IdKey::PlaylistTabNext,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistTabNextBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistTabNextBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPlaylistTabNext {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistTabPrevious {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistTabPrevious {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                "  Playlist Tab Previous  ",
+                IdKey::PlaylistTabPrevious,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PlaylistTabPreviousBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistTabPreviousBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPlaylistTabPrevious {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPlaylistRadio {
+    component: KEModifierSelect,
+}
+
+impl ConfigPlaylistRadio {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                "  Playlist Radio  ",
+                IdKey::PlaylistRadio,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistRadioBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PlaylistRadioBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPlaylistRadio {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalRatingUp {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalRatingUp {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Rating Up ",
+                IdKey::GlobalRatingUp,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalRatingUpBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalRatingUpBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalRatingUp {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalRatingDown {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalRatingDown {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Rating Down ",
+                IdKey::GlobalRatingDown,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalRatingDownBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalRatingDownBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalRatingDown {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalFavouriteToggle {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalFavouriteToggle {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Favourite Toggle ",
+                IdKey::GlobalFavouriteToggle,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalFavouriteToggleBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalFavouriteToggleBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalFavouriteToggle {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::config::{load_alacritty, BindingForEvent, ColorTermusic};
+use crate::config::{load_theme, BindingForEvent, ColorTermusic};
 /**
  * MIT License
  *
@@ -232,8 +232,9 @@ impl Model {
                     let path = PathBuf::from(t);
                     if let Some(n) = path.file_stem() {
                         self.config.theme_selected = n.to_string_lossy().to_string();
-                        if let Ok(theme) = load_alacritty(t) {
-                            self.ce_style_color_symbol.alacritty_theme = theme;
+                        match load_theme(t) {
+                            Ok(theme) => self.ce_style_color_symbol.apply_theme(theme),
+                            Err(e) => self.mount_error_popup(format!("theme load error: {e}")),
                         }
                     }
                 }
@@ -271,7 +272,7 @@ impl Model {
     fn update_key_focus(&mut self, msg: &KFMsg) {
         match msg {
             // Focus of key global page
-            KFMsg::GlobalXywhHideBlurDown | KFMsg::GlobalLeftBlurUp => {
+            KFMsg::GlobalFavouriteToggleBlurDown | KFMsg::GlobalLeftBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalQuit)))
                     .ok();
@@ -494,16 +495,89 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::GlobalXywhZoomOutBlurDown | KFMsg::GlobalQuitBlurUp => {
+            KFMsg::GlobalXywhZoomOutBlurDown | KFMsg::GlobalLayoutCycleBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalXywhHide,
                     )))
                     .ok();
             }
+            KFMsg::GlobalXywhHideBlurDown | KFMsg::GlobalMiniToggleBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalLayoutCycle,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalLayoutCycleBlurDown | KFMsg::PlaylistSortBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalMiniToggle,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalMiniToggleBlurDown | KFMsg::GlobalCommandPaletteBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)))
+                    .ok();
+            }
+            KFMsg::PlaylistSortBlurDown | KFMsg::PlaylistShuffleModeBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalCommandPalette,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalCommandPaletteBlurDown | KFMsg::PlaylistTabNextBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistShuffleMode,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistShuffleModeBlurDown | KFMsg::PlaylistTabPreviousBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistTabNext,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistTabNextBlurDown | KFMsg::PlaylistRadioBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PlaylistTabPrevious,
+                    )))
+                    .ok();
+            }
+            KFMsg::PlaylistTabPreviousBlurDown | KFMsg::GlobalRatingUpBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRadio)))
+                    .ok();
+            }
+            KFMsg::PlaylistRadioBlurDown | KFMsg::GlobalRatingDownBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalRatingUp,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalRatingUpBlurDown | KFMsg::GlobalFavouriteToggleBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalRatingDown,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalRatingDownBlurDown | KFMsg::GlobalQuitBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalFavouriteToggle,
+                    )))
+                    .ok();
+            }
 
             // Focus of key 2 page
-            KFMsg::PodcastSearchAddFeedBlurDown | KFMsg::LibraryDeleteBlurUp => {
+            KFMsg::LyricSearchBlurDown | KFMsg::LibraryDeleteBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::LibraryTagEditor,
@@ -726,20 +800,83 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::PodcastRefreshAllFeedsBlurDown | KFMsg::LibraryTagEditorBlurUp => {
+            KFMsg::PodcastRefreshAllFeedsBlurDown | KFMsg::GlobalPlayerChapterNextBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::PodcastSearchAddFeed,
                     )))
                     .ok();
             }
+            KFMsg::PodcastSearchAddFeedBlurDown | KFMsg::GlobalPlayerChapterPreviousBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerChapterNext,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerChapterNextBlurDown | KFMsg::PodcastFeedSettingsBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerChapterPrevious,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerChapterPreviousBlurDown | KFMsg::GlobalPlayerSectionNextBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PodcastFeedSettings,
+                    )))
+                    .ok();
+            }
+            KFMsg::PodcastFeedSettingsBlurDown | KFMsg::GlobalPlayerSectionPreviousBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerSectionNext,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerSectionNextBlurDown | KFMsg::LibraryAddQueueBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerSectionPrevious,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerSectionPreviousBlurDown | KFMsg::DatabaseAddQueueBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::LibraryAddQueue,
+                    )))
+                    .ok();
+            }
+            KFMsg::LibraryAddQueueBlurDown | KFMsg::PodcastAddQueueBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::DatabaseAddQueue,
+                    )))
+                    .ok();
+            }
+            KFMsg::DatabaseAddQueueBlurDown | KFMsg::LyricSearchBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PodcastAddQueue,
+                    )))
+                    .ok();
+            }
+            KFMsg::PodcastAddQueueBlurDown | KFMsg::LibraryTagEditorBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::LyricSearch)))
+                    .ok();
+            }
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     fn update_key(&mut self, id: &IdKey, binding: &BindingForEvent) {
         self.config_changed = true;
         match id {
             IdKey::DatabaseAddAll => self.ke_key_config.database_add_all = *binding,
+            IdKey::DatabaseAddQueue => self.ke_key_config.database_add_queue = *binding,
             IdKey::GlobalConfig => self.ke_key_config.global_config_open = *binding,
             IdKey::GlobalDown => self.ke_key_config.global_down = *binding,
             IdKey::GlobalGotoBottom => self.ke_key_config.global_goto_bottom = *binding,
@@ -795,9 +932,15 @@ impl Model {
             IdKey::PlaylistSwapUp => self.ke_key_config.playlist_swap_up = *binding,
             IdKey::PlaylistLqueue => self.ke_key_config.playlist_cmus_lqueue = *binding,
             IdKey::PlaylistTqueue => self.ke_key_config.playlist_cmus_tqueue = *binding,
+            IdKey::PlaylistSort => self.ke_key_config.playlist_sort = *binding,
+            IdKey::PlaylistShuffleMode => self.ke_key_config.playlist_shuffle_mode = *binding,
+            IdKey::PlaylistTabNext => self.ke_key_config.playlist_tab_next = *binding,
+            IdKey::PlaylistTabPrevious => self.ke_key_config.playlist_tab_previous = *binding,
+            IdKey::PlaylistRadio => self.ke_key_config.playlist_radio = *binding,
             IdKey::LibrarySwitchRoot => self.ke_key_config.library_switch_root = *binding,
             IdKey::LibraryAddRoot => self.ke_key_config.library_add_root = *binding,
             IdKey::LibraryRemoveRoot => self.ke_key_config.library_remove_root = *binding,
+            IdKey::LibraryAddQueue => self.ke_key_config.library_add_queue = *binding,
             IdKey::GlobalLayoutPodcast => self.ke_key_config.global_layout_podcast = *binding,
             IdKey::GlobalXywhMoveLeft => self.ke_key_config.global_xywh_move_left = *binding,
             IdKey::GlobalXywhMoveRight => self.ke_key_config.global_xywh_move_right = *binding,
@@ -806,10 +949,15 @@ impl Model {
             IdKey::GlobalXywhZoomIn => self.ke_key_config.global_xywh_zoom_in = *binding,
             IdKey::GlobalXywhZoomOut => self.ke_key_config.global_xywh_zoom_out = *binding,
             IdKey::GlobalXywhHide => self.ke_key_config.global_xywh_hide = *binding,
+            IdKey::GlobalLayoutCycle => self.ke_key_config.global_layout_cycle = *binding,
+            IdKey::GlobalMiniToggle => self.ke_key_config.global_mini_toggle = *binding,
+            IdKey::GlobalCommandPalette => self.ke_key_config.global_command_palette = *binding,
             IdKey::PodcastMarkPlayed => self.ke_key_config.podcast_mark_played = *binding,
             IdKey::PodcastMarkAllPlayed => self.ke_key_config.podcast_mark_all_played = *binding,
             IdKey::PodcastEpDownload => self.ke_key_config.podcast_episode_download = *binding,
             IdKey::PodcastEpDeleteFile => self.ke_key_config.podcast_episode_delete_file = *binding,
+            IdKey::PodcastAddQueue => self.ke_key_config.podcast_add_queue = *binding,
+            IdKey::LyricSearch => self.ke_key_config.lyric_search = *binding,
             IdKey::PodcastDeleteFeed => self.ke_key_config.podcast_delete_feed = *binding,
             IdKey::PodcastDeleteAllFeeds => self.ke_key_config.podcast_delete_all_feeds = *binding,
             IdKey::PodcastSearchAddFeed => self.ke_key_config.podcast_search_add_feed = *binding,
@@ -817,6 +965,22 @@ impl Model {
             IdKey::PodcastRefreshAllFeeds => {
                 self.ke_key_config.podcast_refresh_all_feeds = *binding;
             }
+            IdKey::GlobalPlayerChapterNext => {
+                self.ke_key_config.global_player_chapter_next = *binding;
+            }
+            IdKey::GlobalPlayerChapterPrevious => {
+                self.ke_key_config.global_player_chapter_previous = *binding;
+            }
+            IdKey::GlobalPlayerSectionNext => {
+                self.ke_key_config.global_player_section_next = *binding;
+            }
+            IdKey::GlobalPlayerSectionPrevious => {
+                self.ke_key_config.global_player_section_previous = *binding;
+            }
+            IdKey::GlobalRatingUp => self.ke_key_config.global_rating_up = *binding,
+            IdKey::GlobalRatingDown => self.ke_key_config.global_rating_down = *binding,
+            IdKey::GlobalFavouriteToggle => self.ke_key_config.global_favourite_toggle = *binding,
+            IdKey::PodcastFeedSettings => self.ke_key_config.podcast_feed_settings = *binding,
         }
     }
 
@@ -23,29 +23,35 @@
  */
 use crate::config::{LastPosition, SeekStep, Settings};
 use crate::ui::components::{
-    AlbumPhotoAlign, CEHeader, CEThemeSelectTable, ConfigDatabaseAddAll, ConfigGlobalConfig,
-    ConfigGlobalDown, ConfigGlobalGotoBottom, ConfigGlobalGotoTop, ConfigGlobalHelp,
+    AlbumPhotoAlign, CEHeader, CEThemeSelectTable, ConfigDatabaseAddAll, ConfigDatabaseAddQueue,
+    ConfigGlobalCommandPalette, ConfigGlobalConfig, ConfigGlobalDown, ConfigGlobalFavouriteToggle,
+    ConfigGlobalGotoBottom, ConfigGlobalGotoTop, ConfigGlobalHelp, ConfigGlobalLayoutCycle,
     ConfigGlobalLayoutDatabase, ConfigGlobalLayoutPodcast, ConfigGlobalLayoutTreeview,
     ConfigGlobalLeft, ConfigGlobalLyricAdjustBackward, ConfigGlobalLyricAdjustForward,
-    ConfigGlobalLyricCycle, ConfigGlobalPlayerNext, ConfigGlobalPlayerPrevious,
+    ConfigGlobalLyricCycle, ConfigGlobalMiniToggle, ConfigGlobalPlayerChapterNext,
+    ConfigGlobalPlayerChapterPrevious, ConfigGlobalPlayerNext, ConfigGlobalPlayerPrevious,
+    ConfigGlobalPlayerSectionNext, ConfigGlobalPlayerSectionPrevious,
     ConfigGlobalPlayerSeekBackward, ConfigGlobalPlayerSeekForward, ConfigGlobalPlayerSpeedDown,
     ConfigGlobalPlayerSpeedUp, ConfigGlobalPlayerToggleGapless, ConfigGlobalPlayerTogglePause,
-    ConfigGlobalQuit, ConfigGlobalRight, ConfigGlobalSavePlaylist, ConfigGlobalUp,
-    ConfigGlobalVolumeDown, ConfigGlobalVolumeUp, ConfigGlobalXywhHide, ConfigGlobalXywhMoveDown,
-    ConfigGlobalXywhMoveLeft, ConfigGlobalXywhMoveRight, ConfigGlobalXywhMoveUp,
-    ConfigGlobalXywhZoomIn, ConfigGlobalXywhZoomOut, ConfigLibraryAddRoot, ConfigLibraryBackground,
+    ConfigGlobalQuit, ConfigGlobalRatingDown, ConfigGlobalRatingUp, ConfigGlobalRight,
+    ConfigGlobalSavePlaylist, ConfigGlobalUp, ConfigGlobalVolumeDown, ConfigGlobalVolumeUp,
+    ConfigGlobalXywhHide, ConfigGlobalXywhMoveDown, ConfigGlobalXywhMoveLeft,
+    ConfigGlobalXywhMoveRight, ConfigGlobalXywhMoveUp, ConfigGlobalXywhZoomIn,
+    ConfigGlobalXywhZoomOut, ConfigLibraryAddQueue, ConfigLibraryAddRoot, ConfigLibraryBackground,
     ConfigLibraryBorder, ConfigLibraryDelete, ConfigLibraryForeground, ConfigLibraryHighlight,
     ConfigLibraryHighlightSymbol, ConfigLibraryLoadDir, ConfigLibraryPaste,
     ConfigLibraryRemoveRoot, ConfigLibrarySearch, ConfigLibrarySearchYoutube,
     ConfigLibrarySwitchRoot, ConfigLibraryTagEditor, ConfigLibraryTitle, ConfigLibraryYank,
-    ConfigLyricBackground, ConfigLyricBorder, ConfigLyricForeground, ConfigLyricTitle,
-    ConfigPlaylistAddFront, ConfigPlaylistBackground, ConfigPlaylistBorder, ConfigPlaylistDelete,
-    ConfigPlaylistDeleteAll, ConfigPlaylistForeground, ConfigPlaylistHighlight,
-    ConfigPlaylistHighlightSymbol, ConfigPlaylistLqueue, ConfigPlaylistModeCycle,
-    ConfigPlaylistPlaySelected, ConfigPlaylistSearch, ConfigPlaylistShuffle,
-    ConfigPlaylistSwapDown, ConfigPlaylistSwapUp, ConfigPlaylistTitle, ConfigPlaylistTqueue,
-    ConfigPodcastDeleteAllFeeds, ConfigPodcastDeleteFeed, ConfigPodcastEpDeleteFile,
-    ConfigPodcastEpDownload, ConfigPodcastMarkAllPlayed, ConfigPodcastMarkPlayed,
+    ConfigLyricBackground, ConfigLyricBorder, ConfigLyricForeground, ConfigLyricSearch,
+    ConfigLyricTitle, ConfigPlaylistAddFront, ConfigPlaylistBackground, ConfigPlaylistBorder,
+    ConfigPlaylistDelete, ConfigPlaylistDeleteAll, ConfigPlaylistForeground,
+    ConfigPlaylistHighlight, ConfigPlaylistHighlightSymbol, ConfigPlaylistLqueue,
+    ConfigPlaylistModeCycle, ConfigPlaylistPlaySelected, ConfigPlaylistRadio, ConfigPlaylistSearch,
+    ConfigPlaylistShuffle, ConfigPlaylistShuffleMode, ConfigPlaylistSort, ConfigPlaylistSwapDown,
+    ConfigPlaylistSwapUp, ConfigPlaylistTabNext, ConfigPlaylistTabPrevious, ConfigPlaylistTitle,
+    ConfigPlaylistTqueue, ConfigPodcastAddQueue, ConfigPodcastDeleteAllFeeds,
+    ConfigPodcastDeleteFeed, ConfigPodcastEpDeleteFile, ConfigPodcastEpDownload,
+    ConfigPodcastFeedSettings, ConfigPodcastMarkAllPlayed, ConfigPodcastMarkPlayed,
     ConfigPodcastRefreshAllFeeds, ConfigPodcastRefreshFeed, ConfigPodcastSearchAddFeed,
     ConfigProgressBackground, ConfigProgressBorder, ConfigProgressForeground, ConfigProgressTitle,
     ConfigSavePopup, ConfigSeekStep, ExitConfirmation, Footer, GlobalListener, MusicDir,
@@ -750,6 +756,74 @@ impl Model {
             Ok(State::One(_)) => 3,
             _ => 8,
         };
+        let select_global_layout_cycle = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalLayoutCycle),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_mini_toggle = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalMiniToggle),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_sort = match self
+            .app
+            .state(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_command_palette = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalCommandPalette),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_shuffle_mode = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistShuffleMode),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_tab_next = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PlaylistTabNext,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_tab_previous = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PlaylistTabPrevious),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_radio = match self
+            .app
+            .state(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRadio)))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_rating_up = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::GlobalRatingUp,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_rating_down = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalRatingDown),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_favourite_toggle = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalFavouriteToggle),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
         assert!(self
             .terminal
@@ -773,10 +847,11 @@ impl Model {
                     .margin(0)
                     .constraints(
                         [
-                            Constraint::Ratio(1, 4),
-                            Constraint::Ratio(1, 4),
-                            Constraint::Ratio(1, 4),
-                            Constraint::Ratio(1, 4),
+                            Constraint::Ratio(1, 5),
+                            Constraint::Ratio(1, 5),
+                            Constraint::Ratio(1, 5),
+                            Constraint::Ratio(1, 5),
+                            Constraint::Ratio(1, 5),
                         ]
                         .as_ref(),
                     )
@@ -852,7 +927,9 @@ impl Model {
                             Constraint::Length(select_global_xywh_zoom_in),
                             Constraint::Length(select_global_xywh_zoom_out),
                             Constraint::Length(select_global_xywh_hide),
-                            // Constraint::Length(select_global_xywh_hide),
+                            Constraint::Length(select_global_layout_cycle),
+                            Constraint::Length(select_global_mini_toggle),
+                            Constraint::Length(select_playlist_sort),
                             // Constraint::Length(select_global_xywh_hide),
                             // Constraint::Length(select_global_xywh_hide),
                             Constraint::Min(0),
@@ -860,6 +937,25 @@ impl Model {
                         .as_ref(),
                     )
                     .split(chunks_middle[3]);
+
+                let chunks_middle_column5 = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints(
+                        [
+                            Constraint::Length(select_global_command_palette),
+                            Constraint::Length(select_playlist_shuffle_mode),
+                            Constraint::Length(select_playlist_tab_next),
+                            Constraint::Length(select_playlist_tab_previous),
+                            Constraint::Length(select_playlist_radio),
+                            Constraint::Length(select_global_rating_up),
+                            Constraint::Length(select_global_rating_down),
+                            Constraint::Length(select_global_favourite_toggle),
+                            Constraint::Min(0),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(chunks_middle[4]);
                 self.app
                     .view(&Id::ConfigEditor(IdConfigEditor::Header), f, chunks_main[0]);
                 self.app
@@ -1035,6 +1131,61 @@ impl Model {
                     f,
                     chunks_middle_column4[5],
                 );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLayoutCycle)),
+                    f,
+                    chunks_middle_column4[6],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalMiniToggle)),
+                    f,
+                    chunks_middle_column4[7],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)),
+                    f,
+                    chunks_middle_column4[8],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalCommandPalette)),
+                    f,
+                    chunks_middle_column5[0],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistShuffleMode)),
+                    f,
+                    chunks_middle_column5[1],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistTabNext)),
+                    f,
+                    chunks_middle_column5[2],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistTabPrevious)),
+                    f,
+                    chunks_middle_column5[3],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRadio)),
+                    f,
+                    chunks_middle_column5[4],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalRatingUp)),
+                    f,
+                    chunks_middle_column5[5],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalRatingDown)),
+                    f,
+                    chunks_middle_column5[6],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalFavouriteToggle)),
+                    f,
+                    chunks_middle_column5[7],
+                );
                 Self::view_config_editor_commons(f, &mut self.app);
             })
             .is_ok());
@@ -1252,6 +1403,70 @@ impl Model {
             _ => 8,
         };
 
+        let global_player_chapter_next_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerChapterNext),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let global_player_chapter_previous_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerChapterPrevious),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let podcast_feed_settings_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PodcastFeedSettings),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let global_player_section_next_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerSectionNext),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let global_player_section_previous_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerSectionPrevious),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let library_add_queue_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::LibraryAddQueue,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let database_add_queue_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::DatabaseAddQueue,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let podcast_add_queue_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PodcastAddQueue,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let lyric_search_len = match self
+            .app
+            .state(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::LyricSearch)))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
         assert!(self
             .terminal
             .raw_mut()
@@ -1274,10 +1489,11 @@ impl Model {
                     .margin(0)
                     .constraints(
                         [
-                            Constraint::Ratio(1, 4),
-                            Constraint::Ratio(1, 4),
-                            Constraint::Ratio(1, 4),
-                            Constraint::Ratio(1, 4),
+                            Constraint::Ratio(1, 5),
+                            Constraint::Ratio(1, 5),
+                            Constraint::Ratio(1, 5),
+                            Constraint::Ratio(1, 5),
+                            Constraint::Ratio(1, 5),
                         ]
                         .as_ref(),
                     )
@@ -1351,6 +1567,11 @@ impl Model {
                             Constraint::Length(podcast_refresh_feed_len),
                             Constraint::Length(podcast_refresh_all_feeds_len),
                             Constraint::Length(podcast_search_add_feed_len),
+                            Constraint::Length(global_player_chapter_next_len),
+                            Constraint::Length(global_player_chapter_previous_len),
+                            Constraint::Length(podcast_feed_settings_len),
+                            Constraint::Length(global_player_section_next_len),
+                            Constraint::Length(global_player_section_previous_len),
                             // Constraint::Length(podcast_mark_played_len),
                             // Constraint::Length(podcast_mark_all_played_len),
                             // Constraint::Length(podcast_ep_download_len),
@@ -1361,6 +1582,21 @@ impl Model {
                         .as_ref(),
                     )
                     .split(chunks_middle[3]);
+
+                let chunks_middle_column5 = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints(
+                        [
+                            Constraint::Length(library_add_queue_len),
+                            Constraint::Length(database_add_queue_len),
+                            Constraint::Length(podcast_add_queue_len),
+                            Constraint::Length(lyric_search_len),
+                            Constraint::Min(0),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(chunks_middle[4]);
                 self.app
                     .view(&Id::ConfigEditor(IdConfigEditor::Header), f, chunks_main[0]);
                 self.app
@@ -1527,6 +1763,51 @@ impl Model {
                     f,
                     chunks_middle_column4[3],
                 );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerChapterNext)),
+                    f,
+                    chunks_middle_column4[4],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerChapterPrevious)),
+                    f,
+                    chunks_middle_column4[5],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastFeedSettings)),
+                    f,
+                    chunks_middle_column4[6],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerSectionNext)),
+                    f,
+                    chunks_middle_column4[7],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerSectionPrevious)),
+                    f,
+                    chunks_middle_column4[8],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::LibraryAddQueue)),
+                    f,
+                    chunks_middle_column5[0],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::DatabaseAddQueue)),
+                    f,
+                    chunks_middle_column5[1],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastAddQueue)),
+                    f,
+                    chunks_middle_column5[2],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::LyricSearch)),
+                    f,
+                    chunks_middle_column5[3],
+                );
                 Self::view_config_editor_commons(f, &mut self.app);
             })
             .is_ok());
@@ -2194,6 +2475,14 @@ impl Model {
                 vec![],
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::DatabaseAddQueue)),
+                Box::new(ConfigDatabaseAddQueue::new(config)),
+                vec![],
+            )
+            .is_ok());
 
         assert!(self
             .app
@@ -2221,6 +2510,46 @@ impl Model {
                 vec![],
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)),
+                Box::new(ConfigPlaylistSort::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistShuffleMode)),
+                Box::new(ConfigPlaylistShuffleMode::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistTabNext)),
+                Box::new(ConfigPlaylistTabNext::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistTabPrevious)),
+                Box::new(ConfigPlaylistTabPrevious::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRadio)),
+                Box::new(ConfigPlaylistRadio::new(config)),
+                vec![],
+            )
+            .is_ok());
 
         assert!(self
             .app
@@ -2248,6 +2577,14 @@ impl Model {
                 vec![],
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::LibraryAddQueue)),
+                Box::new(ConfigLibraryAddQueue::new(config)),
+                vec![],
+            )
+            .is_ok());
 
         assert!(self
             .app
@@ -2323,6 +2660,30 @@ impl Model {
                 vec![],
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLayoutCycle)),
+                Box::new(ConfigGlobalLayoutCycle::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalMiniToggle)),
+                Box::new(ConfigGlobalMiniToggle::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalCommandPalette)),
+                Box::new(ConfigGlobalCommandPalette::new(config)),
+                vec![],
+            )
+            .is_ok());
         assert!(self
             .app
             .remount(
@@ -2355,6 +2716,22 @@ impl Model {
                 vec![],
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastAddQueue)),
+                Box::new(ConfigPodcastAddQueue::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::LyricSearch)),
+                Box::new(ConfigLyricSearch::new(config)),
+                vec![],
+            )
+            .is_ok());
         assert!(self
             .app
             .remount(
@@ -2395,17 +2772,76 @@ impl Model {
                 vec![],
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerChapterNext)),
+                Box::new(ConfigGlobalPlayerChapterNext::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerChapterPrevious)),
+                Box::new(ConfigGlobalPlayerChapterPrevious::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerSectionNext)),
+                Box::new(ConfigGlobalPlayerSectionNext::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerSectionPrevious)),
+                Box::new(ConfigGlobalPlayerSectionPrevious::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalRatingUp)),
+                Box::new(ConfigGlobalRatingUp::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalRatingDown)),
+                Box::new(ConfigGlobalRatingDown::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalFavouriteToggle)),
+                Box::new(ConfigGlobalFavouriteToggle::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastFeedSettings)),
+                Box::new(ConfigPodcastFeedSettings::new(config)),
+                vec![],
+            )
+            .is_ok());
         self.theme_select_sync();
     }
 
     #[allow(clippy::too_many_lines)]
     pub fn umount_config_editor(&mut self) {
-        self.library_reload_tree();
-        self.playlist_reload();
-        self.database_reload();
-        self.progress_reload();
-        self.mount_label_help();
-        self.lyric_reload();
+        self.styles_reload();
 
         assert!(self
             .app
@@ -2746,6 +3182,11 @@ impl Model {
                 IdKey::DatabaseAddAll,
             )))
             .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::DatabaseAddQueue,
+            )))
+            .ok();
         self.app
             .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalConfig)))
             .ok();
@@ -2760,6 +3201,27 @@ impl Model {
                 IdKey::PlaylistTqueue,
             )))
             .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistSort)))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PlaylistShuffleMode,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PlaylistTabNext,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PlaylistTabPrevious,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::PlaylistRadio)))
+            .ok();
 
         self.app
             .umount(&Id::ConfigEditor(IdConfigEditor::Key(
@@ -2778,6 +3240,11 @@ impl Model {
                 IdKey::LibraryRemoveRoot,
             )))
             .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::LibraryAddQueue,
+            )))
+            .ok();
 
         self.app
             .umount(&Id::ConfigEditor(IdConfigEditor::Key(
@@ -2825,6 +3292,21 @@ impl Model {
                 IdKey::GlobalXywhHide,
             )))
             .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalLayoutCycle,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalMiniToggle,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalCommandPalette,
+            )))
+            .ok();
 
         self.app
             .umount(&Id::ConfigEditor(IdConfigEditor::Key(
@@ -2846,6 +3328,14 @@ impl Model {
                 IdKey::PodcastEpDeleteFile,
             )))
             .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PodcastAddQueue,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::LyricSearch)))
+            .ok();
         self.app
             .umount(&Id::ConfigEditor(IdConfigEditor::Key(
                 IdKey::PodcastDeleteFeed,
@@ -2871,6 +3361,46 @@ impl Model {
                 IdKey::PodcastSearchAddFeed,
             )))
             .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalPlayerChapterNext,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalPlayerChapterPrevious,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalPlayerSectionNext,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalPlayerSectionPrevious,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalRatingUp,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalRatingDown,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalFavouriteToggle,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PodcastFeedSettings,
+            )))
+            .ok();
         assert!(self
             .app
             .remount(
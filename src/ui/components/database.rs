@@ -1,5 +1,6 @@
 use crate::config::{Keys, Settings};
 use crate::sqlite::SearchCriteria;
+use crate::track::Rating;
 use crate::ui::{DBMsg, Id, Model, Msg};
 use crate::utils::{is_playlist, playlist_get_vec};
 use std::path::Path;
@@ -28,20 +29,20 @@ impl DBListCriteria {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .database_border()
                             .unwrap_or(Color::Blue),
                     ),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .database_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .database_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .title(" DataBase ", Alignment::Left)
@@ -49,10 +50,16 @@ impl DBListCriteria {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .database_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.database),
+                )
+                .modifiers(config.style_color_symbol.styles.database.modifiers())
                 .rewind(false)
                 .step(4)
                 .scroll(true)
@@ -67,6 +74,10 @@ impl DBListCriteria {
                         .add_col(TextSpan::from("Directory"))
                         .add_row()
                         .add_col(TextSpan::from("Playlists"))
+                        .add_row()
+                        .add_col(TextSpan::from("Rating"))
+                        .add_row()
+                        .add_col(TextSpan::from("Favourites"))
                         .build(),
                 ),
             on_key_tab,
@@ -175,20 +186,20 @@ impl DBListSearchResult {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .database_border()
                             .unwrap_or(Color::Blue),
                     ),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .database_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .database_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .title(" Result ", Alignment::Left)
@@ -196,10 +207,16 @@ impl DBListSearchResult {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .database_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.database),
+                )
+                .modifiers(config.style_color_symbol.styles.database.modifiers())
                 .rewind(false)
                 .step(4)
                 .scroll(true)
@@ -327,20 +344,20 @@ impl DBListSearchTracks {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .database_border()
                             .unwrap_or(Color::Blue),
                     ),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .database_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .database_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .title(" Tracks ", Alignment::Left)
@@ -348,10 +365,16 @@ impl DBListSearchTracks {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .database_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.database),
+                )
+                .modifiers(config.style_color_symbol.styles.database.modifiers())
                 .rewind(false)
                 .step(4)
                 .scroll(true)
@@ -437,6 +460,12 @@ impl Component<Msg, NoUserEvent> for DBListSearchTracks {
             Event::Keyboard(keyevent) if keyevent == self.keys.database_add_all.key_event() => {
                 return Some(Msg::DataBase(DBMsg::AddAllToPlaylist))
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_add_queue.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::DataBase(DBMsg::AddQueue(index)));
+                }
+                CmdResult::None
+            }
 
             Event::Keyboard(keyevent) if keyevent == self.keys.library_search.key_event() => {
                 return Some(Msg::GeneralSearch(crate::ui::GSMsg::PopupShowDatabase))
@@ -460,7 +489,8 @@ impl Model {
             table
                 .add_col(TextSpan::from(format!("{}", idx + 1)))
                 .add_col(TextSpan::from(" "))
-                .add_col(TextSpan::from(record.name.to_string()));
+                .add_col(TextSpan::from(record.name.to_string()))
+                .add_col(TextSpan::from(format!(" {}", record.rating.display())));
         }
         if self.db_search_results.is_empty() {
             table.add_col(TextSpan::from("empty results"));
@@ -503,6 +533,25 @@ impl Model {
                         }
                     }
                 }
+                SearchCriteria::Rating => {
+                    let stars = record.parse().unwrap_or_default();
+                    display_name = match (Rating {
+                        stars,
+                        favourite: false,
+                    })
+                    .display()
+                    {
+                        unrated if unrated.is_empty() => "unrated".to_string(),
+                        stars => stars,
+                    };
+                }
+                SearchCriteria::Favourite => {
+                    display_name = if record == "1" {
+                        "favourites".to_string()
+                    } else {
+                        "others".to_string()
+                    };
+                }
                 _ => {
                     display_name = record.clone();
                 }
@@ -539,6 +588,11 @@ impl Model {
             SearchCriteria::Playlist => {
                 self.db_search_results = self.database_get_playlist();
             }
+            SearchCriteria::Rating | SearchCriteria::Favourite => {
+                // best first
+                self.db_search_results = self.db.get_criterias(&self.db_criteria);
+                self.db_search_results.reverse();
+            }
             _ => self.db_search_results = self.db.get_criterias(&self.db_criteria),
         }
         self.database_sync_results();
@@ -46,13 +46,13 @@ impl GSInputPopup {
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Magenta),
                 )
                 .borders(
@@ -60,7 +60,7 @@ impl GSInputPopup {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .popup_border()
                                 .unwrap_or(Color::Magenta),
                         )
                         .modifiers(BorderType::Rounded),
@@ -159,7 +159,7 @@ impl GSTablePopup {
                             .color(
                                 config
                                     .style_color_symbol
-                                    .library_border()
+                                    .popup_border()
                                     .unwrap_or(Color::Magenta),
                             )
                             .modifiers(BorderType::Rounded),
@@ -167,13 +167,13 @@ impl GSTablePopup {
                     .background(
                         config
                             .style_color_symbol
-                            .library_background()
+                            .popup_background()
                             .unwrap_or(Color::Reset),
                     )
                     .foreground(
                         config
                             .style_color_symbol
-                            .library_foreground()
+                            .popup_foreground()
                             .unwrap_or(Color::Magenta),
                     )
                     .title(title_library, Alignment::Left)
@@ -181,7 +181,7 @@ impl GSTablePopup {
                     .highlighted_color(
                         config
                             .style_color_symbol
-                            .library_highlight()
+                            .popup_highlight()
                             .unwrap_or(Color::LightBlue),
                     )
                     .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
@@ -208,7 +208,7 @@ impl GSTablePopup {
                             .color(
                                 config
                                     .style_color_symbol
-                                    .library_border()
+                                    .popup_border()
                                     .unwrap_or(Color::Magenta),
                             )
                             .modifiers(BorderType::Rounded),
@@ -216,13 +216,13 @@ impl GSTablePopup {
                     .background(
                         config
                             .style_color_symbol
-                            .library_background()
+                            .popup_background()
                             .unwrap_or(Color::Reset),
                     )
                     .foreground(
                         config
                             .style_color_symbol
-                            .library_foreground()
+                            .popup_foreground()
                             .unwrap_or(Color::Magenta),
                     )
                     .title(title_playlist, Alignment::Left)
@@ -230,7 +230,7 @@ impl GSTablePopup {
                     .highlighted_color(
                         config
                             .style_color_symbol
-                            .library_highlight()
+                            .popup_highlight()
                             .unwrap_or(Color::LightBlue),
                     )
                     .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
@@ -256,7 +256,7 @@ impl GSTablePopup {
                             .color(
                                 config
                                     .style_color_symbol
-                                    .library_border()
+                                    .popup_border()
                                     .unwrap_or(Color::Magenta),
                             )
                             .modifiers(BorderType::Rounded),
@@ -264,13 +264,13 @@ impl GSTablePopup {
                     .background(
                         config
                             .style_color_symbol
-                            .library_background()
+                            .popup_background()
                             .unwrap_or(Color::Reset),
                     )
                     .foreground(
                         config
                             .style_color_symbol
-                            .library_foreground()
+                            .popup_foreground()
                             .unwrap_or(Color::Magenta),
                     )
                     .title(title_database, Alignment::Left)
@@ -278,7 +278,7 @@ impl GSTablePopup {
                     .highlighted_color(
                         config
                             .style_color_symbol
-                            .library_highlight()
+                            .popup_highlight()
                             .unwrap_or(Color::LightBlue),
                     )
                     .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
@@ -47,13 +47,13 @@ impl LabelGeneric {
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .footer_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .footer_highlight()
                         .unwrap_or(Color::Cyan),
                 )
                 .modifiers(TextModifiers::BOLD),
@@ -83,13 +83,13 @@ impl LabelSpan {
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .footer_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .footer_highlight()
                         .unwrap_or(Color::Cyan),
                 )
                 .modifiers(TextModifiers::BOLD),
@@ -162,13 +162,13 @@ impl DownloadSpinner {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .footer_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .footer_background()
                         .unwrap_or(Color::Reset),
                 )
                 // .sequence("⣾⣽⣻⢿⡿⣟⣯⣷"),
@@ -1,11 +1,18 @@
 use crate::config::Settings;
+use crate::player::PlayerTrait;
 use crate::podcast::Episode;
+use crate::songtag::lrc::Lyric as LrcLyric;
+use crate::songtag::lyric_files;
 use crate::track::MediaType;
 use crate::ui::{model::TermusicLayout, Id, LyricMsg, Model, Msg};
 
 use anyhow::{anyhow, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tui_realm_stdlib::utils::get_block;
 use tui_realm_stdlib::Textarea;
 // use tui_realm_textarea::TextArea;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
@@ -13,7 +20,28 @@ use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
 use tuirealm::props::{
     Alignment, AttrValue, Attribute, BorderType, Borders, Color, PropPayload, PropValue, TextSpan,
 };
-use tuirealm::{Component, Event, MockComponent, State, StateValue};
+use tuirealm::tui::layout::{Constraint, Direction as LayoutDirection, Layout, Rect};
+use tuirealm::tui::style::{Modifier, Style};
+use tuirealm::tui::text::{Span, Spans};
+use tuirealm::tui::widgets::{Block, Clear, Paragraph, Wrap};
+use tuirealm::{Component, Event, Frame, MockComponent, State, StateValue};
+use unicode_width::UnicodeWidthStr;
+
+/// Words of the current line styled by whether they have been sung, drawn
+/// over the lyric text for karaoke lyrics
+pub const LYRIC_KARAOKE: &str = "karaoke";
+/// Every line of the lyrics, for the scrolling view
+pub const LYRIC_LINES: &str = "lines";
+/// Translations of the lines, shown next to them
+pub const LYRIC_TRANSLATION: &str = "translation";
+/// Index of the line playing now
+pub const LYRIC_CURRENT: &str = "current";
+
+/// How long the scrolling view stays where it was scrolled to, before it
+/// follows playback again
+const SCROLL_SNAP_BACK: Duration = Duration::from_secs(5);
+/// Lines moved by page up and down
+const SCROLL_PAGE: usize = 4;
 
 lazy_static! {
     /// Regex for finding <br/> tags -- also captures any surrounding
@@ -27,10 +55,15 @@ lazy_static! {
     static ref RE_MULT_LINE_BREAKS: Regex = Regex::new(r"((\r\n)|\r|\n){3,}").expect("Regex error");
 }
 
-#[derive(MockComponent)]
 pub struct Lyric {
     component: Textarea,
     keys: crate::config::Keys,
+    karaoke: Vec<TextSpan>,
+    lines: Vec<String>,
+    translation: Vec<String>,
+    current: Option<usize>,
+    /// The line scrolled to by hand, and when
+    scrolled: Option<(usize, Instant)>,
 }
 
 impl Lyric {
@@ -63,12 +96,269 @@ impl Lyric {
                 // .wrap(true)
                 .step(4)
                 .highlighted_str(&config.style_color_symbol.playlist_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.lyric),
+                )
+                .modifiers(config.style_color_symbol.styles.lyric.modifiers())
                 .text_rows(&[TextSpan::new(format!(
                     "{}.",
                     crate::player::Status::Stopped
                 ))]),
             keys: config.keys.clone(),
+            karaoke: Vec::new(),
+            lines: Vec::new(),
+            translation: Vec::new(),
+            current: None,
+            scrolled: None,
+        }
+    }
+
+    /// Draws the karaoke words over `area`.
+    fn view_karaoke(&self, render: &mut Frame<'_>, area: Rect, alignment: Alignment) {
+        let background = self
+            .query(Attribute::Background)
+            .map_or(Color::Reset, AttrValue::unwrap_color);
+        let spans: Vec<Span<'_>> = self
+            .karaoke
+            .iter()
+            .map(|word| {
+                Span::styled(
+                    word.content.clone(),
+                    Style::default().fg(word.fg).add_modifier(word.modifiers),
+                )
+            })
+            .collect();
+        render.render_widget(Clear, area);
+        render.render_widget(
+            Paragraph::new(Spans::from(spans))
+                .style(Style::default().bg(background))
+                .alignment(alignment)
+                .wrap(Wrap { trim: true }),
+            area,
+        );
+    }
+
+    /// The karaoke words go where the text row of the textarea is.
+    fn view_karaoke_line(&self, render: &mut Frame<'_>, area: Rect) {
+        let borders = self
+            .query(Attribute::Borders)
+            .map_or_else(Borders::default, AttrValue::unwrap_borders);
+        let highlight_width = self
+            .query(Attribute::HighlightedStr)
+            .map_or(0, |s| s.unwrap_string().width());
+        let inner = Block::default().borders(borders.sides).inner(area);
+        let highlight_width = u16::try_from(highlight_width).unwrap_or(0).min(inner.width);
+        let area = Rect {
+            x: inner.x + highlight_width,
+            width: inner.width - highlight_width,
+            ..inner
+        };
+        self.view_karaoke(render, area, Alignment::Left);
+    }
+
+    /// All the lines, centred on the current one or the one scrolled to,
+    /// with the translation in a second column.
+    fn view_lines(&mut self, render: &mut Frame<'_>, area: Rect) {
+        let borders = self
+            .query(Attribute::Borders)
+            .map_or_else(Borders::default, AttrValue::unwrap_borders);
+        let title = self.query(Attribute::Title).map(AttrValue::unwrap_title);
+        let focus = matches!(self.query(Attribute::Focus), Some(AttrValue::Flag(true)));
+        let inactive_style = self
+            .query(Attribute::FocusStyle)
+            .map(AttrValue::unwrap_style);
+        let foreground = self
+            .query(Attribute::Foreground)
+            .map_or(Color::Reset, AttrValue::unwrap_color);
+        let background = self
+            .query(Attribute::Background)
+            .map_or(Color::Reset, AttrValue::unwrap_color);
+        let block =
+            get_block(borders, title, focus, inactive_style).style(Style::default().bg(background));
+        let inner = block.inner(area);
+        render.render_widget(block, area);
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        // unsynced lyrics stay where they were scrolled to
+        if let (Some((_, at)), Some(_)) = (self.scrolled, self.current) {
+            if at.elapsed() > SCROLL_SNAP_BACK {
+                self.scrolled = None;
+            }
+        }
+        let centre = self
+            .scrolled
+            .map_or_else(|| self.current.unwrap_or(0), |(line, _)| line);
+
+        let columns = if self.translation.is_empty() {
+            vec![inner]
+        } else {
+            Layout::default()
+                .direction(LayoutDirection::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(inner)
+        };
+        let width = usize::from(columns[0].width).max(1);
+
+        let rows = wrap_rows(&self.lines, &self.translation, width);
+        let line_rows = |line: usize| {
+            let first = rows.iter().position(|(index, _)| *index == line);
+            let height = rows.iter().filter(|(index, _)| *index == line).count();
+            (first.unwrap_or(0), height)
+        };
+        let (first, height) = line_rows(centre);
+        let top = (first + height / 2).saturating_sub(usize::from(inner.height) / 2);
+
+        let current = Style::default().fg(foreground).add_modifier(Modifier::BOLD);
+        let other = Style::default().fg(foreground).add_modifier(Modifier::DIM);
+        for (column_index, column) in columns.iter().enumerate() {
+            let text: Vec<Spans<'_>> = rows
+                .iter()
+                .skip(top)
+                .take(usize::from(inner.height))
+                .map(|(index, texts)| {
+                    let style = if Some(*index) == self.current {
+                        current
+                    } else {
+                        other
+                    };
+                    let row = texts.get(column_index).cloned().unwrap_or_default();
+                    Spans::from(Span::styled(row, style))
+                })
+                .collect();
+            render.render_widget(Paragraph::new(text).alignment(Alignment::Center), *column);
+        }
+
+        if let Some(line) = self.current.filter(|_| !self.karaoke.is_empty()) {
+            let (first, height) = line_rows(line);
+            let Some(offset) = first.checked_sub(top) else {
+                return;
+            };
+            let Ok(offset) = u16::try_from(offset) else {
+                return;
+            };
+            if offset >= inner.height {
+                return;
+            }
+            let height = u16::try_from(height)
+                .unwrap_or(u16::MAX)
+                .min(inner.height - offset);
+            let area = Rect {
+                y: columns[0].y + offset,
+                height,
+                ..columns[0]
+            };
+            self.view_karaoke(render, area, Alignment::Center);
+        }
+    }
+}
+
+/// The rows of each line wrapped to `width`, with the translation next to
+/// it, as many as the longer of the two needs.
+fn wrap_rows(lines: &[String], translation: &[String], width: usize) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let wrapped: Vec<Vec<String>> = std::iter::once(line)
+            .chain(translation.get(index))
+            .map(|text| {
+                textwrap::wrap(text, width)
+                    .into_iter()
+                    .map(|row| row.to_string())
+                    .collect()
+            })
+            .collect();
+        let height = wrapped.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        for row in 0..height {
+            let texts = wrapped
+                .iter()
+                .map(|column| column.get(row).cloned().unwrap_or_default())
+                .collect();
+            rows.push((index, texts));
+        }
+    }
+    rows
+}
+
+impl MockComponent for Lyric {
+    fn view(&mut self, render: &mut Frame<'_>, area: Rect) {
+        if !self.lines.is_empty() {
+            self.view_lines(render, area);
+            return;
+        }
+        self.component.view(render, area);
+        // drawn over the same line as plain text
+        if !self.karaoke.is_empty() {
+            self.view_karaoke_line(render, area);
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            Attribute::Custom(LYRIC_KARAOKE) => {
+                self.karaoke = value
+                    .unwrap_payload()
+                    .unwrap_vec()
+                    .into_iter()
+                    .map(PropValue::unwrap_text_span)
+                    .collect();
+            }
+            Attribute::Custom(LYRIC_LINES) => {
+                self.lines = value
+                    .unwrap_payload()
+                    .unwrap_vec()
+                    .into_iter()
+                    .map(PropValue::unwrap_str)
+                    .collect();
+                self.scrolled = None;
+            }
+            Attribute::Custom(LYRIC_TRANSLATION) => {
+                self.translation = value
+                    .unwrap_payload()
+                    .unwrap_vec()
+                    .into_iter()
+                    .map(PropValue::unwrap_str)
+                    .collect();
+            }
+            Attribute::Custom(LYRIC_CURRENT) => {
+                self.current = match value {
+                    AttrValue::Payload(PropPayload::One(PropValue::Usize(line))) => Some(line),
+                    _ => None,
+                };
+            }
+            attr => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        if self.lines.is_empty() {
+            return self.component.perform(cmd);
         }
+        let last = self.lines.len() - 1;
+        let from = self
+            .scrolled
+            .map_or_else(|| self.current.unwrap_or(0), |(line, _)| line);
+        let line = match cmd {
+            Cmd::Move(Direction::Down) => from + 1,
+            Cmd::Move(Direction::Up) => from.saturating_sub(1),
+            Cmd::Scroll(Direction::Down) => from + SCROLL_PAGE,
+            Cmd::Scroll(Direction::Up) => from.saturating_sub(SCROLL_PAGE),
+            Cmd::GoTo(Position::Begin) => 0,
+            Cmd::GoTo(Position::End) => last,
+            _ => return CmdResult::None,
+        };
+        self.scrolled = Some((line.min(last), Instant::now()));
+        CmdResult::None
     }
 }
 
@@ -121,6 +411,9 @@ impl Component<Msg, NoUserEvent> for Lyric {
             Event::Keyboard(key) if key == self.keys.global_goto_bottom.key_event() => {
                 self.perform(Cmd::GoTo(Position::End))
             }
+            Event::Keyboard(key) if key == self.keys.lyric_search.key_event() => {
+                return Some(Msg::LyricMessage(LyricMsg::TranscriptSearchPopupShow))
+            }
             _ => CmdResult::None,
         };
         Some(Msg::None)
@@ -133,6 +426,8 @@ impl Model {
             .app
             .remount(Id::Lyric, Box::new(Lyric::new(&self.config)), Vec::new())
             .is_ok());
+        // the new component has no lines until the next update
+        self.lyric_lines = None;
         self.lyric_update_title();
         let lyric_line = self.lyric_line.clone();
         self.lyric_set_lyric(&lyric_line);
@@ -204,25 +499,7 @@ impl Model {
         // remove anything more than two line breaks (i.e., one blank line)
         let no_line_breaks = RE_MULT_LINE_BREAKS.replace_all(&decoded, "\n\n");
 
-        let (term_width, _) = viuer::terminal_size();
-        let term_width = usize::from(term_width);
-        let lyric_width = term_width * 3 / 5;
-        let lines_vec: Vec<_> = no_line_breaks.split('\n').collect();
-        let mut short_string_vec: Vec<_> = Vec::new();
-        for l in lines_vec {
-            let unicode_width = unicode_width::UnicodeWidthStr::width(l);
-            if unicode_width > lyric_width {
-                let mut string_tmp = textwrap::wrap(l, lyric_width);
-                short_string_vec.append(&mut string_tmp);
-            } else {
-                short_string_vec.push(std::borrow::Cow::Borrowed(l));
-            }
-        }
-
-        let mut lines_textspan: Vec<_> = short_string_vec
-            .into_iter()
-            .map(|l| PropValue::TextSpan(TextSpan::from(l)))
-            .collect();
+        let mut lines_textspan = Self::lyric_wrap_lines(&no_line_breaks);
 
         let mut final_vec: Vec<_> = Vec::new();
         final_vec.push(PropValue::TextSpan(TextSpan::from(po_title).bold()));
@@ -239,10 +516,33 @@ impl Model {
             TextSpan::from(format!("Duration: {}", ep.format_duration())).italic(),
         ));
 
+        // the transcript of the episode playing now: the current line when
+        // it is timed, otherwise the full text after the description
+        let mut transcript_text = None;
+        if let Some(track) = self.player.playlist.current_track() {
+            if track.file() == Some(ep.url.as_str()) {
+                if let Some(line) = track.parsed_lyric().and_then(|l| l.get_text(self.time_pos)) {
+                    final_vec.push(PropValue::TextSpan(TextSpan::from("   ")));
+                    final_vec.push(PropValue::TextSpan(TextSpan::from("Transcript:").bold()));
+                    final_vec.append(&mut Self::lyric_wrap_lines(&line));
+                } else if let Some(lyrics) = track.lyric_selected() {
+                    transcript_text = Some(Self::lyric_wrap_lines(&lyrics.text));
+                }
+            }
+        }
+
         final_vec.push(PropValue::TextSpan(TextSpan::from("   ")));
         final_vec.push(PropValue::TextSpan(TextSpan::from("Description:").bold()));
         final_vec.append(&mut lines_textspan);
 
+        if let Some(mut transcript_text) = transcript_text {
+            final_vec.push(PropValue::TextSpan(TextSpan::from("   ")));
+            final_vec.push(PropValue::TextSpan(TextSpan::from("Transcript:").bold()));
+            final_vec.append(&mut transcript_text);
+        }
+
+        self.lyric_clear_lines();
+        self.lyric_set_karaoke(Vec::new());
         self.app
             .attr(
                 &Id::Lyric,
@@ -252,6 +552,50 @@ impl Model {
             .ok();
     }
 
+    /// Wraps text to the width of the lyric panel.
+    fn lyric_wrap_lines(text: &str) -> Vec<PropValue> {
+        let (term_width, _) = viuer::terminal_size();
+        let term_width = usize::from(term_width);
+        let lyric_width = term_width * 3 / 5;
+        let lines_vec: Vec<_> = text.split('\n').collect();
+        let mut short_string_vec: Vec<_> = Vec::new();
+        for l in lines_vec {
+            let unicode_width = unicode_width::UnicodeWidthStr::width(l);
+            if unicode_width > lyric_width {
+                let mut string_tmp = textwrap::wrap(l, lyric_width);
+                short_string_vec.append(&mut string_tmp);
+            } else {
+                short_string_vec.push(std::borrow::Cow::Borrowed(l));
+            }
+        }
+
+        short_string_vec
+            .into_iter()
+            .map(|l| PropValue::TextSpan(TextSpan::from(l)))
+            .collect()
+    }
+
+    /// Seeks to the next transcript or lyric line containing `phrase`.
+    pub fn lyric_search_seek(&mut self, phrase: &str) {
+        let found = self
+            .player
+            .playlist
+            .current_track()
+            .and_then(|track| track.parsed_lyric())
+            .and_then(|lyric| lyric.find_text(phrase, self.time_pos));
+        match found {
+            Some(time) => {
+                self.player
+                    .seek_to(Duration::from_secs(time.try_into().unwrap_or(0)));
+            }
+            None => self.update_show_message_timeout(
+                "Transcript search",
+                format!("\"{phrase}\" not found").as_str(),
+                None,
+            ),
+        }
+    }
+
     pub fn lyric_update(&mut self) {
         if self.layout == TermusicLayout::Podcast {
             if let Err(e) = self.lyric_update_for_podcast() {
@@ -263,13 +607,18 @@ impl Model {
             self.lyric_set_lyric("Stopped.");
             return;
         }
+        if self.config.lyric_scroll && self.lyric_update_lines() {
+            return;
+        }
         if let Some(song) = self.player.playlist.current_track() {
-            if song.lyric_frames_is_empty() {
+            // transcripts are parsed without lyric frames
+            if song.lyric_frames_is_empty() && song.parsed_lyric().is_none() {
                 self.lyric_set_lyric("No lyrics available.");
                 return;
             }
 
             let mut line = String::new();
+            let mut karaoke = Vec::new();
             if let Some(l) = song.parsed_lyric() {
                 if l.unsynced_captions.is_empty() {
                     return;
@@ -277,16 +626,150 @@ impl Model {
                 if let Some(l) = l.get_text(self.time_pos) {
                     line = l;
                 }
+                if let Some(words) = l.get_words(self.time_pos) {
+                    karaoke = self.lyric_karaoke_spans(&words);
+                }
             }
-            if self.lyric_line == line {
-                return;
+            if self.lyric_line != line {
+                self.lyric_set_lyric(&line);
+                self.lyric_line = line;
             }
-            self.lyric_set_lyric(&line);
-            self.lyric_line = line;
+            // set on every update, as more of the words are sung
+            if !karaoke.is_empty() {
+                self.lyric_set_karaoke(karaoke);
+            }
+        }
+    }
+
+    /// Updates the scrolling view. Returns false when there are no lines
+    /// to show.
+    fn lyric_update_lines(&mut self) -> bool {
+        let Some(song) = self.player.playlist.current_track() else {
+            return false;
+        };
+        let timed = song
+            .parsed_lyric()
+            .filter(|l| !l.unsynced_captions.is_empty());
+        let (current, karaoke) = match timed {
+            Some(lyric) => (
+                lyric.get_index(self.time_pos),
+                lyric
+                    .get_words(self.time_pos)
+                    .map(|words| self.lyric_karaoke_spans(&words))
+                    .unwrap_or_default(),
+            ),
+            None if song.lyric_selected().is_some() => (None, Vec::new()),
+            None => return false,
+        };
+
+        let shown = (
+            song.file().unwrap_or_default().to_string(),
+            song.lyric_selected_index(),
+        );
+        if self.lyric_lines.as_ref() != Some(&shown) {
+            let lines: Vec<String> = match timed {
+                Some(lyric) => lyric
+                    .unsynced_captions
+                    .iter()
+                    .map(|c| c.text().to_string())
+                    .collect(),
+                None => song
+                    .lyric_selected()
+                    .map(|lyrics| lyrics.text.lines().map(ToString::to_string).collect())
+                    .unwrap_or_default(),
+            };
+            // timed translations are paired with the lines by time stamp,
+            // plain ones line by line
+            let translation: Vec<String> = match (song.lyric_translation(), timed) {
+                (Some(frame), Some(lyric)) => {
+                    let translation = LrcLyric::from_str(&frame.text).ok();
+                    lyric
+                        .unsynced_captions
+                        .iter()
+                        .map(|c| {
+                            translation
+                                .as_ref()
+                                .and_then(|t| t.text_at(c.time_stamp()))
+                                .unwrap_or_default()
+                                .to_string()
+                        })
+                        .collect()
+                }
+                (Some(frame), None) => frame.text.lines().map(ToString::to_string).collect(),
+                (None, _) => Vec::new(),
+            };
+            let translation = if translation.iter().all(String::is_empty) {
+                Vec::new()
+            } else {
+                translation
+            };
+            self.lyric_set_lines(LYRIC_TRANSLATION, &translation);
+            self.lyric_set_lines(LYRIC_LINES, &lines);
+            self.lyric_lines = Some(shown);
+        }
+        let current = current.map_or(PropPayload::None, |line| {
+            PropPayload::One(PropValue::Usize(line))
+        });
+        self.app
+            .attr(
+                &Id::Lyric,
+                Attribute::Custom(LYRIC_CURRENT),
+                AttrValue::Payload(current),
+            )
+            .ok();
+        self.lyric_set_karaoke(karaoke);
+        true
+    }
+
+    fn lyric_set_lines(&mut self, attr: &'static str, lines: &[String]) {
+        self.app
+            .attr(
+                &Id::Lyric,
+                Attribute::Custom(attr),
+                AttrValue::Payload(PropPayload::Vec(
+                    lines.iter().cloned().map(PropValue::Str).collect(),
+                )),
+            )
+            .ok();
+    }
+
+    /// Goes back to the single line view of the lyrics.
+    pub fn lyric_clear_lines(&mut self) {
+        if self.lyric_lines.take().is_some() {
+            self.lyric_set_lines(LYRIC_LINES, &[]);
         }
     }
 
+    /// Sung words in bold, the words still to come dimmed.
+    fn lyric_karaoke_spans(&self, words: &[(&str, bool)]) -> Vec<PropValue> {
+        let foreground = self
+            .config
+            .style_color_symbol
+            .lyric_foreground()
+            .unwrap_or(Color::Cyan);
+        words
+            .iter()
+            .map(|(word, sung)| {
+                let mut span = TextSpan::new(word).fg(foreground);
+                span.modifiers = if *sung { Modifier::BOLD } else { Modifier::DIM };
+                PropValue::TextSpan(span)
+            })
+            .collect()
+    }
+
+    fn lyric_set_karaoke(&mut self, words: Vec<PropValue>) {
+        self.app
+            .attr(
+                &Id::Lyric,
+                Attribute::Custom(LYRIC_KARAOKE),
+                AttrValue::Payload(PropPayload::Vec(words)),
+            )
+            .ok();
+    }
+
     fn lyric_set_lyric(&mut self, text: &str) {
+        self.lyric_clear_lines();
+        self.lyric_set_karaoke(Vec::new());
         self.app
             .attr(
                 &Id::Lyric,
@@ -316,16 +799,57 @@ impl Model {
             if let Err(e) = song.adjust_lyric_delay(self.time_pos, offset) {
                 self.mount_error_popup(format!("adjust lyric delay error: {e}"));
             };
+            self.player.playlist.set_current_track(Some(&song));
+            // adjusting one line can reorder them
+            self.lyric_lines = None;
         }
     }
 
+    /// Loads a sidecar lyric file for the current track when it has no lyrics
+    /// embedded.
+    pub fn lyric_load_sidecar(&mut self) {
+        let Some(mut track) = self.player.playlist.current_track_as_mut() else {
+            return;
+        };
+        if !matches!(track.media_type, Some(MediaType::Music))
+            || !track.lyric_frames_is_empty()
+            || track.parsed_lyric().is_some()
+        {
+            return;
+        }
+        let Some(file) = track.file() else {
+            return;
+        };
+        let lyric_files = lyric_files(Path::new(file), &self.config.lyric_dir);
+        let Some(path) = lyric_files.first() else {
+            return;
+        };
+        if let Err(e) = track.load_lyric_file(path) {
+            self.mount_error_popup(format!("load lyric file error: {e}"));
+            return;
+        }
+        self.player.playlist.set_current_track(Some(&track));
+        self.lyric_line = String::new();
+        // same track, but new lines to show
+        self.lyric_lines = None;
+        self.lyric_update_title();
+    }
+
     pub fn lyric_update_title(&mut self) {
         let mut lyric_title = " No track is playing ".to_string();
         if let Some(song) = self.player.playlist.current_track() {
             match song.media_type {
                 Some(MediaType::Music) => {
-                    let artist = song.artist().unwrap_or("Unknown Artist");
-                    let title = song.title().unwrap_or("Unknown Title");
+                    // lyric files may name the song when the tags don't
+                    let lyric = song.parsed_lyric();
+                    let artist = song
+                        .artist()
+                        .or_else(|| lyric.and_then(|l| l.tag("ar")))
+                        .unwrap_or("Unknown Artist");
+                    let title = song
+                        .title()
+                        .or_else(|| lyric.and_then(|l| l.tag("ti")))
+                        .unwrap_or("Unknown Title");
                     lyric_title = format!(" Lyrics of {artist:^.20} - {title:^.20} ");
                 }
                 Some(MediaType::Podcast) => {
@@ -334,6 +858,9 @@ impl Model {
                 None => {}
             }
         }
+        if let Some(chapter) = self.chapter_current() {
+            lyric_title = format!("{lyric_title}| Chapter: {chapter:^.30} ");
+        }
         self.lyric_title_set(&lyric_title);
     }
 
@@ -22,6 +22,8 @@
  * SOFTWARE.
  */
 // -- modules
+mod album_art;
+mod command_palette;
 mod config_editor;
 mod database;
 mod general_search;
@@ -39,22 +41,25 @@ mod progress;
     clippy::module_name_repetitions
 )]
 mod tag_editor;
+mod visualizer;
 mod xywh;
 mod youtube_search;
 
 // -- export
+pub use album_art::{sixel_supported, AlbumArt};
+pub use command_palette::{CPInputPopup, CPTablePopup};
 pub use config_editor::*;
 pub use database::{DBListCriteria, DBListSearchResult, DBListSearchTracks};
 pub use general_search::{GSInputPopup, GSTablePopup, Source};
 pub use labels::{DownloadSpinner, LabelGeneric, LabelSpan};
 pub use lyric::Lyric;
-pub use music_library::MusicLibrary;
-pub use playlist::Playlist;
+pub use music_library::{MusicLibrary, LIBRARY_CLICK_ROW, LIBRARY_SCROLL};
+pub use playlist::{Playlist, PlaylistTabs, UpNext};
 pub use podcast::{EpisodeList, FeedsList};
 pub use popups::{
     DeleteConfirmInputPopup, DeleteConfirmRadioPopup, ErrorPopup, FeedDeleteConfirmInputPopup,
-    FeedDeleteConfirmRadioPopup, HelpPopup, MessagePopup, PodcastAddPopup, QuitPopup,
-    SavePlaylistConfirm, SavePlaylistPopup,
+    FeedDeleteConfirmRadioPopup, FeedSettingsPopup, HelpPopup, MessagePopup, PodcastAddPopup,
+    QuitPopup, SavePlaylistConfirm, SavePlaylistPopup, TranscriptSearchPopup,
 };
 pub use progress::Progress;
 pub use youtube_search::{YSInputPopup, YSTablePopup};
@@ -66,7 +71,8 @@ use crate::config::Keys;
 // #[cfg(any(feature = "mpris", feature = "discord"))]
 // use crate::track::Track;
 use crate::ui::{
-    ConfigEditorMsg, GSMsg, Id, IdConfigEditor, IdTagEditor, Model, Msg, PLMsg, XYWHMsg, YSMsg,
+    CPMsg, ConfigEditorMsg, GSMsg, Id, IdConfigEditor, IdTagEditor, Model, Msg, PLMsg, XYWHMsg,
+    YSMsg,
 };
 use tui_realm_stdlib::Phantom;
 use tuirealm::event::NoUserEvent;
@@ -144,6 +150,26 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
             {
                 Some(Msg::PlayerSeekBackward)
             }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_chapter_next.key_event() =>
+            {
+                Some(Msg::PlayerChapterNext)
+            }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_chapter_previous.key_event() =>
+            {
+                Some(Msg::PlayerChapterPrevious)
+            }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_section_next.key_event() =>
+            {
+                Some(Msg::PlayerSectionNext)
+            }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_section_previous.key_event() =>
+            {
+                Some(Msg::PlayerSectionPrevious)
+            }
             Event::Keyboard(keyevent)
                 if keyevent == self.keys.global_player_speed_up.key_event() =>
             {
@@ -187,6 +213,20 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
                 Some(Msg::LayoutPodCast)
             }
 
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_layout_cycle.key_event() => {
+                Some(Msg::LayoutCycle)
+            }
+
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_mini_toggle.key_event() => {
+                Some(Msg::LayoutMiniToggle)
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_command_palette.key_event() =>
+            {
+                Some(Msg::CommandPalette(CPMsg::PopupShow))
+            }
+
             Event::Keyboard(keyevent)
                 if keyevent == self.keys.global_player_toggle_gapless.key_event() =>
             {
@@ -227,6 +267,17 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
             Event::Keyboard(keyevent) if keyevent == self.keys.global_xywh_hide.key_event() => {
                 Some(Msg::Xywh(XYWHMsg::Hide))
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_rating_up.key_event() => {
+                Some(Msg::RatingUp)
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_rating_down.key_event() => {
+                Some(Msg::RatingDown)
+            }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_favourite_toggle.key_event() =>
+            {
+                Some(Msg::FavouriteToggle)
+            }
             _ => None,
         }
     }
@@ -293,6 +344,22 @@ impl Model {
                 SubEventClause::Keyboard(keys.global_player_seek_backward.key_event()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_chapter_next.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_chapter_previous.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_section_next.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_section_previous.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.global_lyric_adjust_forward.key_event()),
                 Self::no_popup_mounted_clause(),
@@ -313,6 +380,18 @@ impl Model {
                 SubEventClause::Keyboard(keys.global_layout_database.key_event()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_layout_cycle.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_mini_toggle.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_command_palette.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.global_player_toggle_gapless.key_event()),
                 Self::no_popup_mounted_clause(),
@@ -329,6 +408,18 @@ impl Model {
                 SubEventClause::Keyboard(keys.global_layout_podcast.key_event()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_rating_up.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_rating_down.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_favourite_toggle.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.global_xywh_move_left.key_event()),
                 SubClause::Always,
@@ -371,7 +462,10 @@ impl Model {
             Box::new(SubClause::IsMounted(Id::FeedDeleteConfirmRadioPopup)),
             Box::new(SubClause::Or(
                 Box::new(SubClause::IsMounted(Id::FeedDeleteConfirmInputPopup)),
-                Box::new(SubClause::IsMounted(Id::PodcastSearchTablePopup)),
+                Box::new(SubClause::Or(
+                    Box::new(SubClause::IsMounted(Id::PodcastSearchTablePopup)),
+                    Box::new(SubClause::IsMounted(Id::CommandPaletteInput)),
+                )),
             )),
         )))
     }
@@ -416,8 +510,18 @@ impl Model {
                                                             Box::new(SubClause::IsMounted(
                                                                 Id::SavePlaylistConfirm,
                                                             )),
-                                                            Box::new(SubClause::IsMounted(
-                                                                Id::PodcastAddPopup,
+                                                            Box::new(SubClause::Or(
+                                                                Box::new(SubClause::IsMounted(
+                                                                    Id::PodcastAddPopup,
+                                                                )),
+                                                                Box::new(SubClause::Or(
+                                                                    Box::new(SubClause::IsMounted(
+                                                                        Id::TranscriptSearchPopup,
+                                                                    )),
+                                                                    Box::new(SubClause::IsMounted(
+                                                                        Id::FeedSettingsPopup,
+                                                                    )),
+                                                                )),
                                                             )),
                                                         )),
                                                     )),
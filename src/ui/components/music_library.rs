@@ -4,14 +4,24 @@ use crate::utils::get_pin_yin;
 use anyhow::{bail, Context, Result};
 use std::fs::{remove_dir_all, remove_file, rename};
 use std::path::{Path, PathBuf};
-use tui_realm_treeview::{Node, Tree, TreeView, TREE_CMD_CLOSE, TREE_CMD_OPEN, TREE_INITIAL_NODE};
+use tui_realm_treeview::{
+    Node, Tree, TreeState, TreeView, TREE_CMD_CLOSE, TREE_CMD_OPEN, TREE_INITIAL_NODE,
+};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
-use tuirealm::props::{Alignment, BorderType, Borders, TableBuilder, TextSpan};
+use tuirealm::props::{
+    Alignment, BorderType, Borders, PropPayload, PropValue, TableBuilder, TextSpan,
+};
+use tuirealm::tui::layout::Rect;
 use tuirealm::tui::style::Color;
-use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent, State, StateValue};
+use tuirealm::{AttrValue, Attribute, Component, Event, Frame, MockComponent, State, StateValue};
+
+/// Selects the node drawn on a row of the tree. Takes `Tup2(row, height)`,
+/// both counted in rows of the area inside the border.
+pub const LIBRARY_CLICK_ROW: &str = "click-row";
+/// Moves the selection one node down when set to `true`, up when `false`.
+pub const LIBRARY_SCROLL: &str = "scroll";
 
-#[derive(MockComponent)]
 pub struct MusicLibrary {
     component: TreeView,
     keys: Keys,
@@ -60,6 +70,12 @@ impl MusicLibrary {
                         .unwrap_or(Color::Yellow),
                 )
                 .highlight_symbol(&config.style_color_symbol.library_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.library),
+                )
+                .modifiers(config.style_color_symbol.styles.library.modifiers())
                 .preserve_state(true)
                 // .highlight_symbol("🦄")
                 .with_tree(tree.clone())
@@ -69,6 +85,55 @@ impl MusicLibrary {
         }
     }
 
+    /// The root is closed until the first event reaches the tree.
+    fn open_root(&mut self) {
+        if self.init {
+            let root = self.component.tree().root();
+            if self.component.tree_state().is_closed(root) {
+                self.perform(Cmd::Custom(TREE_CMD_OPEN));
+                self.init = false;
+            }
+        }
+    }
+
+    /// Ids of the nodes in the order they are drawn, skipping the children
+    /// of closed nodes.
+    fn visible_nodes(&self) -> Vec<String> {
+        fn walk(node: &Node, state: &TreeState, nodes: &mut Vec<String>) {
+            nodes.push(node.id().clone());
+            if state.is_open(node) {
+                for child in node.iter() {
+                    walk(child, state, nodes);
+                }
+            }
+        }
+        let mut nodes = Vec::new();
+        walk(
+            self.component.tree().root(),
+            self.component.tree_state(),
+            &mut nodes,
+        );
+        nodes
+    }
+
+    fn select_row(&mut self, row: usize, height: usize) {
+        let nodes = self.visible_nodes();
+        let selected = self
+            .component
+            .tree_state()
+            .selected()
+            .and_then(|id| nodes.iter().position(|node| node == id))
+            .unwrap_or_default();
+        // the tree scrolls just enough to keep the selected node on screen
+        let skip = (selected + 1).saturating_sub(height);
+        if let Some(node) = nodes.get(skip + row) {
+            self.component.attr(
+                Attribute::Custom(TREE_INITIAL_NODE),
+                AttrValue::String(node.clone()),
+            );
+        }
+    }
+
     fn handle_left_key(&mut self) -> CmdResult {
         if let State::One(StateValue::String(node_id)) = self.state() {
             if let Some(node) = self.component.tree().root().query(&node_id) {
@@ -91,17 +156,53 @@ impl MusicLibrary {
     }
 }
 
+impl MockComponent for MusicLibrary {
+    fn view(&mut self, frame: &mut Frame<'_>, area: Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match (attr, value) {
+            (
+                Attribute::Custom(LIBRARY_CLICK_ROW),
+                AttrValue::Payload(PropPayload::Tup2((
+                    PropValue::Usize(row),
+                    PropValue::Usize(height),
+                ))),
+            ) => {
+                self.open_root();
+                self.select_row(row, height);
+            }
+            (Attribute::Custom(LIBRARY_SCROLL), AttrValue::Flag(down)) => {
+                self.open_root();
+                self.perform(Cmd::Move(if down {
+                    Direction::Down
+                } else {
+                    Direction::Up
+                }));
+            }
+            (attr, value) => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
+
 impl Component<Msg, NoUserEvent> for MusicLibrary {
     #[allow(clippy::too_many_lines)]
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
         // When init, open root
-        if self.init {
-            let root = self.component.tree().root();
-            if self.component.tree_state().is_closed(root) {
-                self.perform(Cmd::Custom(TREE_CMD_OPEN));
-                self.init = false;
-            }
-        }
+        self.open_root();
         let result = match ev {
             Event::Keyboard(keyevent) if keyevent == self.keys.global_left.key_event() => {
                 self.handle_left_key()
@@ -160,6 +261,12 @@ impl Component<Msg, NoUserEvent> for MusicLibrary {
                 }
                 CmdResult::None
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.library_add_queue.key_event() => {
+                let current_node = self.component.tree_state().selected().unwrap();
+                return Some(Msg::Playlist(crate::ui::PLMsg::QueueAdd(
+                    current_node.to_string(),
+                )));
+            }
             Event::Keyboard(KeyEvent {
                 code: Key::PageDown,
                 modifiers: KeyModifiers::NONE,
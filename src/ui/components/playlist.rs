@@ -1,35 +1,54 @@
 use crate::{
-    config::{Keys, Settings},
-    track::Track,
+    config::{Keys, PlaylistColumn, Settings},
+    track::{MediaType, Rating, Track},
     ui::{GSMsg, Id, Model, Msg, PLMsg},
 };
 
 use crate::player::PlayerTrait;
-use crate::sqlite::TrackForDB;
+use crate::sqlite::{PlaylistRecord, TrackForDB};
 use crate::ui::model::TermusicLayout;
 use crate::utils::{filetype_supported, get_parent_folder, is_playlist, playlist_get_vec};
 use anyhow::{anyhow, bail, Result};
 use rand::seq::SliceRandom;
+use std::collections::HashSet;
 use std::path::Path;
 use std::time::Duration;
-use tui_realm_stdlib::Table;
-use tuirealm::props::{Alignment, BorderType, PropPayload, PropValue, TableBuilder, TextSpan};
+use tui_realm_stdlib::{Span, Table};
+use tuirealm::props::{
+    Alignment, BorderType, PropPayload, PropValue, Table as TableRows, TableBuilder, TextModifiers,
+    TextSpan,
+};
+use tuirealm::tui::layout::{Constraint, Direction as LayoutDirection, Layout, Rect};
 use tuirealm::{
     command::{Cmd, CmdResult, Direction, Position},
     event::KeyModifiers,
 };
 use tuirealm::{
     event::{Key, KeyEvent, NoUserEvent},
-    AttrValue, Attribute, Component, Event, MockComponent, State, StateValue,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, State, StateValue,
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::sqlite::SearchCriteria;
 use tuirealm::props::{Borders, Color};
 
-#[derive(MockComponent)]
+/// Alignment of each column of the playlist, as a vector of alignments.
+const PLAYLIST_ALIGNMENTS: &str = "playlist-alignments";
+const COLUMN_SPACING: u16 = 2;
+/// How many tracks radio picks the next one from, at most.
+const RADIO_SAMPLE: usize = 200;
+
+/// The playlist table. Cells are aligned by padding them to the width of
+/// their column, so the rows are kept as given and padded again whenever the
+/// playlist is drawn at another width.
 pub struct Playlist {
     component: Table,
     keys: Keys,
+    rows: TableRows,
+    headers: Vec<String>,
+    alignments: Vec<Alignment>,
+    /// Width the rows were last padded for
+    width: Option<u16>,
 }
 
 impl Playlist {
@@ -65,22 +84,131 @@ impl Playlist {
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.playlist_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.playlist),
+                )
+                .modifiers(config.style_color_symbol.styles.playlist.modifiers())
                 .rewind(false)
                 .step(4)
                 .row_height(1)
-                .headers(&["Duration", "Artist", "Title", "Album"])
-                .column_spacing(2)
-                .widths(&[12, 20, 25, 43])
-                .table(
-                    TableBuilder::default()
-                        .add_col(TextSpan::from("Empty"))
-                        .add_col(TextSpan::from("Empty Queue"))
-                        .add_col(TextSpan::from("Empty"))
-                        .build(),
-                ),
+                .column_spacing(COLUMN_SPACING),
             keys: config.keys.clone(),
+            rows: Vec::new(),
+            headers: Vec::new(),
+            alignments: Vec::new(),
+            width: None,
+        }
+    }
+
+    /// Widths of the columns in cells, the way the table lays them out.
+    fn column_widths(&self, width: u16) -> Vec<u16> {
+        let percentages: Vec<u16> = match self.component.query(Attribute::Width) {
+            Some(AttrValue::Payload(PropPayload::Vec(widths))) => {
+                widths.into_iter().map(PropValue::unwrap_u16).collect()
+            }
+            _ => return Vec::new(),
+        };
+        let highlight = match self.component.query(Attribute::HighlightedStr) {
+            Some(AttrValue::String(symbol)) => symbol.width(),
+            _ => 0,
+        };
+        let mut constraints = vec![Constraint::Length(
+            u16::try_from(highlight).unwrap_or_default(),
+        )];
+        for percentage in percentages {
+            constraints.push(Constraint::Percentage(percentage));
+            constraints.push(Constraint::Length(COLUMN_SPACING));
+        }
+        constraints.pop();
+        // the table leaves what the columns don't take empty
+        constraints.push(Constraint::Min(0));
+        // inside the borders
+        let area = Rect::new(0, 0, width.saturating_sub(2), 1);
+        Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints(constraints)
+            .split(area)
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .map(|chunk| chunk.width)
+            .collect()
+    }
+
+    fn pad(&mut self) {
+        let widths = self.column_widths(self.width.unwrap_or_default());
+        let pad_cells = |cells: &mut dyn Iterator<Item = &mut String>| {
+            for ((cell, width), alignment) in cells.zip(&widths).zip(&self.alignments) {
+                *cell = pad(cell, usize::from(*width), *alignment);
+            }
+        };
+
+        let mut headers = self.headers.clone();
+        pad_cells(&mut headers.iter_mut());
+        let mut rows = self.rows.clone();
+        for row in &mut rows {
+            pad_cells(&mut row.iter_mut().map(|span| &mut span.content));
+        }
+        self.component.attr(
+            Attribute::Text,
+            AttrValue::Payload(PropPayload::Vec(
+                headers.into_iter().map(PropValue::Str).collect(),
+            )),
+        );
+        self.component
+            .attr(Attribute::Content, AttrValue::Table(rows));
+    }
+}
+
+fn pad(text: &str, width: usize, alignment: Alignment) -> String {
+    let space = width.saturating_sub(text.width());
+    match alignment {
+        Alignment::Left => text.to_string(),
+        Alignment::Center => format!("{}{text}", " ".repeat(space / 2)),
+        Alignment::Right => format!("{}{text}", " ".repeat(space)),
+    }
+}
+
+impl MockComponent for Playlist {
+    fn view(&mut self, frame: &mut Frame<'_>, area: Rect) {
+        if self.width != Some(area.width) {
+            self.width = Some(area.width);
+            self.pad();
+        }
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match (attr, value) {
+            (Attribute::Content, AttrValue::Table(rows)) => {
+                self.rows = rows;
+                self.pad();
+            }
+            (Attribute::Text, AttrValue::Payload(PropPayload::Vec(headers))) => {
+                self.headers = headers.into_iter().map(PropValue::unwrap_str).collect();
+                self.pad();
+            }
+            (Attribute::Custom(PLAYLIST_ALIGNMENTS), AttrValue::Payload(PropPayload::Vec(v))) => {
+                self.alignments = v.into_iter().map(PropValue::unwrap_alignment).collect();
+                self.pad();
+            }
+            (attr, value) => self.component.attr(attr, value),
         }
     }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
 }
 
 impl Component<Msg, NoUserEvent> for Playlist {
@@ -148,6 +276,18 @@ impl Component<Msg, NoUserEvent> for Playlist {
             Event::Keyboard(key) if key == self.keys.playlist_mode_cycle.key_event() => {
                 return Some(Msg::Playlist(PLMsg::LoopModeCycle))
             }
+            Event::Keyboard(key) if key == self.keys.playlist_shuffle_mode.key_event() => {
+                return Some(Msg::Playlist(PLMsg::ShuffleModeCycle))
+            }
+            Event::Keyboard(key) if key == self.keys.playlist_radio.key_event() => {
+                return Some(Msg::Playlist(PLMsg::RadioToggle))
+            }
+            Event::Keyboard(key) if key == self.keys.playlist_tab_next.key_event() => {
+                return Some(Msg::Playlist(PLMsg::ListNext))
+            }
+            Event::Keyboard(key) if key == self.keys.playlist_tab_previous.key_event() => {
+                return Some(Msg::Playlist(PLMsg::ListPrevious))
+            }
             Event::Keyboard(key) if key == self.keys.playlist_play_selected.key_event() => {
                 if let State::One(StateValue::Usize(index)) = self.state() {
                     return Some(Msg::Playlist(PLMsg::PlaySelected(index)));
@@ -184,6 +324,145 @@ impl Component<Msg, NoUserEvent> for Playlist {
             Event::Keyboard(key) if key == self.keys.playlist_cmus_tqueue.key_event() => {
                 return Some(Msg::Playlist(PLMsg::CmusTQueue));
             }
+            Event::Keyboard(key) if key == self.keys.playlist_sort.key_event() => {
+                return Some(Msg::Playlist(PLMsg::Sort));
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+/// The names of the named playlists, shown above the playlist while there
+/// is more than one.
+#[derive(MockComponent)]
+pub struct PlaylistTabs {
+    component: Span,
+}
+
+impl PlaylistTabs {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Span::default()
+                .alignment(Alignment::Left)
+                .background(
+                    config
+                        .style_color_symbol
+                        .playlist_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .playlist_foreground()
+                        .unwrap_or(Color::Yellow),
+                ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PlaylistTabs {
+    fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
+        None
+    }
+}
+
+/// Up next: the tracks that play before the playlist goes on, shown above
+/// it while there are any.
+#[derive(MockComponent)]
+pub struct UpNext {
+    component: Table,
+    keys: Keys,
+}
+
+impl UpNext {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .playlist_border()
+                            .unwrap_or(Color::Blue),
+                    ),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .playlist_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .playlist_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .title(" Up next ", Alignment::Left)
+                .scroll(true)
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .playlist_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.playlist_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.playlist),
+                )
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .column_spacing(COLUMN_SPACING)
+                .widths(&[80, 20]),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for UpNext {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down,
+                modifiers: KeyModifiers::NONE,
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Up,
+                modifiers: KeyModifiers::NONE,
+            }) => self.perform(Cmd::Move(Direction::Up)),
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(key) if key == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Tab,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::Playlist(PLMsg::UpNextBlurDown)),
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::Playlist(PLMsg::UpNextBlurUp)),
+            Event::Keyboard(key) if key == self.keys.playlist_delete.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Playlist(PLMsg::QueueDelete(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == self.keys.playlist_delete_all.key_event() => {
+                return Some(Msg::Playlist(PLMsg::QueueClear))
+            }
             _ => CmdResult::None,
         };
         Some(Msg::None)
@@ -200,78 +479,71 @@ impl Model {
                 Vec::new()
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(Id::UpNext, Box::new(UpNext::new(&self.config)), Vec::new())
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::PlaylistTabs,
+                Box::new(PlaylistTabs::new(&self.config)),
+                Vec::new()
+            )
+            .is_ok());
         self.playlist_switch_layout();
         self.playlist_sync();
+        self.playlist_tabs_sync();
     }
 
-    pub fn playlist_switch_layout(&mut self) {
+    /// The columns of the playlist in the current view.
+    fn playlist_columns(&self) -> &[PlaylistColumn] {
         if self.layout == TermusicLayout::Podcast {
-            let headers = &["Duration", "Episodes"];
-            self.app
-                .attr(
-                    &Id::Playlist,
-                    Attribute::Text,
-                    AttrValue::Payload(PropPayload::Vec(
-                        headers
-                            .iter()
-                            .map(|x| PropValue::Str((*x).to_string()))
-                            // .map(|x| PropValue::Str(x.as_ref().to_string()))
-                            .collect(),
-                    )),
-                )
-                .ok();
+            &self.config.playlist_columns.podcast
+        } else {
+            &self.config.playlist_columns.music
+        }
+    }
 
-            let widths = &[12, 88];
+    pub fn playlist_switch_layout(&mut self) {
+        let columns = self.playlist_columns();
+        let headers = columns
+            .iter()
+            .map(|column| PropValue::Str(column.header.clone()))
+            .collect();
+        let widths = columns
+            .iter()
+            .map(|column| PropValue::U16(column.width))
+            .collect();
+        let alignments = columns
+            .iter()
+            .map(|column| PropValue::Alignment(column.align.into()))
+            .collect();
+        for (attr, value) in [
+            (Attribute::Width, widths),
+            (Attribute::Custom(PLAYLIST_ALIGNMENTS), alignments),
+            (Attribute::Text, headers),
+        ] {
             self.app
                 .attr(
                     &Id::Playlist,
-                    Attribute::Width,
-                    AttrValue::Payload(PropPayload::Vec(
-                        widths.iter().map(|x| PropValue::U16(*x)).collect(),
-                    )),
+                    attr,
+                    AttrValue::Payload(PropPayload::Vec(value)),
                 )
                 .ok();
-            self.playlist_sync();
-            return;
         }
-
-        let headers = &["Duration", "Artist", "Title", "Album"];
-        self.app
-            .attr(
-                &Id::Playlist,
-                Attribute::Text,
-                AttrValue::Payload(PropPayload::Vec(
-                    headers
-                        .iter()
-                        .map(|x| PropValue::Str((*x).to_string()))
-                        // .map(|x| PropValue::Str(x.as_ref().to_string()))
-                        .collect(),
-                )),
-            )
-            .ok();
-
-        let widths = &[12, 20, 25, 43];
-        self.app
-            .attr(
-                &Id::Playlist,
-                Attribute::Width,
-                AttrValue::Payload(PropPayload::Vec(
-                    widths.iter().map(|x| PropValue::U16(*x)).collect(),
-                )),
-            )
-            .ok();
         self.playlist_sync();
     }
 
-    fn playlist_add_playlist(&mut self, current_node: &str) -> Result<()> {
-        let vec = playlist_get_vec(current_node)?;
-        let vec_str = vec.iter().map(std::convert::AsRef::as_ref).collect();
-        self.player.playlist.add_playlist(vec_str)?;
-        self.playlist_sync();
-        Ok(())
+    pub fn playlist_add_episode(&mut self, episode_index: usize) -> Result<()> {
+        self.add_episode(episode_index, false)
     }
 
-    pub fn playlist_add_episode(&mut self, episode_index: usize) -> Result<()> {
+    pub fn queue_add_episode(&mut self, episode_index: usize) -> Result<()> {
+        self.add_episode(episode_index, true)
+    }
+
+    fn add_episode(&mut self, episode_index: usize, up_next: bool) -> Result<()> {
         if self.podcasts.is_empty() {
             return Ok(());
         }
@@ -283,39 +555,52 @@ impl Model {
             .episodes
             .get(episode_index)
             .ok_or_else(|| anyhow!("get episode selected failed."))?;
-        self.player.playlist.add_episode(episode_selected);
+        if up_next {
+            self.player
+                .playlist
+                .add_episode_queue(episode_selected, podcast_selected.playback);
+        } else {
+            self.player
+                .playlist
+                .add_episode(episode_selected, podcast_selected.playback);
+        }
         self.playlist_sync();
         Ok(())
     }
 
     pub fn playlist_add(&mut self, current_node: &str) -> Result<()> {
+        let items = Self::playlist_items(current_node)?;
+        self.player
+            .playlist
+            .add_playlist(items.iter().map(AsRef::as_ref).collect())?;
+        self.playlist_sync();
+        Ok(())
+    }
+
+    /// Adds a file, or the files of a directory or playlist file, to up next.
+    pub fn queue_add(&mut self, current_node: &str) -> Result<()> {
+        let items = Self::playlist_items(current_node)?;
+        self.player
+            .playlist
+            .add_queue(items.iter().map(AsRef::as_ref).collect())?;
+        self.playlist_sync();
+        Ok(())
+    }
+
+    /// The files `current_node` stands for: itself, the files of a directory
+    /// or the entries of a playlist file.
+    fn playlist_items(current_node: &str) -> Result<Vec<String>> {
         let p: &Path = Path::new(&current_node);
         if !p.exists() {
-            return Ok(());
+            return Ok(Vec::new());
         }
         if p.is_dir() {
-            let new_items_vec = Self::library_dir_children(p);
-            let new_items_str_vec = new_items_vec
-                .iter()
-                .map(std::convert::AsRef::as_ref)
-                .collect();
-            self.player.playlist.add_playlist(new_items_str_vec)?;
-            self.playlist_sync();
-            return Ok(());
+            return Ok(Self::library_dir_children(p));
         }
-        self.playlist_add_item(current_node)?;
-        self.playlist_sync();
-        Ok(())
-    }
-
-    fn playlist_add_item(&mut self, current_node: &str) -> Result<()> {
         if is_playlist(current_node) {
-            self.playlist_add_playlist(current_node)?;
-            return Ok(());
+            return playlist_get_vec(current_node);
         }
-        let vec = vec![current_node];
-        self.player.playlist.add_playlist(vec)?;
-        Ok(())
+        Ok(vec![current_node.to_string()])
     }
 
     pub fn playlist_add_all_from_db(&mut self, vec: &[TrackForDB]) {
@@ -334,6 +619,25 @@ impl Model {
         self.playlist_add_all_from_db(&vec);
     }
 
+    /// Tops the playlist up to `radio_upcoming` tracks ahead, in radio mode.
+    pub fn playlist_radio_fill(&mut self) {
+        if !self.config.radio {
+            return;
+        }
+        let upcoming = self.player.playlist.upcoming();
+        if upcoming >= self.config.radio_upcoming {
+            return;
+        }
+        // radio follows the music playing, not an episode or a stream
+        let current = match self.player.playlist.current_track() {
+            Some(track) if !matches!(track.media_type, Some(MediaType::Music)) => return,
+            current => current.and_then(Track::file).map(ToString::to_string),
+        };
+        let seed = current.and_then(|file| self.db.get_record_by_path(&file).ok());
+        let vec = self.playlist_get_records_for_radio(seed, self.config.radio_upcoming - upcoming);
+        self.playlist_add_all_from_db(&vec);
+    }
+
     pub fn playlist_add_cmus_tqueue(&mut self) {
         let vec = self.playlist_get_records_for_cmus_tqueue(
             self.config.playlist_select_random_track_quantity,
@@ -341,29 +645,52 @@ impl Model {
         self.playlist_add_all_from_db(&vec);
     }
 
-    fn playlist_sync_podcasts(&mut self) {
+    pub fn playlist_sync(&mut self) {
+        let ratings = self.db.get_ratings();
+        self.player.playlist.set_ratings(&ratings);
+        let style_color_symbol = &self.config.style_color_symbol;
+        let columns = self.playlist_columns();
         let mut table: TableBuilder = TableBuilder::default();
 
+        // the track that is playing, played tracks stay above it
+        let current = self
+            .player
+            .playlist
+            .current_track()
+            .filter(|_| !self.player.playlist.is_playing_from_queue())
+            .map(|_| self.player.playlist.current_track_index());
         for (idx, record) in self.player.playlist.tracks().iter().enumerate() {
             if idx > 0 {
                 table.add_row();
             }
-
-            let duration = record.duration_formatted().to_string();
-            let duration_string = format!("[{duration:^7.7}]");
-
-            let mut title = record.title().unwrap_or("Unknown Title").to_string();
-            // if let Some(_) = record.podcast_localfile {
-            if record.podcast_localfile.is_some() {
-                title = format!("[D] {title}");
+            for column in columns {
+                let mut span = TextSpan::new(column.render(record));
+                if let Some(color) = column
+                    .color
+                    .and_then(|color| color.color(&style_color_symbol.alacritty_theme))
+                {
+                    span = span.fg(color);
+                }
+                span.modifiers = column.modifiers();
+                if current == Some(idx) {
+                    if let Some(color) = style_color_symbol.playlist_highlight() {
+                        span = span.fg(color);
+                    }
+                    span.modifiers |= TextModifiers::BOLD;
+                }
+                table.add_col(span);
             }
-            table
-                .add_col(TextSpan::new(duration_string.as_str()))
-                .add_col(TextSpan::new(title).bold());
         }
         if self.player.playlist.is_empty() {
-            table.add_col(TextSpan::from("0"));
-            table.add_col(TextSpan::from("empty playlist"));
+            // the message goes in the second column, the first is often narrow
+            let message = columns.len().min(2).saturating_sub(1);
+            for idx in 0..columns.len() {
+                table.add_col(TextSpan::from(if idx == message {
+                    "empty playlist"
+                } else {
+                    ""
+                }));
+            }
         }
 
         let table = table.build();
@@ -376,52 +703,80 @@ impl Model {
             .ok();
 
         self.playlist_update_title();
+        self.queue_sync();
     }
 
-    pub fn playlist_sync(&mut self) {
-        if self.layout == TermusicLayout::Podcast {
-            self.playlist_sync_podcasts();
-            return;
-        }
-
+    /// Fills up next, and moves the focus off it once it is empty and hidden.
+    pub fn queue_sync(&mut self) {
+        let queue = self.player.playlist.queue();
         let mut table: TableBuilder = TableBuilder::default();
-
-        for (idx, record) in self.player.playlist.tracks().iter().enumerate() {
+        for (idx, track) in queue.iter().enumerate() {
             if idx > 0 {
                 table.add_row();
             }
-
-            let duration = record.duration_formatted().to_string();
-            let duration_string = format!("[{duration:^7.7}]");
-
-            let noname_string = "No Name".to_string();
-            let name = record.name().unwrap_or(&noname_string);
-            let artist = record.artist().unwrap_or(name);
-            let title = record.title().unwrap_or("Unknown Title");
-
+            let name = match (track.artist(), track.title().or_else(|| track.name())) {
+                (Some(artist), Some(title)) => format!("{artist} - {title}"),
+                (None, Some(name)) | (Some(name), None) => name.to_string(),
+                (None, None) => track.file().unwrap_or_default().to_string(),
+            };
             table
-                .add_col(TextSpan::new(duration_string.as_str()))
-                .add_col(TextSpan::new(artist).fg(tuirealm::tui::style::Color::LightYellow))
-                .add_col(TextSpan::new(title).bold())
-                .add_col(TextSpan::new(record.album().unwrap_or("Unknown Album")));
+                .add_col(TextSpan::new(name))
+                .add_col(TextSpan::new(track.duration_formatted()));
         }
-        if self.player.playlist.is_empty() {
-            table.add_col(TextSpan::from("0"));
-            table.add_col(TextSpan::from("empty playlist"));
-            table.add_col(TextSpan::from(""));
-            table.add_col(TextSpan::from(""));
+        if queue.is_empty() {
+            table
+                .add_col(TextSpan::from(""))
+                .add_col(TextSpan::from(""));
         }
-
-        let table = table.build();
+        let title = format!(" Up next: {} tracks ", queue.len());
+        let empty = queue.is_empty();
         self.app
             .attr(
-                &Id::Playlist,
-                tuirealm::Attribute::Content,
-                tuirealm::AttrValue::Table(table),
+                &Id::UpNext,
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::UpNext,
+                Attribute::Title,
+                AttrValue::Title((title, Alignment::Left)),
             )
             .ok();
+        if empty && matches!(self.app.focus(), Some(Id::UpNext)) {
+            self.app.active(&Id::Playlist).ok();
+        }
+    }
 
-        self.playlist_update_title();
+    /// Sorts the playlist by the next column that has a sort.
+    pub fn playlist_sort(&mut self) {
+        let columns = self.playlist_columns();
+        let sortable: Vec<usize> = (0..columns.len())
+            .filter(|idx| columns[*idx].sort.is_some())
+            .collect();
+        let Some(next) = sortable
+            .iter()
+            .find(|idx| matches!(self.playlist_sort_column, Some(last) if **idx > last))
+            .or_else(|| sortable.first())
+            .copied()
+        else {
+            self.show_message_timeout_label_help("No playlist column has a sort", None, None, None);
+            return;
+        };
+        let column = &columns[next];
+        let header = column.header.clone();
+        if let Some(key) = column.sort {
+            self.player.playlist.sort_by(|a, b| key.compare(a, b));
+        }
+        self.playlist_sort_column = Some(next);
+        self.playlist_sync();
+        self.show_message_timeout_label_help(
+            format!("Playlist sorted by {header}"),
+            None,
+            None,
+            None,
+        );
     }
 
     pub fn playlist_delete_item(&mut self, index: usize) {
@@ -466,12 +821,15 @@ impl Model {
         } else {
             "last"
         };
+        let radio = if self.config.radio { " | Radio" } else { "" };
         let title = format!(
-            "\u{2500} Playlist \u{2500}\u{2500}\u{2524} Total {} tracks | {} | Mode: {} | Add to: {} \u{251c}\u{2500}",
+            "\u{2500} Playlist \u{2500}\u{2500}\u{2524} Total {} tracks | {} | Mode: {} {} | Add to: {}{} \u{251c}\u{2500}",
             self.player.playlist.len(),
             Track::duration_formatted_short(&duration),
             self.config.loop_mode.display(self.config.playlist_display_symbol),
-            add_queue
+            self.config.shuffle.display(self.config.playlist_display_symbol),
+            add_queue,
+            radio
         );
         self.app
             .attr(
@@ -483,12 +841,10 @@ impl Model {
     }
     pub fn playlist_play_selected(&mut self, index: usize) {
         self.player_save_last_position();
-        if let Some(song) = self.player.playlist.remove(index) {
-            self.player.playlist.push_front(&song);
-            self.playlist_sync();
+        if index < self.player.playlist.len() {
+            self.player.playlist.set_next_index(index);
             self.player.stop();
-            // self.status = Some(Status::Stopped);
-            // self.player_next();
+            self.playlist_sync();
         }
     }
 
@@ -567,6 +923,79 @@ impl Model {
         result
     }
 
+    /// Picks `quantity` tracks, each like the one before it starting from
+    /// `seed`: by the same artist, of the same genre or from the same
+    /// directory, less played and better rated ones more likely. Tracks in the playlist or
+    /// played in the last `radio_exclude_hours` are left out while there
+    /// are others.
+    pub fn playlist_get_records_for_radio(
+        &mut self,
+        seed: Option<TrackForDB>,
+        quantity: usize,
+    ) -> Vec<TrackForDB> {
+        let recent = Duration::from_secs(self.config.radio_exclude_hours * 3600);
+        let mut taken: HashSet<String> = self.player.playlist.files().into_iter().collect();
+        let mut rng = rand::thread_rng();
+        let mut seed = seed;
+        let mut result = Vec::new();
+        for _ in 0..quantity {
+            let mut pick = None;
+            for within in [Some(recent), None] {
+                let mut criteria = [
+                    SearchCriteria::Artist,
+                    SearchCriteria::Genre,
+                    SearchCriteria::Directory,
+                ];
+                criteria.shuffle(&mut rng);
+                // a random sample of the library when the seed has no neighbours
+                let mut searches = seed
+                    .iter()
+                    .flat_map(|seed| {
+                        criteria.iter().filter_map(move |criteria| {
+                            Some((criteria, radio_field(seed, criteria)?))
+                        })
+                    })
+                    .map(Some)
+                    .chain([None]);
+                let candidates = searches.find_map(|search| {
+                    let records = self
+                        .db
+                        .get_radio_records(search, within, RADIO_SAMPLE)
+                        .ok()?;
+                    let allowed: Vec<(TrackForDB, u32)> = records
+                        .into_iter()
+                        .filter(|(record, _)| {
+                            filetype_supported(&record.file) && !taken.contains(&record.file)
+                        })
+                        .collect();
+                    (!allowed.is_empty()).then_some(allowed)
+                });
+                pick = candidates.and_then(|candidates| {
+                    candidates
+                        .choose_weighted(&mut rng, |(record, plays)| {
+                            // every star counts as much as an unrated track, a favourite as five
+                            let liked = 1
+                                + u32::from(record.rating.stars)
+                                + if record.rating.favourite { 5 } else { 0 };
+                            f64::from(liked) / f64::from(plays.saturating_add(1))
+                        })
+                        .ok()
+                        .map(|(record, _)| record.clone())
+                });
+                if pick.is_some() {
+                    break;
+                }
+            }
+            let Some(pick) = pick else {
+                break;
+            };
+            taken.insert(pick.file.clone());
+            seed = Some(pick.clone());
+            result.push(pick);
+        }
+        result
+    }
+
     pub fn playlist_get_records_for_cmus_lqueue(&mut self, quantity: u32) -> Vec<TrackForDB> {
         let mut result = vec![];
         if let Ok(vec) = self.db.get_all_records() {
@@ -618,4 +1047,307 @@ impl Model {
 
         Ok(())
     }
+
+    /// The active playlist as the library database keeps it.
+    fn playlist_record(&self) -> PlaylistRecord {
+        let playlist = &self.player.playlist;
+        let position = match playlist.current_track() {
+            Some(track) if !track.is_stream() && !playlist.is_playing_from_queue() => {
+                u64::try_from(self.time_pos).unwrap_or_default()
+            }
+            _ => 0,
+        };
+        let (files, current) = playlist.saved_files();
+        PlaylistRecord {
+            files,
+            current,
+            position: Duration::from_secs(position),
+        }
+    }
+
+    /// Saves the active playlist to the library database.
+    pub fn playlist_store(&mut self) -> Result<()> {
+        let record = self.playlist_record();
+        self.db
+            .save_playlist(self.player.playlist.name(), &record)?;
+        Ok(())
+    }
+
+    /// Shows the named playlists above the playlist, the active one
+    /// highlighted.
+    pub fn playlist_tabs_sync(&mut self) {
+        let active = self.player.playlist.name().to_string();
+        let mut names = self.db.get_playlist_names().unwrap_or_default();
+        if !names.contains(&active) {
+            if let Err(e) = self.playlist_store() {
+                self.mount_error_popup(format!("Save playlist error: {e}"));
+            }
+            names.push(active.clone());
+        }
+        let highlight = self
+            .config
+            .style_color_symbol
+            .playlist_highlight()
+            .unwrap_or(Color::LightBlue);
+        let spans = names
+            .iter()
+            .map(|name| {
+                let span = TextSpan::new(format!(" {name} "));
+                let span = if *name == active {
+                    span.fg(highlight).bold().reversed()
+                } else {
+                    span
+                };
+                PropValue::TextSpan(span)
+            })
+            .collect();
+        self.app
+            .attr(
+                &Id::PlaylistTabs,
+                Attribute::Text,
+                AttrValue::Payload(PropPayload::Vec(spans)),
+            )
+            .ok();
+        self.playlist_names = names;
+    }
+
+    /// Makes the named playlist `name` the active one. The one it replaces
+    /// is saved, and `name` goes on where it was left.
+    pub fn playlist_switch(&mut self, name: &str) -> Result<()> {
+        if name == self.player.playlist.name() {
+            return Ok(());
+        }
+        let record = self
+            .db
+            .get_playlist(name)
+            .map_err(|_| anyhow!("no playlist named {name}"))?;
+        self.player_save_last_position();
+        self.playlist_store()?;
+        self.player
+            .playlist
+            .replace(name, &record.files, record.current)?;
+        self.playlist_resume = (!record.position.is_zero()).then_some(record.position);
+        self.player.stop();
+        self.playlist_sync();
+        self.playlist_tabs_sync();
+        Ok(())
+    }
+
+    /// Switches to the playlist `offset` tabs away, round the ends.
+    pub fn playlist_switch_by(&mut self, offset: isize) -> Result<()> {
+        let names = &self.playlist_names;
+        if names.len() < 2 {
+            return Ok(());
+        }
+        let active = names
+            .iter()
+            .position(|name| name == self.player.playlist.name())
+            .unwrap_or_default();
+        let len = isize::try_from(names.len())?;
+        let index = (isize::try_from(active)? + offset).rem_euclid(len);
+        let name = names[usize::try_from(index)?].clone();
+        self.playlist_switch(&name)
+    }
+
+    fn playlist_name_free(&self, name: &str) -> Result<()> {
+        if name.is_empty() {
+            bail!("a playlist needs a name");
+        }
+        if self.playlist_names.iter().any(|n| n == name) {
+            bail!("there is a playlist named {name} already");
+        }
+        Ok(())
+    }
+
+    /// Creates an empty playlist and switches to it.
+    pub fn playlist_new(&mut self, name: &str) -> Result<()> {
+        self.playlist_name_free(name)?;
+        self.db.save_playlist(name, &PlaylistRecord::default())?;
+        self.playlist_switch(name)
+    }
+
+    pub fn playlist_rename(&mut self, name: &str) -> Result<()> {
+        self.playlist_name_free(name)?;
+        self.db.rename_playlist(self.player.playlist.name(), name)?;
+        self.player.playlist.set_name(name);
+        self.playlist_tabs_sync();
+        Ok(())
+    }
+
+    /// Saves a copy of the active playlist as `name`.
+    pub fn playlist_duplicate(&mut self, name: &str) -> Result<()> {
+        self.playlist_name_free(name)?;
+        let record = self.playlist_record();
+        self.db.save_playlist(name, &record)?;
+        self.playlist_tabs_sync();
+        Ok(())
+    }
+
+    /// Deletes the active playlist and switches to the one next to it.
+    pub fn playlist_delete(&mut self) -> Result<()> {
+        if self.playlist_names.len() < 2 {
+            bail!("the last playlist can't be deleted");
+        }
+        let name = self.player.playlist.name().to_string();
+        self.playlist_switch_by(1)?;
+        self.db.delete_playlist(&name)?;
+        self.playlist_tabs_sync();
+        Ok(())
+    }
+
+    /// Adds what is selected in the focused view to the playlist `name`.
+    pub fn playlist_add_to(&mut self, name: &str) -> Result<()> {
+        if name.is_empty() {
+            bail!("which playlist to add to?");
+        }
+        let files = self.selected_files()?;
+        if name == self.player.playlist.name() {
+            self.player.playlist.add_files(&files)?;
+            self.playlist_sync();
+            return Ok(());
+        }
+        self.db
+            .add_playlist_files(name, &files)
+            .map_err(|_| anyhow!("no playlist named {name}"))?;
+        Ok(())
+    }
+
+    /// Changes the stars of the selected tracks, `stars` gets the ones they have.
+    pub fn rating_stars(&mut self, stars: impl Fn(u8) -> u8) {
+        self.rating_update(|rating| Rating {
+            stars: stars(rating.stars).min(5),
+            ..rating
+        });
+    }
+
+    /// Makes the selected tracks favourites, or no longer if they all are.
+    pub fn rating_favourite_toggle(&mut self) {
+        let favourite = match self.selected_ratings() {
+            Ok(selected) => !selected.iter().all(|(_, rating)| rating.favourite),
+            Err(e) => {
+                self.mount_error_popup(format!("rating error: {e}"));
+                return;
+            }
+        };
+        self.rating_update(|rating| Rating {
+            favourite,
+            ..rating
+        });
+    }
+
+    fn rating_update(&mut self, rate: impl Fn(Rating) -> Rating) {
+        let result = self.selected_ratings().and_then(|selected| {
+            let mut last = Rating::default();
+            for (file, old) in &selected {
+                last = rate(*old);
+                self.db.set_rating(file, last)?;
+                if self.config.rating_write_tags && last.stars != old.stars {
+                    let mut track = Track::read_from_path(file, true)?;
+                    track.set_rating(last);
+                    track.save_rating()?;
+                }
+                for record in self.db_search_tracks.iter_mut().filter(|r| &r.file == file) {
+                    record.rating = last;
+                }
+            }
+            Ok((selected.len(), last))
+        });
+        match result {
+            Ok((count, rating)) => {
+                let rating = match rating.display() {
+                    unrated if unrated.is_empty() => "unrated".to_string(),
+                    rating => rating,
+                };
+                let tracks = if count == 1 { "track" } else { "tracks" };
+                self.show_message_timeout_label_help(
+                    format!("{count} {tracks} {rating}"),
+                    None,
+                    None,
+                    None,
+                );
+            }
+            Err(e) => self.mount_error_popup(format!("rating error: {e}")),
+        }
+        self.playlist_sync();
+        self.database_sync_tracks();
+    }
+
+    /// The selected tracks on disk, with the ratings they have now.
+    fn selected_ratings(&mut self) -> Result<Vec<(String, Rating)>> {
+        let files: Vec<String> = self
+            .selected_files()?
+            .into_iter()
+            .filter(|file| Path::new(file).exists())
+            .collect();
+        if files.is_empty() {
+            bail!("only tracks on disk can be rated");
+        }
+        let ratings = self.db.get_ratings();
+        Ok(files
+            .into_iter()
+            .map(|file| {
+                let rating = ratings.get(&file).copied().unwrap_or_default();
+                (file, rating)
+            })
+            .collect())
+    }
+
+    /// The files selected in the focused view: a track, an episode, or the
+    /// files of a directory or playlist file.
+    fn selected_files(&self) -> Result<Vec<String>> {
+        let focus = self.app.focus();
+        let state = focus.and_then(|id| self.app.state(id).ok());
+        let files = match (focus, state) {
+            (Some(Id::Library), Some(State::One(StateValue::String(node)))) => {
+                Self::playlist_items(&node)?
+            }
+            (Some(Id::DBListSearchTracks), Some(State::One(StateValue::Usize(index)))) => self
+                .db_search_tracks
+                .get(index)
+                .map(|track| track.file.clone())
+                .into_iter()
+                .collect(),
+            (Some(Id::Episode), Some(State::One(StateValue::Usize(index)))) => self
+                .podcasts
+                .get(self.podcasts_index)
+                .and_then(|podcast| podcast.episodes.get(index))
+                .map(|episode| episode.url.clone())
+                .into_iter()
+                .collect(),
+            (Some(Id::Playlist), Some(State::One(StateValue::Usize(index)))) => self
+                .player
+                .playlist
+                .tracks()
+                .get(index)
+                .and_then(Track::file)
+                .map(ToString::to_string)
+                .into_iter()
+                .collect(),
+            (Some(Id::UpNext), Some(State::One(StateValue::Usize(index)))) => self
+                .player
+                .playlist
+                .queue()
+                .get(index)
+                .and_then(Track::file)
+                .map(ToString::to_string)
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        };
+        if files.is_empty() {
+            bail!("select tracks in the library, database, podcast or playlist view first");
+        }
+        Ok(files)
+    }
+}
+
+/// What radio matches tracks by, None where the library has a placeholder.
+fn radio_field<'a>(record: &'a TrackForDB, criteria: &SearchCriteria) -> Option<&'a str> {
+    let value = match criteria {
+        SearchCriteria::Artist if record.artist != "Unknown Artist" => &record.artist,
+        SearchCriteria::Genre if record.genre != "no type" => &record.genre,
+        SearchCriteria::Directory => &record.directory,
+        _ => return None,
+    };
+    (!value.is_empty()).then_some(value.as_str())
 }
@@ -1,5 +1,9 @@
 use crate::config::{Keys, Settings};
-use crate::podcast::{download_list, EpData, PodcastFeed, PodcastNoId};
+use crate::podcast::transcript::{parse_transcript, Transcript};
+use crate::podcast::{
+    download_list, fetch_chapters, fetch_transcript, EpData, PodcastFeed, PodcastNoId,
+    PodcastPlayback,
+};
 use crate::track::MediaType;
 use crate::ui::{Id, Model, Msg, PCMsg};
 use anyhow::{anyhow, bail, Result};
@@ -9,7 +13,7 @@ use serde_json::Value;
 use std::time::Duration;
 use tui_realm_stdlib::List;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
-use tuirealm::props::{Alignment, BorderType, TableBuilder, TextSpan};
+use tuirealm::props::{Alignment, BorderType, PropPayload, PropValue, TableBuilder, TextSpan};
 use tuirealm::props::{Borders, Color};
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers, NoUserEvent},
@@ -34,20 +38,20 @@ impl FeedsList {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .podcast_border()
                             .unwrap_or(Color::Blue),
                     ),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .podcast_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .podcast_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .title(" Podcast Feeds: ", Alignment::Left)
@@ -55,10 +59,16 @@ impl FeedsList {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .podcast_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.podcast),
+                )
+                .modifiers(config.style_color_symbol.styles.podcast.modifiers())
                 .rewind(false)
                 .step(4)
                 .scroll(true)
@@ -180,6 +190,12 @@ impl Component<Msg, NoUserEvent> for FeedsList {
             Event::Keyboard(keyevent) if keyevent == self.keys.podcast_delete_feed.key_event() => {
                 return Some(Msg::Podcast(PCMsg::FeedDeleteShow));
             }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.podcast_feed_settings.key_event() =>
+            {
+                return Some(Msg::Podcast(PCMsg::FeedSettingsPopupShow));
+            }
             Event::Keyboard(keyevent)
                 if keyevent == self.keys.podcast_delete_all_feeds.key_event() =>
             {
@@ -207,20 +223,20 @@ impl EpisodeList {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .podcast_border()
                             .unwrap_or(Color::Blue),
                     ),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .podcast_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .podcast_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .title(" Episodes: ", Alignment::Left)
@@ -228,10 +244,16 @@ impl EpisodeList {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .podcast_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .inactive(
+                    config
+                        .style_color_symbol
+                        .inactive_style(&config.style_color_symbol.styles.podcast),
+                )
+                .modifiers(config.style_color_symbol.styles.podcast.modifiers())
                 .rewind(false)
                 .step(4)
                 .scroll(true)
@@ -332,6 +354,13 @@ impl Component<Msg, NoUserEvent> for EpisodeList {
                 CmdResult::None
             }
 
+            Event::Keyboard(keyevent) if keyevent == self.keys.podcast_add_queue.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Podcast(PCMsg::EpisodeQueue(index)));
+                }
+                CmdResult::None
+            }
+
             Event::Keyboard(keyevent) if keyevent == self.keys.podcast_mark_played.key_event() => {
                 if let State::One(StateValue::Usize(index)) = self.state() {
                     return Some(Msg::Podcast(PCMsg::EpisodeMarkPlayed(index)));
@@ -369,6 +398,41 @@ impl Component<Msg, NoUserEvent> for EpisodeList {
 }
 
 impl Model {
+    pub fn podcast_reload(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::Podcast,
+                Box::new(FeedsList::new(
+                    &self.config,
+                    Msg::Podcast(PCMsg::PodcastBlurDown),
+                    Msg::Podcast(PCMsg::PodcastBlurUp)
+                )),
+                Vec::new()
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::Episode,
+                Box::new(EpisodeList::new(
+                    &self.config,
+                    Msg::Podcast(PCMsg::EpisodeBlurDown),
+                    Msg::Podcast(PCMsg::EpisodeBlurUp)
+                )),
+                Vec::new()
+            )
+            .is_ok());
+        self.podcast_sync_feeds_and_episodes();
+        self.app
+            .attr(
+                &Id::Podcast,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(self.podcasts_index))),
+            )
+            .ok();
+    }
+
     pub fn podcast_search_itunes(&self, search_str: &str) {
         let encoded: String = utf8_percent_encode(search_str, NON_ALPHANUMERIC).to_string();
         let url = format!(
@@ -512,6 +576,8 @@ impl Model {
             // if let Some(_) = record.path {
             if record.path.is_some() {
                 title = format!("[D] {title}");
+            } else if let Some(progress) = self.download_tracker.progress_str(&record.url) {
+                title = format!("[{progress}] {title}");
             }
             if record.played {
                 table.add_col(TextSpan::new(title).strikethrough());
@@ -725,6 +791,7 @@ impl Model {
         let mut ep_data = Vec::new();
         {
             pod_title = podcast_selected.title.clone();
+            let pod_image_url = podcast_selected.image_url.clone();
 
             // if we are selecting one specific episode, just grab that
             // one; otherwise, loop through them all
@@ -736,12 +803,22 @@ impl Model {
                         .episodes
                         .get_mut(idx)
                         .ok_or_else(|| anyhow!("get episode selected failed"))?;
+                    // asking again for a running download cancels it
+                    if self.download_tracker.contains(&ep.url) {
+                        self.download_cancel
+                            .lock()
+                            .map_err(|_| anyhow!("lock download cancel list failed"))?
+                            .insert(ep.id);
+                        return Ok(());
+                    }
                     let data = EpData {
                         id: ep.id,
                         pod_id: ep.pod_id,
                         title: ep.title.clone(),
+                        pod_title: pod_title.clone(),
                         url: ep.url.clone(),
                         pubdate: ep.pubdate,
+                        image_url: ep.image_url.clone().or(pod_image_url),
                         file_path: None,
                     };
                     if ep.path.is_none() && !self.download_tracker.contains(&ep.url) {
@@ -759,8 +836,13 @@ impl Model {
                                     id: ep.id,
                                     pod_id: ep.pod_id,
                                     title: ep.title.clone(),
+                                    pod_title: pod_title.clone(),
                                     url: ep.url.clone(),
                                     pubdate: ep.pubdate,
+                                    image_url: ep
+                                        .image_url
+                                        .clone()
+                                        .or_else(|| pod_image_url.clone()),
                                     file_path: None,
                                 })
                             } else {
@@ -788,15 +870,17 @@ impl Model {
             );
             match crate::utils::create_podcast_dir(&self.config, dir_name) {
                 Ok(path) => {
-                    // for ep in ep_data.iter() {
-                    //     self.download_tracker.insert(ep.id);
-                    // }
+                    // tracked while queued too, so they aren't queued twice
+                    for ep in &ep_data {
+                        self.download_tracker.increase_one(&ep.url);
+                    }
                     download_list(
                         ep_data,
                         &path,
                         self.config.podcast_max_retries,
                         &self.threadpool,
                         &self.tx_to_main,
+                        &self.download_cancel,
                     );
                 }
                 Err(_) => bail!("Could not create dir: {pod_title}"),
@@ -807,6 +891,14 @@ impl Model {
         Ok(())
     }
 
+    /// Forgets a download that has ended, along with any cancel asked for it.
+    pub fn episode_download_finished(&mut self, ep_data: &EpData) {
+        self.download_tracker.decrease_one(&ep_data.url);
+        if let Ok(mut cancel) = self.download_cancel.lock() {
+            cancel.remove(&ep_data.id);
+        }
+    }
+
     pub fn episode_download_complete(&mut self, ep_data: EpData) -> Result<()> {
         let file_path = ep_data.file_path.unwrap();
         let res = self.db_podcast.insert_file(ep_data.id, &file_path);
@@ -932,6 +1024,36 @@ impl Model {
         Ok(())
     }
 
+    /// Parses and saves the playback settings of the selected feed, and
+    /// applies them to its episodes already in the playlist.
+    pub fn podcast_set_playback(&mut self, input: &str) -> Result<()> {
+        let playback: PodcastPlayback = input.parse()?;
+        let feed_index = self.podcast_get_feed_index()?;
+        let podcast_selected = self
+            .podcasts
+            .get_mut(feed_index)
+            .ok_or_else(|| anyhow!("cannot get podcast selected"))?;
+        self.db_podcast
+            .set_podcast_playback(podcast_selected.id, &playback)?;
+        podcast_selected.playback = playback;
+
+        let episode_urls: Vec<&str> = podcast_selected
+            .episodes
+            .iter()
+            .map(|ep| ep.url.as_str())
+            .collect();
+        self.player
+            .playlist
+            .set_podcast_playback(&episode_urls, playback);
+        Ok(())
+    }
+
+    /// Playback settings of the selected feed, as shown in the settings popup.
+    pub fn podcast_playback_selected(&self) -> Option<PodcastPlayback> {
+        let feed_index = self.podcast_get_feed_index().ok()?;
+        self.podcasts.get(feed_index).map(|pod| pod.playback)
+    }
+
     fn podcast_get_feed_index(&self) -> Result<usize> {
         if let Ok(State::One(StateValue::Usize(feed_index))) = self.app.state(&Id::Podcast) {
             return Ok(feed_index);
@@ -946,6 +1068,43 @@ impl Model {
         Err(anyhow!("cannot get feed index"))
     }
 
+    pub fn podcast_fetch_chapters(&mut self) {
+        if let Some(track) = self.player.playlist.current_track() {
+            if let Some(url) = &track.chapters_url {
+                // chapters embedded in a downloaded file take precedence
+                if track.chapters().is_empty() {
+                    fetch_chapters(url.clone(), self.tx_to_main.clone());
+                }
+            }
+        }
+    }
+
+    pub fn podcast_fetch_transcript(&mut self) {
+        if let Some(track) = self.player.playlist.current_track() {
+            if let Some((url, mime)) = &track.transcript {
+                if track.parsed_lyric().is_none() && track.lyric_frames_is_empty() {
+                    fetch_transcript(url.clone(), mime.clone(), self.tx_to_main.clone());
+                }
+            }
+        }
+    }
+
+    pub fn podcast_transcript_fetched(&mut self, url: &str, mime: &str, body: &str) -> Result<()> {
+        let Some(mut track) = self.player.playlist.current_track_as_mut() else {
+            return Ok(());
+        };
+        if track.transcript.as_ref().map(|(u, _)| u.as_str()) != Some(url) {
+            return Ok(());
+        }
+        match parse_transcript(body, mime)? {
+            Transcript::Timed(lyric) => track.set_parsed_lyric(Some(lyric)),
+            Transcript::Text(text) => track.set_lyric(&text, "Transcript"),
+        }
+        self.player.playlist.set_current_track(Some(&track));
+        self.lyric_line = String::new();
+        Ok(())
+    }
+
     pub fn podcast_mark_current_track_played(&mut self) -> Result<()> {
         if self.podcasts.is_empty() {
             return Ok(());
@@ -22,7 +22,7 @@ use crate::config::{BindingForEvent, Keys, Settings, StyleColorSymbol};
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use crate::ui::{Id, Model, Msg, PCMsg};
+use crate::ui::{Id, LyricMsg, Model, Msg, PCMsg};
 use tui_realm_stdlib::{Input, Paragraph, Radio, Table};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
@@ -44,13 +44,13 @@ impl QuitPopup {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
@@ -58,7 +58,7 @@ impl QuitPopup {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .popup_border()
                                 .unwrap_or(Color::Yellow),
                         )
                         .modifiers(BorderType::Rounded),
@@ -186,26 +186,26 @@ impl HelpPopup {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .popup_border()
                             .unwrap_or(Color::Green),
                     ),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Black),
                 )
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .popup_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
@@ -243,6 +243,18 @@ impl HelpPopup {
                         ]))
                         .add_col(Self::comment("Seek forward/backward 5 seconds"))
                         .add_row()
+                        .add_col(Self::key(&[
+                            keys.global_player_chapter_next,
+                            keys.global_player_chapter_previous,
+                        ]))
+                        .add_col(Self::comment("Next/previous chapter"))
+                        .add_row()
+                        .add_col(Self::key(&[
+                            keys.global_player_section_next,
+                            keys.global_player_section_previous,
+                        ]))
+                        .add_col(Self::comment("Next/previous loud or quiet part"))
+                        .add_row()
                         .add_col(Self::key(&[
                             keys.global_lyric_adjust_forward,
                             keys.global_lyric_adjust_backward,
@@ -267,6 +279,9 @@ impl HelpPopup {
                         .add_col(Self::key(&[keys.global_lyric_cycle]))
                         .add_col(Self::comment("Switch lyrics if more than 1 available"))
                         .add_row()
+                        .add_col(Self::key(&[keys.lyric_search]))
+                        .add_col(Self::comment("Lyrics: search transcript and seek"))
+                        .add_row()
                         .add_col(Self::key(&[
                             keys.global_player_next,
                             keys.global_player_previous,
@@ -297,6 +312,15 @@ impl HelpPopup {
                         .add_col(Self::key(&[keys.global_layout_podcast]))
                         .add_col(Self::comment("Switch layout to podcast"))
                         .add_row()
+                        .add_col(Self::key(&[keys.global_layout_cycle]))
+                        .add_col(Self::comment("Cycle layouts of current view"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.global_mini_toggle]))
+                        .add_col(Self::comment("Toggle mini player"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.global_command_palette]))
+                        .add_col(Self::comment("Command palette, e.g. volume 40"))
+                        .add_row()
                         .add_col(Self::key(&[
                             keys.global_xywh_move_left,
                             keys.global_xywh_move_right,
@@ -318,11 +342,22 @@ impl HelpPopup {
                         .add_col(Self::key(&[keys.global_xywh_hide]))
                         .add_col(Self::comment("Hide/Show album cover"))
                         .add_row()
+                        .add_col(Self::key(&[keys.global_rating_up, keys.global_rating_down]))
+                        .add_col(Self::comment("Rate the selection a star up/down"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.global_favourite_toggle]))
+                        .add_col(Self::comment(
+                            "Make the selection a favourite, or no longer",
+                        ))
+                        .add_row()
                         .add_col(TextSpan::new("Library").bold().fg(Color::LightYellow))
                         .add_row()
                         .add_col(Self::key(&[keys.global_right, keys.library_load_dir]))
                         .add_col(Self::comment("Add one/all tracks to playlist"))
                         .add_row()
+                        .add_col(Self::key(&[keys.library_add_queue]))
+                        .add_col(Self::comment("Add track or folder to up next"))
+                        .add_row()
                         .add_col(Self::key(&[keys.library_delete]))
                         .add_col(Self::comment("Delete track or folder"))
                         .add_row()
@@ -367,6 +402,24 @@ impl HelpPopup {
                         .add_col(Self::key(&[keys.playlist_mode_cycle]))
                         .add_col(Self::comment("Loop mode cycle"))
                         .add_row()
+                        .add_col(Self::key(&[keys.playlist_shuffle_mode]))
+                        .add_col(Self::comment(
+                            "Play order: in order, shuffle, album shuffle",
+                        ))
+                        .add_row()
+                        .add_col(Self::key(&[
+                            keys.playlist_tab_next,
+                            keys.playlist_tab_previous,
+                        ]))
+                        .add_col(Self::comment(
+                            "Next/previous named playlist, see playlist-new in the palette",
+                        ))
+                        .add_row()
+                        .add_col(Self::key(&[keys.playlist_radio]))
+                        .add_col(Self::comment(
+                            "Radio: keep adding tracks like the last ones",
+                        ))
+                        .add_row()
                         .add_col(Self::key(&[keys.playlist_add_front]))
                         .add_col(Self::comment(
                             "Add a track to the front of playlist or back",
@@ -384,11 +437,22 @@ impl HelpPopup {
                         ]))
                         .add_col(Self::comment("Select random tracks/albums to playlist"))
                         .add_row()
+                        .add_col(Self::key(&[keys.playlist_sort]))
+                        .add_col(Self::comment("Sort playlist by the next column"))
+                        .add_row()
+                        .add_col(TextSpan::new("<Shift+Tab>").bold().fg(Color::Cyan))
+                        .add_col(TextSpan::from(
+                            "Up next: focus, delete one/all with the keys above",
+                        ))
+                        .add_row()
                         .add_col(TextSpan::new("Database").bold().fg(Color::LightYellow))
                         .add_row()
                         .add_col(Self::key(&[keys.global_right, keys.database_add_all]))
                         .add_col(Self::comment("Add one/all track(s) to playlist"))
                         .add_row()
+                        .add_col(Self::key(&[keys.database_add_queue]))
+                        .add_col(Self::comment("Add track to up next"))
+                        .add_row()
                         .add_col(Self::key(&[keys.library_search]))
                         .add_col(Self::comment("Search in database"))
                         .add_row()
@@ -409,6 +473,9 @@ impl HelpPopup {
                         ]))
                         .add_col(Self::comment("Feeds : refresh one/all feeds"))
                         .add_row()
+                        .add_col(Self::key(&[keys.podcast_feed_settings]))
+                        .add_col(Self::comment("Feeds : speed and intro/outro skip"))
+                        .add_row()
                         .add_col(Self::key(&[
                             keys.podcast_mark_played,
                             keys.podcast_mark_all_played,
@@ -416,7 +483,10 @@ impl HelpPopup {
                         .add_col(Self::comment("Episode: Mark one/all episodes played"))
                         .add_row()
                         .add_col(Self::key(&[keys.podcast_episode_download]))
-                        .add_col(Self::comment("Episode: Download episode"))
+                        .add_col(Self::comment("Episode: Download or cancel download"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.podcast_add_queue]))
+                        .add_col(Self::comment("Episode: Add to up next"))
                         .add_row()
                         .add_col(Self::key(&[keys.podcast_episode_delete_file]))
                         .add_col(Self::comment("Episode: delete episode local file"))
@@ -476,14 +546,14 @@ impl DeleteConfirmRadioPopup {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::LightRed),
                 )
                 // .background(Color::Black)
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
@@ -491,7 +561,7 @@ impl DeleteConfirmRadioPopup {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .popup_border()
                                 .unwrap_or(Color::LightRed),
                         )
                         .modifiers(BorderType::Rounded),
@@ -566,17 +636,17 @@ impl DeleteConfirmInputPopup {
             component: Input::default()
                 .foreground(
                     style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .background(
                     style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
                     Borders::default()
-                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
+                        .color(style_color_symbol.popup_border().unwrap_or(Color::Green))
                         .modifiers(BorderType::Rounded),
                 )
                 // .invalid_style(Style::default().fg(Color::Red))
@@ -682,17 +752,17 @@ impl SavePlaylistPopup {
             component: Input::default()
                 .foreground(
                     style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .background(
                     style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
                     Borders::default()
-                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
+                        .color(style_color_symbol.popup_border().unwrap_or(Color::Green))
                         .modifiers(BorderType::Rounded),
                 )
                 // .invalid_style(Style::default().fg(Color::Red))
@@ -770,13 +840,13 @@ impl SavePlaylistConfirm {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
@@ -784,7 +854,7 @@ impl SavePlaylistConfirm {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .popup_border()
                                 .unwrap_or(Color::Yellow),
                         )
                         .modifiers(BorderType::Rounded),
@@ -864,17 +934,17 @@ impl PodcastAddPopup {
             component: Input::default()
                 .foreground(
                     style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .background(
                     style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
                     Borders::default()
-                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
+                        .color(style_color_symbol.popup_border().unwrap_or(Color::Green))
                         .modifiers(BorderType::Rounded),
                 )
                 // .invalid_style(Style::default().fg(Color::Red))
@@ -935,6 +1005,156 @@ impl Component<Msg, NoUserEvent> for PodcastAddPopup {
     }
 }
 
+#[derive(MockComponent)]
+pub struct FeedSettingsPopup {
+    component: Input,
+}
+
+impl FeedSettingsPopup {
+    pub fn new(style_color_symbol: &StyleColorSymbol, playback: &str) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    style_color_symbol
+                        .popup_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    style_color_symbol
+                        .popup_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(style_color_symbol.popup_border().unwrap_or(Color::Green))
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(playback)
+                .title(
+                    " Feed settings, e.g. speed=1.5 intro=30 outro=60 : (Enter to confirm) ",
+                    Alignment::Left,
+                ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for FeedSettingsPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Podcast(PCMsg::FeedSettingsPopupCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input_string)) => {
+                    return Some(Msg::Podcast(PCMsg::FeedSettingsPopupCloseOk(input_string)));
+                }
+                _ => return Some(Msg::None),
+            },
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct TranscriptSearchPopup {
+    component: Input,
+}
+
+impl TranscriptSearchPopup {
+    pub fn new(style_color_symbol: &StyleColorSymbol) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(style_color_symbol.lyric_foreground().unwrap_or(Color::Cyan))
+                .background(
+                    style_color_symbol
+                        .lyric_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(style_color_symbol.lyric_border().unwrap_or(Color::Green))
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .title(" Search in transcript : (Enter to seek) ", Alignment::Left),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TranscriptSearchPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::LyricMessage(
+                    LyricMsg::TranscriptSearchPopupCloseCancel,
+                ));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input_string)) if !input_string.is_empty() => {
+                    return Some(Msg::LyricMessage(LyricMsg::TranscriptSearchPopupCloseOk(
+                        input_string,
+                    )));
+                }
+                _ => return Some(Msg::None),
+            },
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
 #[derive(MockComponent)]
 pub struct FeedDeleteConfirmRadioPopup {
     component: Radio,
@@ -948,14 +1168,14 @@ impl FeedDeleteConfirmRadioPopup {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::LightRed),
                 )
                 // .background(Color::Black)
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
@@ -963,7 +1183,7 @@ impl FeedDeleteConfirmRadioPopup {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .popup_border()
                                 .unwrap_or(Color::LightRed),
                         )
                         .modifiers(BorderType::Rounded),
@@ -1038,17 +1258,17 @@ impl FeedDeleteConfirmInputPopup {
             component: Input::default()
                 .foreground(
                     style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .background(
                     style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
                     Borders::default()
-                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
+                        .color(style_color_symbol.popup_border().unwrap_or(Color::Green))
                         .modifiers(BorderType::Rounded),
                 )
                 // .invalid_style(Style::default().fg(Color::Red))
@@ -1126,13 +1346,13 @@ impl PodcastSearchTablePopup {
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Magenta),
                 )
                 .borders(
@@ -1140,7 +1360,7 @@ impl PodcastSearchTablePopup {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .popup_border()
                                 .unwrap_or(Color::Magenta),
                         )
                         .modifiers(BorderType::Rounded),
@@ -1151,7 +1371,7 @@ impl PodcastSearchTablePopup {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .popup_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
@@ -1,16 +1,27 @@
 use crate::config::Settings;
+use crate::player::PlayerTrait;
 use crate::track::{MediaType, Track};
 use crate::ui::{Id, Model, Msg};
 
 use std::time::Duration;
 use tui_realm_stdlib::ProgressBar;
+use tuirealm::command::{Cmd, CmdResult};
 use tuirealm::event::NoUserEvent;
 use tuirealm::props::{Alignment, BorderType, Borders, Color, PropPayload, PropValue};
-use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent};
+use tuirealm::tui::buffer::Buffer;
+use tuirealm::tui::layout::Rect;
+use tuirealm::tui::style::{Modifier, Style};
+use tuirealm::tui::symbols::bar::NINE_LEVELS;
+use tuirealm::tui::widgets::{Block, Widget};
+use tuirealm::{AttrValue, Attribute, Component, Event, Frame, MockComponent, State};
+
+/// The envelope of the current track, one `PropValue::U8` per slice. An
+/// empty one brings back the plain bar.
+pub const PROGRESS_WAVEFORM: &str = "waveform";
 
-#[derive(MockComponent)]
 pub struct Progress {
     component: ProgressBar,
+    waveform: Vec<u8>,
 }
 
 impl Progress {
@@ -40,6 +51,7 @@ impl Progress {
                         .progress_foreground()
                         .unwrap_or(Color::Yellow),
                 )
+                .modifiers(config.style_color_symbol.styles.progress.modifiers())
                 .label("Progress")
                 .title(
                     format!(
@@ -50,8 +62,167 @@ impl Progress {
                     Alignment::Center,
                 )
                 .progress(0.0),
+            waveform: Vec::new(),
+        }
+    }
+
+    /// The envelope as bars across `area`, bright up to the play position
+    /// and dimmed after it, with the time label over the middle.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn view_waveform(&self, render: &mut Frame<'_>, area: Rect) {
+        let foreground = self
+            .query(Attribute::Foreground)
+            .map_or(Color::Reset, AttrValue::unwrap_color);
+        let background = self
+            .query(Attribute::Background)
+            .map_or(Color::Reset, AttrValue::unwrap_color);
+        let borders = self
+            .query(Attribute::Borders)
+            .map_or_else(Borders::default, AttrValue::unwrap_borders);
+        let (title, alignment) = self
+            .query(Attribute::Title)
+            .map_or((String::new(), Alignment::Center), AttrValue::unwrap_title);
+        let progress = self.query(Attribute::Value).map_or(0.0, |value| {
+            value.unwrap_payload().unwrap_one().unwrap_f64()
+        });
+        let label = self
+            .query(Attribute::Text)
+            .map(AttrValue::unwrap_string)
+            .unwrap_or_default();
+        let modifiers = self
+            .query(Attribute::TextProps)
+            .map_or(Modifier::empty(), AttrValue::unwrap_text_modifiers);
+
+        let block = Block::default()
+            .borders(borders.sides)
+            .border_style(borders.style())
+            .border_type(borders.modifiers)
+            .title(title)
+            .title_alignment(alignment)
+            .style(Style::default().bg(background));
+        let inner = block.inner(area);
+        render.render_widget(block, area);
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        let played = Style::default()
+            .fg(foreground)
+            .bg(background)
+            .add_modifier(modifiers);
+        render.render_widget(
+            WaveformBar {
+                waveform: &self.waveform,
+                progress,
+                label: &label,
+                played,
+                unplayed: played.add_modifier(Modifier::DIM),
+            },
+            inner,
+        );
+    }
+}
+
+/// Bars of the envelope, one column per slice group.
+struct WaveformBar<'a> {
+    waveform: &'a [u8],
+    progress: f64,
+    label: &'a str,
+    played: Style,
+    unplayed: Style,
+}
+
+impl Widget for WaveformBar<'_> {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = usize::from(area.width);
+        let played_width = (self.progress * width as f64).round() as usize;
+        for column in 0..width {
+            let from = column * self.waveform.len() / width;
+            let to = ((column + 1) * self.waveform.len() / width).max(from + 1);
+            let level = self.waveform[from..to.min(self.waveform.len())]
+                .iter()
+                .max()
+                .copied()
+                .unwrap_or(0);
+            let style = if column < played_width {
+                self.played
+            } else {
+                self.unplayed
+            };
+            let x = area.x + column as u16;
+            let mut eighths = usize::from(level) * usize::from(area.height) * 8 / 255;
+            for y in (area.top()..area.bottom()).rev() {
+                let symbol = match eighths.min(8) {
+                    0 => NINE_LEVELS.empty,
+                    1 => NINE_LEVELS.one_eighth,
+                    2 => NINE_LEVELS.one_quarter,
+                    3 => NINE_LEVELS.three_eighths,
+                    4 => NINE_LEVELS.half,
+                    5 => NINE_LEVELS.five_eighths,
+                    6 => NINE_LEVELS.three_quarters,
+                    7 => NINE_LEVELS.seven_eighths,
+                    _ => NINE_LEVELS.full,
+                };
+                eighths = eighths.saturating_sub(8);
+                buf.get_mut(x, y).set_symbol(symbol).set_style(style);
+            }
+        }
+
+        let label_width = (self.label.chars().count() as u16).min(area.width);
+        buf.set_stringn(
+            area.x + (area.width - label_width) / 2,
+            area.y + area.height / 2,
+            self.label,
+            label_width.into(),
+            self.played
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        );
+    }
+}
+
+impl MockComponent for Progress {
+    fn view(&mut self, render: &mut Frame<'_>, area: Rect) {
+        if self.waveform.is_empty() {
+            self.component.view(render, area);
+        } else {
+            self.view_waveform(render, area);
         }
     }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            Attribute::Custom(PROGRESS_WAVEFORM) => {
+                self.waveform = value
+                    .unwrap_payload()
+                    .unwrap_vec()
+                    .into_iter()
+                    .map(PropValue::unwrap_u8)
+                    .collect();
+            }
+            attr => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
 }
 
 impl Component<Msg, NoUserEvent> for Progress {
@@ -71,31 +242,50 @@ impl Model {
             )
             .is_ok());
         self.progress_update_title();
+        self.progress_set_waveform();
+    }
+
+    pub fn progress_set_waveform(&mut self) {
+        self.app
+            .attr(
+                &Id::Progress,
+                Attribute::Custom(PROGRESS_WAVEFORM),
+                AttrValue::Payload(PropPayload::Vec(
+                    self.waveform.iter().copied().map(PropValue::U8).collect(),
+                )),
+            )
+            .ok();
     }
 
     #[allow(clippy::cast_precision_loss)]
     pub fn progress_update_title(&mut self) {
         let gapless = if self.config.gapless { "True" } else { "False" };
+        let chapter = self
+            .chapter_current()
+            .map(|c| format!("| Chapter: {c:^.20} "))
+            .unwrap_or_default();
         let mut progress_title = String::new();
         if let Some(track) = self.player.playlist.current_track() {
             match track.media_type {
                 Some(MediaType::Music) => {
                     progress_title = format!(
-                        " Status: {} | Volume: {} | Speed: {:^.1} | Gapless: {} ",
+                        " Status: {} | Volume: {} | Speed: {:^.1} | Gapless: {} {}",
                         self.player.playlist.status(),
                         self.config.volume,
-                        self.config.speed as f32 / 10.0,
+                        self.player.speed() as f32 / 10.0,
                         gapless,
+                        chapter,
                     );
                 }
                 Some(MediaType::Podcast) => {
                     progress_title = format!(
-                        " Status: {} {:^.20} | Volume: {} | Speed: {:^.1} | Gapless: {} ",
+                        " Status: {} {:^.20} | Volume: {} | Speed: {:^.1} | Gapless: {} {}",
                         self.player.playlist.status(),
                         track.title().unwrap_or("Unknown title"),
                         self.config.volume,
-                        self.config.speed as f32 / 10.0,
+                        self.player.speed() as f32 / 10.0,
                         gapless,
+                        chapter,
                     );
                 }
                 None => {}
@@ -118,7 +308,12 @@ impl Model {
             return;
         }
 
+        let chapter_before = self.chapter_current();
         self.time_pos = time_pos;
+        if self.chapter_current() != chapter_before {
+            self.progress_update_title();
+            self.lyric_update_title();
+        }
 
         let progress = (time_pos * 100).checked_div(duration).unwrap() as f64;
 
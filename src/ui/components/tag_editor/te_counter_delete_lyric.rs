@@ -208,7 +208,7 @@ impl TECounterDelete {
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .tag_editor_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
@@ -216,7 +216,7 @@ impl TECounterDelete {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .tag_editor_border()
                                 .unwrap_or(Color::LightRed),
                         )
                         .modifiers(BorderType::Rounded),
@@ -224,7 +224,7 @@ impl TECounterDelete {
                 .foreground(
                     // config
                     //     .style_color_symbol
-                    //     .library_highlight()
+                    //     .tag_editor_highlight()
                     //     .unwrap_or(Color::Cyan),
                     Color::Red,
                 )
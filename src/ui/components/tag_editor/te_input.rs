@@ -42,13 +42,13 @@ impl TEInputArtist {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .tag_editor_foreground()
                         .unwrap_or(Color::Cyan),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .tag_editor_background()
                         .unwrap_or(Color::Black),
                 )
                 .borders(
@@ -56,7 +56,7 @@ impl TEInputArtist {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .tag_editor_border()
                                 .unwrap_or(Color::LightYellow),
                         )
                         .modifiers(BorderType::Rounded),
@@ -94,13 +94,13 @@ impl TEInputTitle {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .tag_editor_foreground()
                         .unwrap_or(Color::Cyan),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .tag_editor_background()
                         .unwrap_or(Color::Black),
                 )
                 .borders(
@@ -108,7 +108,7 @@ impl TEInputTitle {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .tag_editor_border()
                                 .unwrap_or(Color::LightYellow),
                         )
                         .modifiers(BorderType::Rounded),
@@ -226,13 +226,13 @@ impl TEInputAlbum {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .tag_editor_foreground()
                         .unwrap_or(Color::Cyan),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .tag_editor_background()
                         .unwrap_or(Color::Black),
                 )
                 .borders(
@@ -240,7 +240,7 @@ impl TEInputAlbum {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .tag_editor_border()
                                 .unwrap_or(Color::LightYellow),
                         )
                         .modifiers(BorderType::Rounded),
@@ -278,13 +278,13 @@ impl TEInputGenre {
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .tag_editor_foreground()
                         .unwrap_or(Color::Cyan),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .tag_editor_background()
                         .unwrap_or(Color::Black),
                 )
                 .borders(
@@ -292,7 +292,7 @@ impl TEInputGenre {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .tag_editor_border()
                                 .unwrap_or(Color::LightYellow),
                         )
                         .modifiers(BorderType::Rounded),
@@ -43,14 +43,14 @@ impl TESelectLyric {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .tag_editor_border()
                             .unwrap_or(Color::Blue),
                     ),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .tag_editor_foreground()
                         .unwrap_or(Color::LightRed),
                 )
                 .title(" Select a lyric ", Alignment::Center)
@@ -58,7 +58,7 @@ impl TESelectLyric {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .tag_editor_highlight()
                         .unwrap_or(Color::LightGreen),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
@@ -22,7 +22,7 @@ use crate::config::Settings;
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use crate::songtag::{search, SongTag};
+use crate::songtag::{search, Query, SongTag};
 use crate::ui::{Id, IdTagEditor, Model, Msg, SearchLyricState, TEMsg, TFMsg};
 
 use anyhow::{anyhow, Context, Result};
@@ -47,20 +47,20 @@ impl TETableLyricOptions {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .tag_editor_border()
                             .unwrap_or(Color::Blue),
                     ),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .tag_editor_foreground()
                         .unwrap_or(Color::Yellow),
                 )
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .tag_editor_background()
                         .unwrap_or(Color::Reset),
                 )
                 .title(" Search Results ", Alignment::Left)
@@ -68,7 +68,7 @@ impl TETableLyricOptions {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .tag_editor_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str("\u{1f680}")
@@ -244,7 +244,20 @@ impl Model {
                 }
             }
         }
-        search(&search_str, self.sender_songtag.clone());
+        let query = Query {
+            text: search_str,
+            file: self
+                .tageditor_song
+                .as_ref()
+                .and_then(|song| song.file())
+                .map(ToString::to_string),
+            lyric_dir: self.config.lyric_dir.clone(),
+        };
+        search(
+            &query,
+            &self.config.lyric_providers,
+            self.sender_songtag.clone(),
+        );
     }
     pub fn te_update_lyric_options(&mut self) {
         if self
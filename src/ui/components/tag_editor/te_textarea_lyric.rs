@@ -45,14 +45,14 @@ impl TETextareaLyric {
                     Borders::default().modifiers(BorderType::Rounded).color(
                         config
                             .style_color_symbol
-                            .library_border()
+                            .tag_editor_border()
                             .unwrap_or(Color::LightMagenta),
                     ),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .tag_editor_foreground()
                         .unwrap_or(Color::Green),
                 )
                 .title(" Lyrics ", Alignment::Left)
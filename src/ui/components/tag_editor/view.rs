@@ -474,56 +474,56 @@ impl Model {
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .tag_editor_highlight()
                                 .unwrap_or(Color::Cyan)),
                         TextSpan::new(" Save tag ").fg(self
                             .config
                             .style_color_symbol
-                            .library_foreground()
+                            .tag_editor_foreground()
                             .unwrap_or(Color::White)),
                         TextSpan::new(format!("<{}>", self.config.keys.global_esc))
                             .bold()
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .tag_editor_highlight()
                                 .unwrap_or(Color::Cyan)),
                         TextSpan::new(" Exit ").fg(self
                             .config
                             .style_color_symbol
-                            .library_foreground()
+                            .tag_editor_foreground()
                             .unwrap_or(Color::White)),
                         TextSpan::new("<Tab/ShiftTab>").bold().fg(self
                             .config
                             .style_color_symbol
-                            .library_highlight()
+                            .tag_editor_highlight()
                             .unwrap_or(Color::Cyan)),
                         TextSpan::new(" Change field ").fg(self
                             .config
                             .style_color_symbol
-                            .library_foreground()
+                            .tag_editor_foreground()
                             .unwrap_or(Color::White)),
                         TextSpan::new("<ENTER>").bold().fg(self
                             .config
                             .style_color_symbol
-                            .library_highlight()
+                            .tag_editor_highlight()
                             .unwrap_or(Color::Cyan)),
                         TextSpan::new(" Search/Embed tag ").fg(self
                             .config
                             .style_color_symbol
-                            .library_foreground()
+                            .tag_editor_foreground()
                             .unwrap_or(Color::White)),
                         TextSpan::new(format!("<{}>", self.config.keys.library_search_youtube))
                             .bold()
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .tag_editor_highlight()
                                 .unwrap_or(Color::Cyan)),
                         TextSpan::new(" download ").fg(self
                             .config
                             .style_color_symbol
-                            .library_foreground()
+                            .tag_editor_foreground()
                             .unwrap_or(Color::White)),
                     ]
                 )),
@@ -0,0 +1,358 @@
+//! The spectrum and oscilloscope panes, drawn from the samples the player
+//! copies to its `SampleTap`.
+use crate::config::Settings;
+use crate::player::SampleTap;
+use crate::ui::{Id, Model, Msg};
+
+use std::f32::consts::PI;
+use std::sync::Arc;
+use tuirealm::command::{Cmd, CmdResult};
+use tuirealm::event::NoUserEvent;
+use tuirealm::props::{Alignment, AttrValue, Attribute, BorderType, Borders, Color, Props};
+use tuirealm::tui::buffer::Buffer;
+use tuirealm::tui::layout::Rect;
+use tuirealm::tui::style::Style;
+use tuirealm::tui::symbols::Marker;
+use tuirealm::tui::widgets::canvas::{Canvas, Points};
+use tuirealm::tui::widgets::{Block, Widget};
+use tuirealm::{Component, Event, Frame, MockComponent, State};
+
+/// Samples per FFT, about 46ms at 44.1kHz.
+const FFT_SIZE: usize = 2048;
+/// Quietest level drawn, in dB below a full scale sine.
+const SPECTRUM_FLOOR_DB: f32 = 70.0;
+const SPECTRUM_LOW_HZ: f32 = 40.0;
+const SPECTRUM_HIGH_HZ: f32 = 16000.0;
+/// How much of its height a bar keeps per frame when the level drops.
+const SPECTRUM_DECAY: f32 = 0.85;
+const BAR_SYMBOLS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VisualizerMode {
+    Spectrum,
+    Oscilloscope,
+}
+
+pub struct Visualizer {
+    props: Props,
+    mode: VisualizerMode,
+    samples: Arc<SampleTap>,
+    buffer: Vec<f32>,
+    bars: Vec<f32>,
+}
+
+impl Visualizer {
+    pub fn new(config: &Settings, mode: VisualizerMode, samples: Arc<SampleTap>) -> Self {
+        let style_color_symbol = &config.style_color_symbol;
+        let title = match mode {
+            VisualizerMode::Spectrum => " Spectrum ",
+            VisualizerMode::Oscilloscope => " Oscilloscope ",
+        };
+        let mut props = Props::default();
+        props.set(
+            Attribute::Borders,
+            AttrValue::Borders(
+                Borders::default()
+                    .color(
+                        style_color_symbol
+                            .visualizer_border()
+                            .unwrap_or(Color::Blue),
+                    )
+                    .modifiers(BorderType::Rounded),
+            ),
+        );
+        props.set(
+            Attribute::Foreground,
+            AttrValue::Color(
+                style_color_symbol
+                    .visualizer_foreground()
+                    .unwrap_or(Color::Cyan),
+            ),
+        );
+        props.set(
+            Attribute::Background,
+            AttrValue::Color(
+                style_color_symbol
+                    .visualizer_background()
+                    .unwrap_or(Color::Reset),
+            ),
+        );
+        props.set(
+            Attribute::Title,
+            AttrValue::Title((title.to_string(), Alignment::Left)),
+        );
+        Self {
+            props,
+            mode,
+            samples,
+            buffer: vec![0.0; FFT_SIZE],
+            bars: Vec::new(),
+        }
+    }
+
+    /// Levels between 0 and 1 for `count` bands spread logarithmically over
+    /// the audible range, falling slowly so the bars don't flicker.
+    #[allow(clippy::cast_precision_loss)]
+    fn spectrum(&mut self, count: usize) -> &[f32] {
+        self.samples.latest(&mut self.buffer);
+        let mut re: Vec<f32> = self
+            .buffer
+            .iter()
+            .enumerate()
+            .map(|(i, sample)| sample * hann(i, FFT_SIZE))
+            .collect();
+        let mut im = vec![0.0; FFT_SIZE];
+        fft(&mut re, &mut im);
+
+        let bin_hz = self.samples.sample_rate() as f32 / FFT_SIZE as f32;
+        let high = SPECTRUM_HIGH_HZ.min(bin_hz * (FFT_SIZE / 2) as f32);
+        // a full scale sine peaks at FFT_SIZE / 4 with the Hann window
+        let full_scale = FFT_SIZE as f32 / 4.0;
+        self.bars.resize(count, 0.0);
+        for (band, bar) in self.bars.iter_mut().enumerate() {
+            let from = band_edge(band, count, high) / bin_hz;
+            let to = band_edge(band + 1, count, high) / bin_hz;
+            let (from, to) = bin_range(from, to, FFT_SIZE / 2);
+            let magnitude = (from..to)
+                .map(|bin| re[bin].hypot(im[bin]))
+                .fold(0.0, f32::max);
+            let db = 20.0 * (magnitude / full_scale).max(1e-9).log10();
+            let level = ((db + SPECTRUM_FLOOR_DB) / SPECTRUM_FLOOR_DB).clamp(0.0, 1.0);
+            *bar = level.max(*bar * SPECTRUM_DECAY);
+        }
+        &self.bars
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn waveform(&mut self, count: usize) -> Vec<(f64, f64)> {
+        let count = count.min(FFT_SIZE);
+        self.samples.latest(&mut self.buffer[..count]);
+        self.buffer[..count]
+            .iter()
+            .enumerate()
+            .map(|(i, sample)| (i as f64, f64::from(sample.clamp(-1.0, 1.0))))
+            .collect()
+    }
+}
+
+/// Frequency where band `band` of `count` starts.
+#[allow(clippy::cast_precision_loss)]
+fn band_edge(band: usize, count: usize, high: f32) -> f32 {
+    SPECTRUM_LOW_HZ * (high / SPECTRUM_LOW_HZ).powf(band as f32 / count as f32)
+}
+
+/// The bins from `from` to `to` Hz, at least one so low bands narrower than
+/// a bin are not empty.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn bin_range(from: f32, to: f32, bins: usize) -> (usize, usize) {
+    let from = (from.round() as usize).clamp(1, bins - 1);
+    let to = (to.round() as usize).clamp(from + 1, bins);
+    (from, to)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn hann(i: usize, len: usize) -> f32 {
+    0.5 - 0.5 * (2.0 * PI * i as f32 / (len - 1) as f32).cos()
+}
+
+/// In-place radix-2 FFT. The length must be a power of two.
+#[allow(clippy::cast_precision_loss)]
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n);
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let (w_im, w_re) = (-2.0 * PI / len as f32).sin_cos();
+        for start in (0..n).step_by(len) {
+            let (mut cur_re, mut cur_im) = (1.0, 0.0);
+            for k in 0..len / 2 {
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * cur_re - im[b] * cur_im;
+                let t_im = re[b] * cur_im + im[b] * cur_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+                let next_re = cur_re * w_re - cur_im * w_im;
+                cur_im = cur_re * w_im + cur_im * w_re;
+                cur_re = next_re;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Vertical bars in eighths of a cell.
+struct Bars<'a> {
+    levels: &'a [f32],
+    style: Style,
+}
+
+impl Widget for Bars<'_> {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_lossless
+    )]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for (x, level) in (area.left()..area.right()).zip(self.levels) {
+            let mut eighths = (level * f32::from(area.height) * 8.0).round() as u16;
+            for y in (area.top()..area.bottom()).rev() {
+                let fill = eighths.min(8);
+                eighths -= fill;
+                buf.get_mut(x, y)
+                    .set_symbol(BAR_SYMBOLS[fill as usize])
+                    .set_style(self.style);
+            }
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Visualizer {
+    fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
+        None
+    }
+}
+
+impl MockComponent for Visualizer {
+    #[allow(clippy::cast_precision_loss)]
+    fn view(&mut self, render: &mut Frame<'_>, area: Rect) {
+        let foreground = self
+            .props
+            .get_or(Attribute::Foreground, AttrValue::Color(Color::Reset))
+            .unwrap_color();
+        let background = self
+            .props
+            .get_or(Attribute::Background, AttrValue::Color(Color::Reset))
+            .unwrap_color();
+        let borders = self
+            .props
+            .get_or(Attribute::Borders, AttrValue::Borders(Borders::default()))
+            .unwrap_borders();
+        let (title, alignment) = self
+            .props
+            .get_or(
+                Attribute::Title,
+                AttrValue::Title((String::new(), Alignment::Left)),
+            )
+            .unwrap_title();
+        let block = Block::default()
+            .borders(borders.sides)
+            .border_style(borders.style())
+            .border_type(borders.modifiers)
+            .title(title)
+            .title_alignment(alignment)
+            .style(Style::default().bg(background));
+        let inner = block.inner(area);
+        render.render_widget(block, area);
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        let style = Style::default().fg(foreground).bg(background);
+        match self.mode {
+            VisualizerMode::Spectrum => {
+                let levels = self.spectrum(inner.width.into());
+                render.render_widget(Bars { levels, style }, inner);
+            }
+            VisualizerMode::Oscilloscope => {
+                // two braille dots per cell
+                let coords = self.waveform(usize::from(inner.width) * 2);
+                let width = coords.len() as f64;
+                render.render_widget(
+                    Canvas::default()
+                        .background_color(background)
+                        .marker(Marker::Braille)
+                        .x_bounds([0.0, width])
+                        .y_bounds([-1.0, 1.0])
+                        .paint(|ctx| {
+                            ctx.draw(&Points {
+                                coords: &coords,
+                                color: foreground,
+                            });
+                        }),
+                    inner,
+                );
+            }
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value);
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Model {
+    pub fn visualizer_reload(&mut self) {
+        for (id, mode) in [
+            (Id::Spectrum, VisualizerMode::Spectrum),
+            (Id::Oscilloscope, VisualizerMode::Oscilloscope),
+        ] {
+            assert!(self
+                .app
+                .remount(
+                    id,
+                    Box::new(Visualizer::new(
+                        &self.config,
+                        mode,
+                        self.player.samples.clone()
+                    )),
+                    Vec::new()
+                )
+                .is_ok());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn test_fft_finds_sine() {
+        let n = 64;
+        let mut re: Vec<f32> = (0..n)
+            .map(|i| (2.0 * PI * 5.0 * i as f32 / n as f32).sin())
+            .collect();
+        let mut im = vec![0.0; n];
+        fft(&mut re, &mut im);
+        let magnitudes: Vec<f32> = re.iter().zip(&im).map(|(r, i)| r.hypot(*i)).collect();
+        let peak = magnitudes[..n / 2]
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(bin, _)| bin);
+        assert_eq!(peak, Some(5));
+        assert!((magnitudes[5] - n as f32 / 2.0).abs() < 1e-3);
+        assert!(magnitudes[9] < 1e-3);
+    }
+}
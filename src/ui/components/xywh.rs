@@ -1,4 +1,5 @@
-use crate::track::MediaType;
+use crate::cover::Cover;
+use crate::track::{MediaType, Track};
 /**
  * MIT License
  *
@@ -24,15 +25,17 @@ use crate::track::MediaType;
  */
 use crate::ui::{model::ViuerSupported, DLMsg, Id, IdConfigEditor, IdTagEditor, Model, Msg};
 use anyhow::{anyhow, bail, Result};
-use image::io::Reader as ImageReader;
 use image::DynamicImage;
-use lofty::Picture;
 use serde::{Deserialize, Serialize};
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use tuirealm::tui::layout::Rect;
 
 #[derive(Clone, PartialEq)]
 pub struct ImageWrapper {
     pub data: DynamicImage,
+    /// Where the image is cached
+    pub path: PathBuf,
 }
 impl Eq for ImageWrapper {}
 
@@ -146,6 +149,27 @@ impl Xywh {
             align: self.align.clone(),
         })
     }
+    /// Fits the image in the album art pane of a layout, keeping its aspect
+    /// ratio. Heights are in half rows, like in `calculate_xywh`.
+    fn fit_area(&self, area: Rect, image: &DynamicImage) -> Self {
+        let (pic_width, pic_height) = image::GenericImageView::dimensions(image);
+        let (pic_width, pic_height) = (pic_width.max(1), pic_height.max(1));
+        let mut width = u32::from(area.width);
+        let mut height = width * pic_height / pic_width;
+        let height_max = u32::from(area.height) * 2;
+        if height > height_max {
+            height = height_max;
+            width = height * pic_width / pic_height;
+        }
+        Self {
+            x: u32::from(area.x),
+            y: u32::from(area.y),
+            width,
+            height,
+            ..self.clone()
+        }
+    }
+
     fn calculate_xywh(
         &self,
         term_width: u32,
@@ -222,6 +246,9 @@ impl Model {
         self.update_photo().ok();
     }
     fn should_not_show_photo(&self) -> bool {
+        if self.mini {
+            return true;
+        }
         if self.app.mounted(&Id::HelpPopup) {
             return true;
         }
@@ -241,6 +268,10 @@ impl Model {
             return true;
         }
 
+        if self.app.mounted(&Id::CommandPaletteInput) {
+            return true;
+        }
+
         if self.player.playlist.is_stopped() {
             return true;
         }
@@ -263,85 +294,70 @@ impl Model {
         if self.should_not_show_photo() {
             return Ok(());
         }
-        let track = match self.player.playlist.current_track() {
-            Some(track) => track,
-            None => return Ok(()),
+        let Some(cover) = self
+            .player
+            .playlist
+            .current_track()
+            .and_then(|track| self.cover_of(track))
+        else {
+            return Ok(());
         };
+        if !cover.is_remote() {
+            let image = cover.load()?;
+            self.show_image(&image, &cover.cache_path())?;
+            return Ok(());
+        }
+        let tx = self.tx_to_main.clone();
+        std::thread::spawn(move || {
+            let msg = match cover.load() {
+                Ok(image) => DLMsg::FetchPhotoSuccess(ImageWrapper {
+                    data: image,
+                    path: cover.cache_path(),
+                }),
+                Err(e) => DLMsg::FetchPhotoErr(e.to_string()),
+            };
+            tx.send(Msg::Download(msg)).ok();
+        });
 
-        match track.media_type {
-            Some(MediaType::Music) => {
-                // just show the first photo
-                if let Some(picture) = track.picture() {
-                    if let Ok(image) = image::load_from_memory(picture.data()) {
-                        self.show_image(&image)?;
-                        return Ok(());
-                    }
-                }
+        Ok(())
+    }
 
-                if let Some(album_photo) = track.album_photo() {
-                    let img = ImageReader::open(album_photo)?.decode()?;
-                    self.show_image(&img)?;
-                }
-            }
+    fn cover_of(&self, track: &Track) -> Option<Cover> {
+        let feed_image = match track.media_type {
             Some(MediaType::Podcast) => {
-                let mut url = String::new();
-                if let Some(episode_photo_url) = track.album_photo() {
-                    url = episode_photo_url.to_string();
-                } else if let Some(pod_photo_url) =
-                    self.podcast_get_album_photo_by_url(track.file().unwrap_or(""))
-                {
-                    url = pod_photo_url;
-                }
-
-                if url.is_empty() {
-                    return Ok(());
-                }
-                let tx = self.tx_to_main.clone();
-                std::thread::spawn(move || {
-                    match ureq::get(&url).call() {
-                        Ok(result) => match Picture::from_reader(&mut result.into_reader()) {
-                            Ok(picture) => match image::load_from_memory(picture.data()) {
-                                Ok(image) => {
-                                    let image_wrapper = ImageWrapper { data: image };
-                                    tx.send(Msg::Download(DLMsg::FetchPhotoSuccess(image_wrapper)))
-                                        .ok()
-                                }
-                                Err(e) => tx
-                                    .send(Msg::Download(DLMsg::FetchPhotoErr(format!(
-                                        "Error in load_from_memory: {e}"
-                                    ))))
-                                    .ok(),
-                            },
-                            Err(e) => tx
-                                .send(Msg::Download(DLMsg::FetchPhotoErr(format!(
-                                    "Error in picture from_reader: {e}"
-                                ))))
-                                .ok(),
-                        },
-                        Err(e) => tx
-                            .send(Msg::Download(DLMsg::FetchPhotoErr(format!(
-                                "Error in ureq get: {e}"
-                            ))))
-                            .ok(),
-                    }
-
-                    // if let Ok(result) = ureq::get(&url).call() {
-                    //     let picture = Picture::from_reader(&mut result.into_reader())?;
-                    //     if let Ok(image) = image::load_from_memory(picture.data()) {
-                    //         self.show_image(&image)?;
-                    //     }
-                    // }
-                });
+                self.podcast_get_album_photo_by_url(track.file().unwrap_or(""))
             }
-            None => {}
-        }
+            _ => None,
+        };
+        Cover::of_track(track, feed_image)
+    }
 
-        Ok(())
+    /// Caches the art of the next track in the queue in the background, so
+    /// that it shows at once when the track starts.
+    pub fn cover_prefetch(&self) {
+        let Some(cover) = self
+            .player
+            .playlist
+            .fetch_next_track()
+            .and_then(|track| self.cover_of(track))
+        else {
+            return;
+        };
+        if cover.cache_path().exists() {
+            return;
+        }
+        std::thread::spawn(move || cover.load().ok());
     }
 
+    /// Shows `img`, the thumbnail cached at `path`.
     #[allow(clippy::cast_possible_truncation)]
-    pub fn show_image(&mut self, img: &DynamicImage) -> Result<()> {
-        match self.config.album_photo_xywh.update_size(img) {
+    #[cfg_attr(not(feature = "cover"), allow(unused_variables))]
+    pub fn show_image(&mut self, img: &DynamicImage, path: &Path) -> Result<()> {
+        let xywh = match self.album_art_area {
+            Some(area) => Ok(self.config.album_photo_xywh.fit_area(area, img)),
+            None => self.config.album_photo_xywh.update_size(img),
+        };
+        match xywh {
             Err(e) => self.mount_error_popup(e.to_string()),
             Ok(xywh) => {
                 match self.viuer_supported {
@@ -358,17 +374,39 @@ impl Model {
                         viuer::print(img, &config)
                             .map_err(|e| anyhow!("viuer print error: {}", e))?;
                     }
+                    ViuerSupported::Sixel => {
+                        self.album_art.set_sixel(
+                            img,
+                            xywh.x as u16,
+                            xywh.y as u16,
+                            xywh.width as u16,
+                        );
+                        self.redraw = true;
+                    }
+                    ViuerSupported::HalfBlocks => {
+                        self.album_art.set_half_blocks(
+                            img,
+                            xywh.x as u16,
+                            xywh.y as u16,
+                            xywh.width as u16,
+                            xywh.height as u16,
+                        );
+                        self.redraw = true;
+                    }
                     ViuerSupported::NotSupported => {
                         #[cfg(feature = "cover")]
                         {
-                            let mut cache_file =
-                                dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
-                            cache_file.push("termusic");
+                            let mut cache_file = path.to_path_buf();
                             if !cache_file.exists() {
-                                std::fs::create_dir_all(&cache_file)?;
+                                // the cover cache couldn't be written
+                                cache_file = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+                                cache_file.push("termusic");
+                                if !cache_file.exists() {
+                                    std::fs::create_dir_all(&cache_file)?;
+                                }
+                                cache_file.push("termusic_cover.jpg");
+                                img.save(cache_file.clone())?;
                             }
-                            cache_file.push("termusic_cover.jpg");
-                            img.save(cache_file.clone())?;
                             if !cache_file.exists() {
                                 bail!("cover file is not saved correctly");
                             }
@@ -394,6 +432,13 @@ impl Model {
             //     // iterm images
             //     self.terminal.raw_mut().clear()?;
             // }
+            ViuerSupported::Sixel | ViuerSupported::HalfBlocks => {
+                // sixels stay on screen until the cells under them are drawn again
+                if self.album_art.clear() {
+                    self.terminal.raw_mut().clear()?;
+                }
+                self.redraw = true;
+            }
             ViuerSupported::NotSupported => {
                 #[cfg(feature = "cover")]
                 self.ueberzug_instance.clear_cover_ueberzug()?;
@@ -41,13 +41,13 @@ impl YSInputPopup {
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Magenta),
                 )
                 .borders(
@@ -55,7 +55,7 @@ impl YSInputPopup {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .popup_border()
                                 .unwrap_or(Color::Magenta),
                         )
                         .modifiers(BorderType::Rounded),
@@ -124,13 +124,13 @@ impl YSTablePopup {
                 .background(
                     config
                         .style_color_symbol
-                        .library_background()
+                        .popup_background()
                         .unwrap_or(Color::Reset),
                 )
                 .foreground(
                     config
                         .style_color_symbol
-                        .library_foreground()
+                        .popup_foreground()
                         .unwrap_or(Color::Magenta),
                 )
                 .borders(
@@ -138,7 +138,7 @@ impl YSTablePopup {
                         .color(
                             config
                                 .style_color_symbol
-                                .library_border()
+                                .popup_border()
                                 .unwrap_or(Color::Magenta),
                         )
                         .modifiers(BorderType::Rounded),
@@ -152,7 +152,7 @@ impl YSTablePopup {
                 .highlighted_color(
                     config
                         .style_color_symbol
-                        .library_highlight()
+                        .popup_highlight()
                         .unwrap_or(Color::LightBlue),
                 )
                 .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
@@ -226,6 +226,16 @@ impl Component<Msg, NoUserEvent> for YSTablePopup {
                 }
                 CmdResult::None
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.library_add_queue.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::YoutubeSearch(YSMsg::TablePopupStream(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => return Some(Msg::YoutubeSearch(YSMsg::TablePopupBack)),
             _ => CmdResult::None,
         };
         Some(Msg::None)
@@ -25,8 +25,10 @@ pub mod components;
 pub mod model;
 
 use crate::config::{BindingForEvent, ColorTermusic, Settings};
+use crate::invidious::AudioStream;
 use crate::podcast::{EpData, PodcastFeed, PodcastNoId};
 use crate::songtag::SongTag;
+use crate::track::Chapter;
 use components::ImageWrapper;
 use model::YoutubeOptions;
 use model::{Model, TermusicLayout};
@@ -36,11 +38,13 @@ use tuirealm::{Application, Update};
 // -- internal
 
 const FORCED_REDRAW_INTERVAL: Duration = Duration::from_millis(1000);
+const VISUALIZER_REDRAW_INTERVAL: Duration = Duration::from_millis(40);
 
 // Let's define the messages handled by our app. NOTE: it must derive `PartialEq`
 #[derive(Clone, PartialEq, Eq)]
 pub enum Msg {
     // AppClose,
+    CommandPalette(CPMsg),
     ConfigEditor(ConfigEditorMsg),
     DataBase(DBMsg),
     DeleteConfirmCloseCancel,
@@ -48,16 +52,20 @@ pub enum Msg {
     DeleteConfirmShow,
     Download(DLMsg),
     ErrorPopupClose,
+    FavouriteToggle,
     GeneralSearch(GSMsg),
     HelpPopupShow,
     HelpPopupClose,
     LayoutTreeView,
     LayoutDataBase,
     LayoutPodCast,
+    LayoutCycle,
+    LayoutMiniToggle,
     Library(LIMsg),
     LyricMessage(LyricMsg),
     LyricCycle,
     LyricAdjustDelay(i64),
+    LyricScrollToggle,
     PlayerToggleGapless,
     PlayerTogglePause,
     PlayerVolumeUp,
@@ -66,11 +74,19 @@ pub enum Msg {
     PlayerSpeedDown,
     PlayerSeekForward,
     PlayerSeekBackward,
+    PlayerChapterNext,
+    PlayerChapterPrevious,
+    PlayerSectionNext,
+    PlayerSectionPrevious,
+    PlayerWaveform(String, Vec<u8>),
     Playlist(PLMsg),
     Podcast(PCMsg),
     QuitPopupCloseCancel,
     QuitPopupCloseOk,
     QuitPopupShow,
+    RatingDown,
+    RatingSet(u8),
+    RatingUp,
     SavePlaylistPopupShow,
     SavePlaylistPopupCloseCancel,
     SavePlaylistPopupUpdate(String),
@@ -106,6 +122,8 @@ pub enum DLMsg {
     MessageHide((String, String)),
     YoutubeSearchSuccess(YoutubeOptions),
     YoutubeSearchFail(String),
+    YoutubeStreamReady(AudioStream),
+    YoutubeStreamFail(String),
     FetchPhotoSuccess(ImageWrapper),
     FetchPhotoErr(String),
 }
@@ -114,6 +132,9 @@ pub enum DLMsg {
 pub enum LyricMsg {
     LyricTextAreaBlurUp,
     LyricTextAreaBlurDown,
+    TranscriptSearchPopupShow,
+    TranscriptSearchPopupCloseCancel,
+    TranscriptSearchPopupCloseOk(String),
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -192,6 +213,8 @@ pub enum ConfigEditorMsg {
 pub enum KFMsg {
     DatabaseAddAllBlurDown,
     DatabaseAddAllBlurUp,
+    DatabaseAddQueueBlurDown,
+    DatabaseAddQueueBlurUp,
     GlobalConfigBlurDown,
     GlobalConfigBlurUp,
     GlobalDownBlurDown,
@@ -278,12 +301,24 @@ pub enum KFMsg {
     PlaylistLqueueBlurUp,
     PlaylistTqueueBlurDown,
     PlaylistTqueueBlurUp,
+    PlaylistSortBlurDown,
+    PlaylistSortBlurUp,
+    PlaylistShuffleModeBlurDown,
+    PlaylistShuffleModeBlurUp,
+    PlaylistTabNextBlurDown,
+    PlaylistTabNextBlurUp,
+    PlaylistTabPreviousBlurDown,
+    PlaylistTabPreviousBlurUp,
+    PlaylistRadioBlurDown,
+    PlaylistRadioBlurUp,
     LibrarySwitchRootBlurDown,
     LibrarySwitchRootBlurUp,
     LibraryAddRootBlurDown,
     LibraryAddRootBlurUp,
     LibraryRemoveRootBlurDown,
     LibraryRemoveRootBlurUp,
+    LibraryAddQueueBlurDown,
+    LibraryAddQueueBlurUp,
     GlobalLayoutPodcastBlurDown,
     GlobalLayoutPodcastBlurUp,
     GlobalXywhMoveLeftBlurDown,
@@ -300,6 +335,12 @@ pub enum KFMsg {
     GlobalXywhZoomOutBlurUp,
     GlobalXywhHideBlurDown,
     GlobalXywhHideBlurUp,
+    GlobalLayoutCycleBlurDown,
+    GlobalLayoutCycleBlurUp,
+    GlobalMiniToggleBlurDown,
+    GlobalMiniToggleBlurUp,
+    GlobalCommandPaletteBlurDown,
+    GlobalCommandPaletteBlurUp,
     PodcastMarkPlayedBlurDown,
     PodcastMarkPlayedBlurUp,
     PodcastMarkAllPlayedBlurDown,
@@ -308,6 +349,10 @@ pub enum KFMsg {
     PodcastEpDownloadBlurUp,
     PodcastEpDeleteFileBlurDown,
     PodcastEpDeleteFileBlurUp,
+    PodcastAddQueueBlurDown,
+    PodcastAddQueueBlurUp,
+    LyricSearchBlurDown,
+    LyricSearchBlurUp,
     PodcastDeleteFeedBlurDown,
     PodcastDeleteFeedBlurUp,
     PodcastDeleteAllFeedsBlurDown,
@@ -318,6 +363,22 @@ pub enum KFMsg {
     PodcastRefreshFeedBlurUp,
     PodcastRefreshAllFeedsBlurDown,
     PodcastRefreshAllFeedsBlurUp,
+    GlobalPlayerChapterNextBlurDown,
+    GlobalPlayerChapterNextBlurUp,
+    GlobalPlayerChapterPreviousBlurDown,
+    GlobalPlayerChapterPreviousBlurUp,
+    GlobalPlayerSectionNextBlurDown,
+    GlobalPlayerSectionNextBlurUp,
+    GlobalPlayerSectionPreviousBlurDown,
+    GlobalPlayerSectionPreviousBlurUp,
+    GlobalRatingUpBlurDown,
+    GlobalRatingUpBlurUp,
+    GlobalRatingDownBlurDown,
+    GlobalRatingDownBlurUp,
+    GlobalFavouriteToggleBlurDown,
+    GlobalFavouriteToggleBlurUp,
+    PodcastFeedSettingsBlurDown,
+    PodcastFeedSettingsBlurUp,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -336,6 +397,7 @@ pub enum LIMsg {
 pub enum DBMsg {
     AddAllToPlaylist,
     AddPlaylist(usize),
+    AddQueue(usize),
     CriteriaBlurDown,
     CriteriaBlurUp,
     SearchResult(usize),
@@ -355,12 +417,16 @@ pub enum PCMsg {
     PodcastAddPopupShow,
     PodcastAddPopupCloseOk(String),
     PodcastAddPopupCloseCancel,
+    FeedSettingsPopupShow,
+    FeedSettingsPopupCloseOk(String),
+    FeedSettingsPopupCloseCancel,
     SyncData((i64, PodcastNoId)),
     NewData(PodcastNoId),
     Error(String, PodcastFeed),
     PodcastSelected(usize),
     DescriptionUpdate,
     EpisodeAdd(usize),
+    EpisodeQueue(usize),
     EpisodeMarkPlayed(usize),
     EpisodeMarkAllPlayed,
     PodcastRefreshOne(usize),
@@ -368,7 +434,11 @@ pub enum PCMsg {
     FetchPodcastStart(String),
     EpisodeDownload(usize),
     DLStart(EpData),
+    DLProgress(String, u64, Option<u64>),
     DLComplete(EpData),
+    DLCancelled(EpData),
+    ChaptersFetched(String, Vec<Chapter>),
+    TranscriptFetched(String, String, String),
     DLResponseError(EpData),
     DLFileCreateError(EpData),
     DLFileWriteError(EpData),
@@ -402,6 +472,22 @@ pub enum PLMsg {
     SwapUp(usize),
     CmusLQueue,
     CmusTQueue,
+    Sort,
+    QueueAdd(String),
+    QueueDelete(usize),
+    QueueClear,
+    UpNextBlurDown,
+    UpNextBlurUp,
+    ShuffleModeCycle,
+    ListNext,
+    ListPrevious,
+    ListSwitch(String),
+    ListNew(String),
+    ListRename(String),
+    ListDuplicate(String),
+    ListDelete,
+    ListAddTo(String),
+    RadioToggle,
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GSMsg {
@@ -421,6 +507,17 @@ pub enum GSMsg {
     PopupCloseOkPlaylistLocate,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CPMsg {
+    PopupShow,
+    PopupCloseCancel,
+    InputBlur,
+    TableBlur,
+    PopupUpdate(String),
+    PopupCloseOk(String),
+    PopupCloseOkSelected,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum YSMsg {
     InputPopupShow,
@@ -430,6 +527,8 @@ pub enum YSMsg {
     TablePopupPrevious,
     TablePopupCloseCancel,
     TablePopupCloseOk(usize),
+    TablePopupStream(usize),
+    TablePopupBack,
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TEMsg {
@@ -466,6 +565,8 @@ pub enum TFMsg {
 // Let's define the component ids for our application
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub enum Id {
+    CommandPaletteInput,
+    CommandPaletteTable,
     ConfigEditor(IdConfigEditor),
     DBListCriteria,
     DBListSearchResult,
@@ -482,18 +583,24 @@ pub enum Id {
     Label,
     Library,
     Lyric,
+    UpNext,
     MessagePopup,
     Playlist,
+    PlaylistTabs,
     Podcast,
     PodcastAddPopup,
+    FeedSettingsPopup,
     PodcastSearchTablePopup,
+    TranscriptSearchPopup,
     FeedDeleteConfirmRadioPopup,
     FeedDeleteConfirmInputPopup,
     Progress,
+    Oscilloscope,
     QuitPopup,
     SavePlaylistPopup,
     SavePlaylistLabel,
     SavePlaylistConfirm,
+    Spectrum,
     TagEditor(IdTagEditor),
     YoutubeSearchInputPopup,
     YoutubeSearchTablePopup,
@@ -555,6 +662,7 @@ pub enum IdConfigEditor {
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub enum IdKey {
     DatabaseAddAll,
+    DatabaseAddQueue,
     GlobalConfig,
     GlobalDown,
     GlobalGotoBottom,
@@ -598,9 +706,15 @@ pub enum IdKey {
     PlaylistSwapUp,
     PlaylistLqueue,
     PlaylistTqueue,
+    PlaylistSort,
+    PlaylistShuffleMode,
+    PlaylistTabNext,
+    PlaylistTabPrevious,
+    PlaylistRadio,
     LibrarySwitchRoot,
     LibraryAddRoot,
     LibraryRemoveRoot,
+    LibraryAddQueue,
     GlobalLayoutPodcast,
     GlobalXywhMoveLeft,
     GlobalXywhMoveRight,
@@ -609,15 +723,28 @@ pub enum IdKey {
     GlobalXywhZoomIn,
     GlobalXywhZoomOut,
     GlobalXywhHide,
+    GlobalLayoutCycle,
+    GlobalMiniToggle,
+    GlobalCommandPalette,
     PodcastMarkPlayed,
     PodcastMarkAllPlayed,
     PodcastEpDownload,
     PodcastEpDeleteFile,
+    PodcastAddQueue,
+    LyricSearch,
     PodcastDeleteFeed,
     PodcastDeleteAllFeeds,
     PodcastSearchAddFeed,
     PodcastRefreshFeed,
     PodcastRefreshAllFeeds,
+    GlobalPlayerChapterNext,
+    GlobalPlayerChapterPrevious,
+    GlobalPlayerSectionNext,
+    GlobalPlayerSectionPrevious,
+    GlobalRatingUp,
+    GlobalRatingDown,
+    GlobalFavouriteToggle,
+    PodcastFeedSettings,
 }
 pub enum SearchLyricState {
     Finish(Vec<SongTag>),
@@ -648,6 +775,8 @@ impl UI {
             self.model.te_update_lyric_options();
             self.model.update_player_msg();
             self.model.update_outside_msg();
+            self.model.update_mouse();
+            self.model.theme_watch();
             if self.model.layout != TermusicLayout::Podcast {
                 self.model.lyric_update();
             }
@@ -683,6 +812,9 @@ impl UI {
         }
         self.model.player_save_last_position();
         assert!(self.model.player.playlist.save().is_ok());
+        if let Err(e) = self.model.playlist_store() {
+            eprintln!("{e}");
+        }
         if let Err(e) = self.model.config.save() {
             eprintln!("{e}");
         };
@@ -696,6 +828,11 @@ impl UI {
         if self.model.since_last_redraw() >= FORCED_REDRAW_INTERVAL {
             self.model.force_redraw();
         }
+        if self.model.visualizer_active()
+            && self.model.since_last_redraw() >= VISUALIZER_REDRAW_INTERVAL
+        {
+            self.model.force_redraw();
+        }
         // }
     }
 }
@@ -24,6 +24,7 @@
 
 #[cfg(feature = "discord")]
 use crate::discord::Rpc;
+mod mouse;
 #[cfg(feature = "mpris")]
 mod mpris;
 mod update;
@@ -38,20 +39,26 @@ use crate::{
     ui::{Application, Id, Msg},
 };
 
-use crate::config::{Keys, StyleColorSymbol};
-use crate::player::{GeneralPlayer, Loop, PlayerTrait};
-use crate::podcast::{db::Database as DBPod, Podcast, PodcastFeed, Threadpool};
+use crate::config::{theme_modified, AlbumArtProtocol, Keys, StyleColorSymbol};
+use crate::player::{GeneralPlayer, PlayerTrait};
+use crate::podcast::{db::Database as DBPod, DownloadCancel, Podcast, PodcastFeed, Threadpool};
 use crate::songtag::SongTag;
 use crate::sqlite::TrackForDB;
 use crate::track::MediaType;
+use crate::ui::components::{sixel_supported, AlbumArt};
 use crate::ui::SearchLyricState;
 use crate::utils::{get_app_config_path, DownloadTracker};
-use std::path::PathBuf;
+use crate::waveform::{self, WAVEFORM_POINTS};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use mouse::Mouse;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, Sender};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tui_realm_treeview::Tree;
 use tuirealm::event::NoUserEvent;
 use tuirealm::terminal::TerminalBridge;
+use tuirealm::tui::layout::Rect;
 pub use youtube_options::YoutubeOptions;
 
 #[derive(PartialEq, Eq)]
@@ -69,6 +76,7 @@ pub enum ConfigEditorLayout {
     Key2,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct Model {
     /// Indicates that the application must quit
     pub quit: bool,
@@ -87,6 +95,8 @@ pub struct Model {
     pub tageditor_song: Option<Track>,
     pub time_pos: i64,
     pub lyric_line: String,
+    /// The file and lyric frame the scrolling lyric view shows the lines of
+    pub lyric_lines: Option<(String, usize)>,
     youtube_options: YoutubeOptions,
     #[cfg(feature = "cover")]
     pub ueberzug_instance: UeInstance,
@@ -106,9 +116,30 @@ pub struct Model {
     pub db_search_results: Vec<String>,
     pub db_search_tracks: Vec<TrackForDB>,
     pub layout: TermusicLayout,
+    /// Where the album art pane of the current layout is, if it has one
+    pub album_art_area: Option<Rect>,
+    /// Album art drawn as Sixel graphics or half blocks
+    pub album_art: AlbumArt,
+    /// Draws the mini player instead of the layout
+    pub mini: bool,
+    /// The view that had focus before the mini player took it
+    pub mini_focus: Option<Id>,
+    /// Envelope of the current track, empty until it is computed
+    pub waveform: Vec<u8>,
+    pub mouse: Mouse,
+    /// When the theme file was last changed, to reload it after edits
+    pub theme_modified: Option<SystemTime>,
+    pub theme_checked: Instant,
+    /// Column the playlist was last sorted by
+    pub playlist_sort_column: Option<usize>,
+    /// Names of the named playlists, for the tabs above the playlist
+    pub playlist_names: Vec<String>,
+    /// Where to go on in the current track of a playlist switched to
+    pub playlist_resume: Option<Duration>,
     pub config_layout: ConfigEditorLayout,
     pub config_changed: bool,
     pub download_tracker: DownloadTracker,
+    pub download_cancel: DownloadCancel,
     pub podcasts: Vec<Podcast>,
     pub podcasts_index: usize,
     pub db_podcast: DBPod,
@@ -121,9 +152,37 @@ pub struct Model {
 pub enum ViuerSupported {
     Kitty,
     ITerm,
+    Sixel,
+    HalfBlocks,
+    /// Drawn by ueberzug, if the `cover` feature is enabled
     NotSupported,
 }
 
+impl ViuerSupported {
+    fn new(protocol: AlbumArtProtocol) -> Self {
+        match protocol {
+            AlbumArtProtocol::Kitty => Self::Kitty,
+            AlbumArtProtocol::ITerm => Self::ITerm,
+            AlbumArtProtocol::Sixel => Self::Sixel,
+            AlbumArtProtocol::HalfBlocks => Self::HalfBlocks,
+            AlbumArtProtocol::Ueberzug => Self::NotSupported,
+            AlbumArtProtocol::Auto => {
+                if viuer::KittySupport::None != viuer::get_kitty_support() {
+                    Self::Kitty
+                } else if viuer::is_iterm_supported() {
+                    Self::ITerm
+                } else if sixel_supported() {
+                    Self::Sixel
+                } else if cfg!(feature = "cover") {
+                    Self::NotSupported
+                } else {
+                    Self::HalfBlocks
+                }
+            }
+        }
+    }
+}
+
 impl Model {
     pub fn new(config: &Settings) -> Self {
         let path = Self::get_full_path_from_config(config);
@@ -131,17 +190,14 @@ impl Model {
 
         let (tx3, rx3): (Sender<SearchLyricState>, Receiver<SearchLyricState>) = mpsc::channel();
 
-        let mut viuer_supported = ViuerSupported::NotSupported;
-        if viuer::KittySupport::None != viuer::get_kitty_support() {
-            viuer_supported = ViuerSupported::Kitty;
-        } else if viuer::is_iterm_supported() {
-            viuer_supported = ViuerSupported::ITerm;
-        }
+        let viuer_supported = ViuerSupported::new(config.album_art_protocol);
         let db = DataBase::new(config);
         let db_criteria = SearchCriteria::Artist;
-        let app = Self::init_app(&tree, config);
+        let (mouse, mouse_tx) = Mouse::new();
+        let app = Self::init_app(&tree, config, mouse_tx);
         let terminal = TerminalBridge::new().expect("Could not initialize terminal");
-        let player = GeneralPlayer::new(config);
+        let mut player = GeneralPlayer::new(config);
+        player.playlist.set_play_counts(db.get_play_counts());
         // let viuer_supported =
         //     viuer::KittySupport::None != viuer::get_kitty_support() || viuer::is_iterm_supported();
 
@@ -176,6 +232,7 @@ impl Model {
             tageditor_song: None,
             time_pos: 0,
             lyric_line: String::new(),
+            lyric_lines: None,
             youtube_options: YoutubeOptions::new(),
             #[cfg(feature = "cover")]
             ueberzug_instance,
@@ -192,6 +249,17 @@ impl Model {
             discord,
             db,
             layout: TermusicLayout::TreeView,
+            album_art_area: None,
+            album_art: AlbumArt::default(),
+            mini: config.mini_from_cli,
+            mini_focus: None,
+            waveform: Vec::new(),
+            mouse,
+            theme_modified: theme_modified(&config.style_color_symbol),
+            theme_checked: Instant::now(),
+            playlist_sort_column: None,
+            playlist_names: Vec::new(),
+            playlist_resume: None,
             config_layout: ConfigEditorLayout::General,
             db_criteria,
             db_search_results: Vec::new(),
@@ -204,6 +272,7 @@ impl Model {
             tx_to_main,
             rx_to_main,
             download_tracker: DownloadTracker::default(),
+            download_cancel: DownloadCancel::default(),
             podcast_search_vec: None,
         }
     }
@@ -225,8 +294,11 @@ impl Model {
             self.mount_error_popup(format!("theme save error: {e}"));
         }
         self.mount_label_help();
+        self.visualizer_reload();
         self.db.sync_database(&self.path);
-        self.playlist_sync();
+        self.playlist_switch_layout();
+        self.playlist_tabs_sync();
+        self.mini_focus_update();
     }
 
     /// Initialize terminal
@@ -234,10 +306,16 @@ impl Model {
         let _ = self.terminal.enable_raw_mode();
         let _ = self.terminal.enter_alternate_screen();
         let _ = self.terminal.clear_screen();
+        if self.config.enable_mouse {
+            let _ = execute!(self.terminal.raw_mut().backend_mut(), EnableMouseCapture);
+        }
     }
 
     /// Finalize terminal
     pub fn finalize_terminal(&mut self) {
+        if self.config.enable_mouse {
+            let _ = execute!(self.terminal.raw_mut().backend_mut(), DisableMouseCapture);
+        }
         let _ = self.terminal.disable_raw_mode();
         let _ = self.terminal.leave_alternate_screen();
         let _ = self.terminal.clear_screen();
@@ -288,16 +366,32 @@ impl Model {
         if let Err(e) = self.update_photo() {
             self.mount_error_popup(format!("update photo error: {e}"));
         };
+        self.cover_prefetch();
         self.progress_update_title();
         self.lyric_update_title();
         self.update_playing_song();
     }
 
-    pub fn player_previous(&mut self) {
-        if let Loop::Single | Loop::Queue = self.config.loop_mode {
+    /// Counts a play of the current track, for shuffling less played first.
+    /// Streams aren't counted, their urls expire.
+    fn player_count_play(&mut self) {
+        let Some(file) = self
+            .player
+            .playlist
+            .current_track()
+            .filter(|track| !track.is_stream())
+            .and_then(Track::file)
+            .map(ToString::to_string)
+        else {
             return;
+        };
+        if let Err(e) = self.db.add_play_count(&file) {
+            self.mount_error_popup(format!("Error count play: {e}"));
         }
+        self.player.playlist.add_play_count(&file);
+    }
 
+    pub fn player_previous(&mut self) {
         if self.player.playlist.is_empty() {
             self.player_stop();
             return;
@@ -327,6 +421,116 @@ impl Model {
         self.progress_update_title();
     }
 
+    /// Title of the chapter playing now.
+    pub fn chapter_current(&self) -> Option<String> {
+        let track = self.player.playlist.current_track()?;
+        let time_pos = Duration::from_secs(self.time_pos.try_into().unwrap_or(0));
+        track.chapter_at(time_pos).map(|c| c.title.clone())
+    }
+
+    pub fn player_seek_chapter(&mut self, forward: bool) {
+        let Some(track) = self.player.playlist.current_track() else {
+            return;
+        };
+        let time_pos = Duration::from_secs(self.time_pos.try_into().unwrap_or(0));
+        let target = if forward {
+            track.chapter_next_start(time_pos)
+        } else {
+            track.chapter_previous_start(time_pos)
+        };
+        if let Some(target) = target {
+            self.player.seek_to(target);
+        }
+    }
+
+    /// Seeks to the next or previous point where the waveform of the track
+    /// turns loud or quiet.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn player_seek_section(&mut self, forward: bool) {
+        let Some(track) = self.player.playlist.current_track() else {
+            return;
+        };
+        let duration = track.duration().as_secs_f64();
+        let time_pos = self.time_pos as f64;
+        // a little slack so pressing again moves on instead of staying put
+        let mut starts = waveform::sections(&self.waveform)
+            .into_iter()
+            .map(|fraction| fraction * duration);
+        let target = if forward {
+            starts.find(|start| *start > time_pos + 1.0)
+        } else {
+            starts
+                .rev()
+                .find(|start| *start < time_pos - 2.0)
+                .or(Some(0.0))
+        };
+        if let Some(target) = target {
+            self.player.seek_to(Duration::from_secs(target as u64));
+        }
+    }
+
+    /// Shows the waveform of the current track, from the library database if
+    /// it was computed before, otherwise by decoding the file in the
+    /// background.
+    pub fn waveform_load(&mut self) {
+        self.waveform.clear();
+        self.progress_set_waveform();
+        let Some(file) = self.player.playlist.current_track().and_then(Track::file) else {
+            return;
+        };
+        let file = file.to_string();
+        let Some(last_modified) = std::fs::metadata(&file)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| modified.as_secs())
+        else {
+            // streams and episodes that are not downloaded
+            return;
+        };
+        if let Some(waveform) = self.db.get_waveform(&file, last_modified) {
+            self.waveform = waveform;
+            self.progress_set_waveform();
+            return;
+        }
+        let conn = self.db.connection();
+        let tx = self.tx_to_main.clone();
+        std::thread::spawn(move || {
+            if let Ok(waveform) = waveform::envelope(Path::new(&file), WAVEFORM_POINTS) {
+                DataBase::set_waveform(&conn, &file, last_modified, &waveform).ok();
+                tx.send(Msg::PlayerWaveform(file, waveform)).ok();
+            }
+        });
+    }
+
+    pub fn waveform_loaded(&mut self, file: &str, waveform: &[u8]) {
+        let current = self.player.playlist.current_track().and_then(Track::file);
+        if current == Some(file) {
+            self.waveform = waveform.to_vec();
+            self.progress_set_waveform();
+        }
+    }
+
+    /// Keeps a speed change for the current episode only when its feed has
+    /// a speed of its own, otherwise it becomes the global speed.
+    pub fn player_speed_changed(&mut self) {
+        let feed_speed = self
+            .player
+            .playlist
+            .current_track()
+            .and_then(|track| track.playback)
+            .and_then(|playback| playback.speed);
+        if feed_speed.is_none() {
+            self.config.speed = self.player.speed();
+            self.player.speed_default = self.config.speed;
+        }
+        self.progress_update_title();
+    }
+
     pub fn player_seek(&mut self, offset: i64) {
         // FIXME: dirty fix for seeking when paused with symphonia,basically set it to play
         // in rusty sink code, and seek, and then set it back to pause.
@@ -388,6 +592,10 @@ impl Model {
 
     // #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
     pub fn player_restore_last_position(&mut self) {
+        if let Some(position) = self.playlist_resume.take() {
+            self.player.seek_to(position);
+            return;
+        }
         let mut restored = false;
         match self.config.remember_last_played_position {
             crate::config::LastPosition::Yes => {
@@ -401,9 +609,12 @@ impl Model {
                         }
 
                         Some(MediaType::Podcast) => {
+                            // A podcast that was never started keeps its intro skip.
                             if let Ok(last_pos) = self.db_podcast.get_last_position(track) {
-                                self.player.seek_to(last_pos);
-                                restored = true;
+                                if !last_pos.is_zero() {
+                                    self.player.seek_to(last_pos);
+                                    restored = true;
+                                }
                             }
                         }
                         None => {}
@@ -425,8 +636,10 @@ impl Model {
 
                             Some(MediaType::Podcast) => {
                                 if let Ok(last_pos) = self.db_podcast.get_last_position(track) {
-                                    self.player.seek_to(last_pos);
-                                    restored = true;
+                                    if !last_pos.is_zero() {
+                                        self.player.seek_to(last_pos);
+                                        restored = true;
+                                    }
                                 }
                             }
                             None => {}
@@ -0,0 +1,347 @@
+//! Mouse support. The components only handle the keyboard, so the input
+//! listener hands mouse events to the model, which finds the component under
+//! the pointer from where it was last drawn.
+use super::Model;
+use crate::player::PlayerTrait;
+use crate::ui::components::{LIBRARY_CLICK_ROW, LIBRARY_SCROLL};
+use crate::ui::{DBMsg, Id, LIMsg, Msg, PCMsg, PLMsg};
+use crossterm::event::{self as xterm, MouseButton, MouseEvent, MouseEventKind};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+use tuirealm::event::NoUserEvent;
+use tuirealm::listener::{ListenerError, ListenerResult, Poll};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue};
+use tuirealm::tui::layout::{Margin, Rect};
+use tuirealm::{Event, State, StateValue, Update};
+
+/// Longest gap between the two clicks of a double click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+/// Components of the main views. Mouse events are ignored while anything
+/// else, like a popup, has the focus.
+const MAIN_VIEW: [Id; 9] = [
+    Id::Library,
+    Id::Playlist,
+    Id::UpNext,
+    Id::DBListCriteria,
+    Id::DBListSearchResult,
+    Id::DBListSearchTracks,
+    Id::Podcast,
+    Id::Episode,
+    Id::Lyric,
+];
+
+/// Reads terminal events like the default crossterm listener, but sends
+/// mouse events to the model instead of the application.
+pub struct InputListener {
+    interval: Duration,
+    mouse: Sender<MouseEvent>,
+}
+
+impl InputListener {
+    pub fn new(interval: Duration, mouse: Sender<MouseEvent>) -> Self {
+        Self {
+            interval: interval / 2,
+            mouse,
+        }
+    }
+}
+
+impl Poll<NoUserEvent> for InputListener {
+    fn poll(&mut self) -> ListenerResult<Option<Event<NoUserEvent>>> {
+        match xterm::poll(self.interval) {
+            Ok(true) => match xterm::read() {
+                Ok(xterm::Event::Mouse(event)) => {
+                    self.mouse.send(event).ok();
+                    Ok(None)
+                }
+                Ok(event) => Ok(Some(Event::from(event))),
+                Err(_) => Err(ListenerError::PollFailed),
+            },
+            Ok(false) => Ok(None),
+            Err(_) => Err(ListenerError::PollFailed),
+        }
+    }
+}
+
+pub struct Mouse {
+    rx: Receiver<MouseEvent>,
+    /// Where each component that reacts to the mouse was last drawn
+    pub areas: Vec<(Id, Rect)>,
+    /// Component, row and time of the last click, to recognize double clicks
+    last_click: Option<(Id, usize, Instant)>,
+    /// Playlist row being dragged
+    drag: Option<usize>,
+}
+
+impl Mouse {
+    /// The model's end of the channel, and the sender for the input listener.
+    pub fn new() -> (Self, Sender<MouseEvent>) {
+        let (tx, rx) = mpsc::channel();
+        let mouse = Self {
+            rx,
+            areas: Vec::new(),
+            last_click: None,
+            drag: None,
+        };
+        (mouse, tx)
+    }
+}
+
+fn inner(area: Rect) -> Rect {
+    area.inner(&Margin {
+        vertical: 1,
+        horizontal: 1,
+    })
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+}
+
+impl Model {
+    pub fn update_mouse(&mut self) {
+        while let Ok(event) = self.mouse.rx.try_recv() {
+            if !matches!(self.app.focus(), Some(focus) if MAIN_VIEW.contains(focus)) {
+                continue;
+            }
+            self.mouse_event(event);
+            self.redraw = true;
+        }
+    }
+
+    fn mouse_event(&mut self, event: MouseEvent) {
+        if let MouseEventKind::Up(MouseButton::Left) = event.kind {
+            self.mouse.drag = None;
+            return;
+        }
+        let Some((id, area)) = self
+            .mouse
+            .areas
+            .iter()
+            .find(|(_, area)| contains(*area, event.column, event.row))
+            .cloned()
+        else {
+            return;
+        };
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.mouse_click(&id, area, event.column, event.row);
+            }
+            MouseEventKind::Drag(MouseButton::Left) if id == Id::Playlist => {
+                self.mouse_drag(area, event.row);
+            }
+            MouseEventKind::ScrollDown => self.mouse_scroll(&id, true),
+            MouseEventKind::ScrollUp => self.mouse_scroll(&id, false),
+            _ => {}
+        }
+    }
+
+    fn mouse_click(&mut self, id: &Id, area: Rect, column: u16, row: u16) {
+        if *id == Id::Progress {
+            self.mouse_seek(area, column);
+            return;
+        }
+        self.app.active(id).ok();
+
+        if *id == Id::Library {
+            let inner = inner(area);
+            if !contains(inner, column, row) {
+                return;
+            }
+            self.app
+                .attr(
+                    id,
+                    Attribute::Custom(LIBRARY_CLICK_ROW),
+                    AttrValue::Payload(PropPayload::Tup2((
+                        PropValue::Usize(usize::from(row - inner.y)),
+                        PropValue::Usize(usize::from(inner.height)),
+                    ))),
+                )
+                .ok();
+            if self.mouse_double_click(id, usize::from(row)) {
+                self.mouse_activate_library();
+            }
+            return;
+        }
+
+        let Some(index) = self.mouse_list_index(id, area, row) else {
+            return;
+        };
+        self.mouse_select(id, index);
+        if *id == Id::Playlist {
+            self.mouse.drag = Some(index);
+        }
+        if self.mouse_double_click(id, index) {
+            self.mouse_activate(id, index);
+        }
+    }
+
+    /// Index of the item of list `id` drawn on screen row `row`, if any.
+    fn mouse_list_index(&self, id: &Id, area: Rect, row: u16) -> Option<usize> {
+        let inner = inner(area);
+        let header = u16::from(*id == Id::Playlist);
+        if row < inner.y + header || row >= inner.bottom() {
+            return None;
+        }
+        let rows = usize::from(inner.height - header);
+        // lists scroll just enough to keep the selected item on screen
+        let offset = (self.mouse_selected(id) + 1).saturating_sub(rows);
+        let index = offset + usize::from(row - inner.y - header);
+        let len = if *id == Id::Playlist {
+            // the empty playlist has a placeholder row
+            self.player.playlist.len()
+        } else {
+            match self.app.query(id, Attribute::Content) {
+                Ok(Some(AttrValue::Table(table))) => table.len(),
+                _ => 0,
+            }
+        };
+        (index < len).then_some(index)
+    }
+
+    fn mouse_selected(&self, id: &Id) -> usize {
+        match self.app.state(id) {
+            Ok(State::One(StateValue::Usize(index))) => index,
+            _ => 0,
+        }
+    }
+
+    fn mouse_select(&mut self, id: &Id, index: usize) {
+        self.app
+            .attr(
+                id,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(index))),
+            )
+            .ok();
+    }
+
+    fn mouse_double_click(&mut self, id: &Id, index: usize) -> bool {
+        let now = Instant::now();
+        let double = matches!(
+            &self.mouse.last_click,
+            Some((last_id, last_index, at))
+                if last_id == id
+                    && *last_index == index
+                    && now.duration_since(*at) <= DOUBLE_CLICK_INTERVAL
+        );
+        // a third click starts over
+        self.mouse.last_click = if double {
+            None
+        } else {
+            Some((id.clone(), index, now))
+        };
+        double
+    }
+
+    /// Does what Enter does on the item, or adds it to the playlist and
+    /// plays it.
+    fn mouse_activate(&mut self, id: &Id, index: usize) {
+        let msg = match id {
+            Id::Playlist => Msg::Playlist(PLMsg::PlaySelected(index)),
+            Id::DBListCriteria => Msg::DataBase(DBMsg::SearchResult(index)),
+            Id::DBListSearchResult => Msg::DataBase(DBMsg::SearchTrack(index)),
+            Id::DBListSearchTracks => {
+                self.mouse_add_and_play(Msg::DataBase(DBMsg::AddPlaylist(index)));
+                return;
+            }
+            Id::Podcast => Msg::Podcast(PCMsg::PodcastSelected(index)),
+            Id::Episode => {
+                self.mouse_add_and_play(Msg::Podcast(PCMsg::EpisodeAdd(index)));
+                return;
+            }
+            _ => return,
+        };
+        self.mouse_update(msg);
+    }
+
+    /// Steps into the selected directory, or adds the selected file to the
+    /// playlist and plays it.
+    fn mouse_activate_library(&mut self) {
+        let Ok(State::One(StateValue::String(node))) = self.app.state(&Id::Library) else {
+            return;
+        };
+        if Path::new(&node).is_dir() {
+            self.mouse_update(Msg::Library(LIMsg::TreeExtendDir(node)));
+        } else {
+            self.mouse_add_and_play(Msg::Playlist(PLMsg::Add(node)));
+        }
+    }
+
+    fn mouse_add_and_play(&mut self, msg: Msg) {
+        let len = self.player.playlist.len();
+        self.mouse_update(msg);
+        if self.player.playlist.len() > len {
+            let index = if self.config.add_playlist_front {
+                0
+            } else {
+                self.player.playlist.len() - 1
+            };
+            self.mouse_update(Msg::Playlist(PLMsg::PlaySelected(index)));
+        }
+    }
+
+    fn mouse_update(&mut self, msg: Msg) {
+        let mut msg = Some(msg);
+        while msg.is_some() {
+            msg = self.update(msg);
+        }
+    }
+
+    fn mouse_scroll(&mut self, id: &Id, down: bool) {
+        match id {
+            Id::Library => {
+                self.app
+                    .attr(id, Attribute::Custom(LIBRARY_SCROLL), AttrValue::Flag(down))
+                    .ok();
+            }
+            Id::Progress => {}
+            _ => {
+                let selected = self.mouse_selected(id);
+                let index = if down {
+                    selected + 1
+                } else {
+                    selected.saturating_sub(1)
+                };
+                // the list keeps the index within its length
+                self.mouse_select(id, index);
+            }
+        }
+    }
+
+    /// Moves the dragged track to the row under the pointer.
+    fn mouse_drag(&mut self, area: Rect, row: u16) {
+        let Some(from) = self.mouse.drag else {
+            return;
+        };
+        let Some(to) = self.mouse_list_index(&Id::Playlist, area, row) else {
+            return;
+        };
+        if from == to {
+            return;
+        }
+        for index in from..to {
+            self.player.playlist.swap_down(index);
+        }
+        for index in (to + 1..=from).rev() {
+            self.player.playlist.swap_up(index);
+        }
+        self.playlist_sync();
+        self.mouse_select(&Id::Playlist, to);
+        self.mouse.drag = Some(to);
+    }
+
+    fn mouse_seek(&mut self, area: Rect, column: u16) {
+        let inner = inner(area);
+        if column < inner.x || column >= inner.right() {
+            return;
+        }
+        let Some(track) = self.player.playlist.current_track() else {
+            return;
+        };
+        let fraction = f64::from(column - inner.x) / f64::from(inner.width);
+        let position = track.duration().mul_f64(fraction);
+        self.player.seek_to(position);
+    }
+}
@@ -25,13 +25,13 @@ use crate::player::{PlayerMsg, PlayerTrait};
 use crate::sqlite::SearchCriteria;
 use crate::track::MediaType;
 use crate::ui::{
-    model::TermusicLayout, DBMsg, DLMsg, GSMsg, Id, IdTagEditor, LIMsg, LyricMsg, Model, Msg,
-    PCMsg, PLMsg, XYWHMsg, YSMsg,
+    model::TermusicLayout, CPMsg, DBMsg, DLMsg, GSMsg, Id, IdTagEditor, LIMsg, LyricMsg, Model,
+    Msg, PCMsg, PLMsg, XYWHMsg, YSMsg,
 };
 use std::thread::{self, sleep};
 use std::time::Duration;
 use tuirealm::props::{AttrValue, Attribute};
-use tuirealm::Update;
+use tuirealm::{State, StateValue, Update};
 
 impl Update<Msg> for Model {
     #[allow(clippy::too_many_lines)]
@@ -79,6 +79,7 @@ impl Update<Msg> for Model {
                     self.update_general_search(&m);
                     None
                 }
+                Msg::CommandPalette(m) => self.update_command_palette(&m),
                 Msg::Playlist(m) => {
                     self.update_playlist(&m);
                     None
@@ -91,7 +92,12 @@ impl Update<Msg> for Model {
                 | Msg::PlayerVolumeUp
                 | Msg::PlayerVolumeDown
                 | Msg::PlayerSeekForward
-                | Msg::PlayerSeekBackward => self.update_player(&msg),
+                | Msg::PlayerSeekBackward
+                | Msg::PlayerChapterNext
+                | Msg::PlayerChapterPrevious
+                | Msg::PlayerSectionNext
+                | Msg::PlayerSectionPrevious
+                | Msg::PlayerWaveform(..) => self.update_player(&msg),
 
                 Msg::HelpPopupShow => {
                     self.mount_help_popup();
@@ -116,6 +122,29 @@ impl Update<Msg> for Model {
                     self.lyric_adjust_delay(offset);
                     None
                 }
+                Msg::LyricScrollToggle => {
+                    self.config.lyric_scroll = !self.config.lyric_scroll;
+                    self.lyric_line = String::new();
+                    self.lyric_clear_lines();
+                    self.lyric_update();
+                    None
+                }
+                Msg::RatingUp => {
+                    self.rating_stars(|stars| stars.saturating_add(1));
+                    None
+                }
+                Msg::RatingDown => {
+                    self.rating_stars(|stars| stars.saturating_sub(1));
+                    None
+                }
+                Msg::RatingSet(stars) => {
+                    self.rating_stars(|_| stars);
+                    None
+                }
+                Msg::FavouriteToggle => {
+                    self.rating_favourite_toggle();
+                    None
+                }
                 Msg::TagEditor(m) => {
                     self.update_tageditor(&m);
                     None
@@ -126,9 +155,11 @@ impl Update<Msg> for Model {
                     }
                     None
                 }
-                Msg::LayoutDataBase | Msg::LayoutTreeView | Msg::LayoutPodCast => {
-                    self.update_layout(&msg)
-                }
+                Msg::LayoutDataBase
+                | Msg::LayoutTreeView
+                | Msg::LayoutPodCast
+                | Msg::LayoutCycle
+                | Msg::LayoutMiniToggle => self.update_layout(&msg),
 
                 Msg::None => None,
                 Msg::SavePlaylistPopupShow => {
@@ -200,6 +231,19 @@ impl Model {
                 TermusicLayout::DataBase => self.app.active(&Id::DBListCriteria).ok(),
                 TermusicLayout::Podcast => self.app.active(&Id::Podcast).ok(),
             },
+            LyricMsg::TranscriptSearchPopupShow => {
+                self.mount_transcript_search_popup();
+                None
+            }
+            LyricMsg::TranscriptSearchPopupCloseCancel => {
+                self.umount_transcript_search_popup();
+                None
+            }
+            LyricMsg::TranscriptSearchPopupCloseOk(phrase) => {
+                self.umount_transcript_search_popup();
+                self.lyric_search_seek(phrase);
+                None
+            }
         };
         None
     }
@@ -231,6 +275,14 @@ impl Model {
                 }
             }
             PCMsg::PodcastAddPopupCloseCancel => self.umount_podcast_add_popup(),
+            PCMsg::FeedSettingsPopupShow => self.mount_feed_settings_popup(),
+            PCMsg::FeedSettingsPopupCloseOk(input) => {
+                self.umount_feed_settings_popup();
+                if let Err(e) = self.podcast_set_playback(input) {
+                    self.mount_error_popup(format!("Error in feed settings: {e}"));
+                }
+            }
+            PCMsg::FeedSettingsPopupCloseCancel => self.umount_feed_settings_popup(),
             PCMsg::SyncData((id, pod)) => {
                 self.download_tracker.decrease_one(&pod.url);
                 self.show_message_timeout_label_help(
@@ -277,6 +329,11 @@ impl Model {
                     self.mount_error_popup(format!("Error add episode: {e}"));
                 }
             }
+            PCMsg::EpisodeQueue(index) => {
+                if let Err(e) = self.queue_add_episode(*index) {
+                    self.mount_error_popup(format!("Error add episode to up next: {e}"));
+                }
+            }
             PCMsg::EpisodeMarkPlayed(index) => {
                 if let Err(e) = self.episode_mark_played(*index) {
                     self.mount_error_popup(format!("Error mark played: {e}"));
@@ -320,11 +377,47 @@ impl Model {
                     None,
                 );
             }
+            PCMsg::DLProgress(url, downloaded, total) => {
+                if !self.download_tracker.set_progress(url, *downloaded, *total) {
+                    return None;
+                }
+                if let Err(e) = self.podcast_sync_episodes() {
+                    self.mount_error_popup(format!("Error sync episodes: {e}"));
+                }
+            }
+            PCMsg::DLCancelled(ep_data) => {
+                self.episode_download_finished(ep_data);
+                self.show_message_timeout_label_help(
+                    self.download_tracker
+                        .message_download_cancelled(&ep_data.title),
+                    None,
+                    None,
+                    None,
+                );
+                if let Err(e) = self.podcast_sync_episodes() {
+                    self.mount_error_popup(format!("Error sync episodes: {e}"));
+                }
+            }
+            PCMsg::ChaptersFetched(url, chapters) => {
+                if let Some(mut track) = self.player.playlist.current_track_as_mut() {
+                    if track.chapters_url.as_ref() == Some(url) {
+                        track.set_chapters(chapters.clone());
+                        self.player.playlist.set_current_track(Some(&track));
+                        self.progress_update_title();
+                        self.lyric_update_title();
+                    }
+                }
+            }
+            PCMsg::TranscriptFetched(url, mime, body) => {
+                if let Err(e) = self.podcast_transcript_fetched(url, mime, body) {
+                    self.mount_error_popup(format!("Error in loading transcript: {e}"));
+                }
+            }
             PCMsg::DLComplete(ep_data) => {
                 if let Err(e) = self.episode_download_complete(ep_data.clone()) {
                     self.mount_error_popup(format!("Error in inserting episode: {e}"));
                 }
-                self.download_tracker.decrease_one(&ep_data.url);
+                self.episode_download_finished(ep_data);
                 self.show_message_timeout_label_help(
                     self.download_tracker.message_download_complete(),
                     None,
@@ -333,7 +426,7 @@ impl Model {
                 );
             }
             PCMsg::DLResponseError(ep_data) => {
-                self.download_tracker.decrease_one(&ep_data.url);
+                self.episode_download_finished(ep_data);
                 self.mount_error_popup(format!("download failed for episode: {}", ep_data.title));
                 self.show_message_timeout_label_help(
                     self.download_tracker
@@ -344,7 +437,7 @@ impl Model {
                 );
             }
             PCMsg::DLFileCreateError(ep_data) => {
-                self.download_tracker.decrease_one(&ep_data.url);
+                self.episode_download_finished(ep_data);
                 self.mount_error_popup(format!("download failed for episode: {}", ep_data.title));
                 self.show_message_timeout_label_help(
                     self.download_tracker
@@ -355,7 +448,7 @@ impl Model {
                 );
             }
             PCMsg::DLFileWriteError(ep_data) => {
-                self.download_tracker.decrease_one(&ep_data.url);
+                self.episode_download_finished(ep_data);
                 self.mount_error_popup(format!("download failed for episode: {}", ep_data.title));
                 self.show_message_timeout_label_help(
                     self.download_tracker
@@ -445,15 +538,18 @@ impl Model {
                 };
                 self.player_seek(offset);
             }
+            Msg::PlayerChapterNext => self.player_seek_chapter(true),
+            Msg::PlayerChapterPrevious => self.player_seek_chapter(false),
+            Msg::PlayerSectionNext => self.player_seek_section(true),
+            Msg::PlayerSectionPrevious => self.player_seek_section(false),
+            Msg::PlayerWaveform(file, waveform) => self.waveform_loaded(file, waveform),
             Msg::PlayerSpeedUp => {
                 self.player.speed_up();
-                self.config.speed = self.player.speed();
-                self.progress_update_title();
+                self.player_speed_changed();
             }
             Msg::PlayerSpeedDown => {
                 self.player.speed_down();
-                self.config.speed = self.player.speed();
-                self.progress_update_title();
+                self.player_speed_changed();
             }
             Msg::PlayerVolumeUp => {
                 self.player.volume_up();
@@ -505,6 +601,7 @@ impl Model {
 
                 self.layout = TermusicLayout::DataBase;
                 self.playlist_switch_layout();
+                self.mini_focus_update();
                 None
             }
             Msg::LayoutTreeView => {
@@ -527,6 +624,7 @@ impl Model {
 
                 self.layout = TermusicLayout::TreeView;
                 self.playlist_switch_layout();
+                self.mini_focus_update();
                 None
             }
 
@@ -562,6 +660,18 @@ impl Model {
                 self.layout = TermusicLayout::Podcast;
                 self.podcast_sync_feeds_and_episodes();
                 self.playlist_switch_layout();
+                self.mini_focus_update();
+                None
+            }
+            Msg::LayoutCycle => {
+                self.layout_cycle();
+                None
+            }
+            Msg::LayoutMiniToggle => {
+                self.mini_toggle();
+                if let Err(e) = self.update_photo() {
+                    self.mount_error_popup(format!("update photo error: {e}"));
+                }
                 None
             }
             _ => None,
@@ -598,6 +708,14 @@ impl Model {
                     }
                 }
             }
+            DBMsg::AddQueue(index) => {
+                if let Some(track) = self.db_search_tracks.get(*index) {
+                    let file = track.file.clone();
+                    if let Err(e) = self.queue_add(&file) {
+                        self.mount_error_popup(format!("Add up next error: {e}"));
+                    }
+                }
+            }
             DBMsg::AddAllToPlaylist => {
                 let db_search_tracks = self.db_search_tracks.clone();
                 self.playlist_add_all_from_db(&db_search_tracks);
@@ -650,6 +768,10 @@ impl Model {
                     assert!(self.app.umount(&Id::YoutubeSearchInputPopup).is_ok());
                 }
                 if url.starts_with("http") {
+                    if self.youtube_options_browse(url) {
+                        self.mount_youtube_search_table();
+                        return;
+                    }
                     match self.youtube_dl(url) {
                         Ok(_) => {}
                         Err(e) => {
@@ -670,8 +792,10 @@ impl Model {
             YSMsg::TablePopupPrevious => {
                 self.youtube_options_prev_page();
             }
+            YSMsg::TablePopupStream(index) => self.youtube_options_stream(*index),
+            YSMsg::TablePopupBack => self.youtube_options_back(),
             YSMsg::TablePopupCloseOk(index) => {
-                if let Err(e) = self.youtube_options_download(*index) {
+                if let Err(e) = self.youtube_options_select(*index) {
                     self.library_reload_with_node_focus(None);
                     self.mount_error_popup(format!("Error downloading: {e}"));
                 }
@@ -679,6 +803,44 @@ impl Model {
         }
     }
 
+    fn update_command_palette(&mut self, msg: &CPMsg) -> Option<Msg> {
+        match msg {
+            CPMsg::PopupShow => self.mount_command_palette(),
+            CPMsg::PopupUpdate(input) => self.command_palette_update(input),
+            CPMsg::InputBlur => {
+                self.app.active(&Id::CommandPaletteTable).ok();
+            }
+            CPMsg::TableBlur => {
+                self.app.active(&Id::CommandPaletteInput).ok();
+            }
+            CPMsg::PopupCloseCancel => self.umount_command_palette(),
+            CPMsg::PopupCloseOk(input) => {
+                self.umount_command_palette();
+                return self.command_palette_run(input, None);
+            }
+            CPMsg::PopupCloseOkSelected => {
+                let input = match self.app.state(&Id::CommandPaletteInput) {
+                    Ok(State::One(StateValue::String(input))) => input,
+                    _ => String::new(),
+                };
+                let selected = self.command_palette_selected();
+                self.umount_command_palette();
+                if let Some(selected) = selected {
+                    return self.command_palette_run(&input, Some(&selected));
+                }
+            }
+        }
+        None
+    }
+
+    fn umount_command_palette(&mut self) {
+        self.app.umount(&Id::CommandPaletteInput).ok();
+        self.app.umount(&Id::CommandPaletteTable).ok();
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("update photo error: {e}"));
+        }
+    }
+
     fn update_general_search(&mut self, msg: &GSMsg) {
         match msg {
             GSMsg::PopupShowDatabase => {
@@ -785,6 +947,7 @@ impl Model {
         }
         None
     }
+    #[allow(clippy::too_many_lines)]
     fn update_playlist(&mut self, msg: &PLMsg) {
         match msg {
             PLMsg::Add(current_node) => {
@@ -809,6 +972,51 @@ impl Model {
                 self.config.loop_mode = self.player.playlist.cycle_loop_mode();
                 self.playlist_sync();
             }
+            PLMsg::ShuffleModeCycle => {
+                self.config.shuffle = self.player.playlist.cycle_shuffle();
+                self.playlist_sync();
+            }
+            PLMsg::RadioToggle => {
+                self.config.radio = !self.config.radio;
+                self.playlist_update_title();
+                self.playlist_radio_fill();
+            }
+            PLMsg::ListNext | PLMsg::ListPrevious => {
+                let offset = if *msg == PLMsg::ListNext { 1 } else { -1 };
+                if let Err(e) = self.playlist_switch_by(offset) {
+                    self.mount_error_popup(format!("Switch playlist error: {e}"));
+                }
+            }
+            PLMsg::ListSwitch(name) => {
+                if let Err(e) = self.playlist_switch(name) {
+                    self.mount_error_popup(format!("Switch playlist error: {e}"));
+                }
+            }
+            PLMsg::ListNew(name) => {
+                if let Err(e) = self.playlist_new(name) {
+                    self.mount_error_popup(format!("New playlist error: {e}"));
+                }
+            }
+            PLMsg::ListRename(name) => {
+                if let Err(e) = self.playlist_rename(name) {
+                    self.mount_error_popup(format!("Rename playlist error: {e}"));
+                }
+            }
+            PLMsg::ListDuplicate(name) => {
+                if let Err(e) = self.playlist_duplicate(name) {
+                    self.mount_error_popup(format!("Duplicate playlist error: {e}"));
+                }
+            }
+            PLMsg::ListDelete => {
+                if let Err(e) = self.playlist_delete() {
+                    self.mount_error_popup(format!("Delete playlist error: {e}"));
+                }
+            }
+            PLMsg::ListAddTo(name) => {
+                if let Err(e) = self.playlist_add_to(name) {
+                    self.mount_error_popup(format!("Add to playlist error: {e}"));
+                }
+            }
             PLMsg::AddFront => {
                 self.config.add_playlist_front = self.player.playlist.toggle_add_front();
                 self.playlist_update_title();
@@ -841,7 +1049,25 @@ impl Model {
             PLMsg::CmusTQueue => {
                 self.playlist_add_cmus_tqueue();
             }
-            PLMsg::PlaylistTableBlurUp => match self.layout {
+            PLMsg::Sort => self.playlist_sort(),
+            PLMsg::QueueAdd(current_node) => {
+                if let Err(e) = self.queue_add(current_node) {
+                    self.mount_error_popup(format!("Add up next error: {e}"));
+                }
+            }
+            PLMsg::QueueDelete(index) => {
+                self.player.playlist.queue_remove(*index);
+                self.playlist_sync();
+            }
+            PLMsg::QueueClear => {
+                self.player.playlist.queue_clear();
+                self.playlist_sync();
+            }
+            PLMsg::PlaylistTableBlurUp if !self.player.playlist.queue().is_empty() => {
+                assert!(self.app.active(&Id::UpNext).is_ok());
+            }
+            PLMsg::UpNextBlurDown => assert!(self.app.active(&Id::Playlist).is_ok()),
+            PLMsg::PlaylistTableBlurUp | PLMsg::UpNextBlurUp => match self.layout {
                 TermusicLayout::TreeView => assert!(self.app.active(&Id::Library).is_ok()),
                 TermusicLayout::DataBase => {
                     assert!(self.app.active(&Id::DBListSearchTracks).is_ok());
@@ -905,11 +1131,19 @@ impl Model {
                     }
                 }
                 PlayerMsg::CurrentTrackUpdated => {
+                    self.player.outro_skipped = false;
                     self.update_layout_for_current_track();
                     self.player_update_current_track_after();
                     self.lyric_update_for_podcast_by_current_track();
-                    if (self.config.speed - 10).abs() >= 1 {
-                        self.player.set_speed(self.config.speed);
+                    self.podcast_fetch_chapters();
+                    self.podcast_fetch_transcript();
+                    self.lyric_load_sidecar();
+                    self.waveform_load();
+                    self.player_count_play();
+                    self.playlist_radio_fill();
+                    let speed = self.player.track_speed();
+                    if self.player.speed() != speed {
+                        self.player.set_speed(speed);
                     }
 
                     if let Err(e) = self.podcast_mark_current_track_played() {
@@ -917,7 +1151,9 @@ impl Model {
                     }
                 }
                 PlayerMsg::Progress(time_pos, duration) => {
-                    self.progress_update(time_pos, duration);
+                    if !self.player.skip_outro(time_pos, duration) {
+                        self.progress_update(time_pos, duration);
+                    }
                 }
                 #[cfg(not(any(feature = "mpv", feature = "gst")))]
                 PlayerMsg::Duration(duration) => {
@@ -1059,8 +1295,13 @@ impl Model {
             DLMsg::YoutubeSearchFail(e) => {
                 self.mount_error_popup(format!("Youtube search fail: {e}"));
             }
+            DLMsg::YoutubeStreamReady(stream) => self.youtube_options_queue_stream(stream),
+            DLMsg::YoutubeStreamFail(e) => {
+                self.mount_error_popup(format!("Youtube stream fail: {e}"));
+            }
             DLMsg::FetchPhotoSuccess(image_wrapper) => {
-                self.show_image(&image_wrapper.data).ok();
+                self.show_image(&image_wrapper.data, &image_wrapper.path)
+                    .ok();
             }
             DLMsg::FetchPhotoErr(err_text) => {
                 self.show_message_timeout_label_help(err_text, None, None, None);
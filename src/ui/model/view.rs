@@ -21,42 +21,60 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use crate::config::Settings;
+use crate::config::{default_layouts, LayoutNode, Pane, Settings};
+use crate::player::{PlayerTrait, Status};
+use crate::track::Track;
 use crate::ui::components::{
-    DBListCriteria, DBListSearchResult, DBListSearchTracks, DownloadSpinner, EpisodeList,
-    ErrorPopup, FeedsList, GSInputPopup, GSTablePopup, GlobalListener, HelpPopup, LabelSpan, Lyric,
-    MessagePopup, MusicLibrary, Playlist, PodcastAddPopup, Progress, QuitPopup,
-    SavePlaylistConfirm, SavePlaylistPopup, Source, YSInputPopup, YSTablePopup,
+    CPInputPopup, CPTablePopup, DBListCriteria, DBListSearchResult, DBListSearchTracks,
+    DownloadSpinner, EpisodeList, ErrorPopup, FeedSettingsPopup, FeedsList, GSInputPopup,
+    GSTablePopup, GlobalListener, HelpPopup, LabelSpan, Lyric, MessagePopup, MusicLibrary,
+    Playlist, PlaylistTabs, PodcastAddPopup, Progress, QuitPopup, SavePlaylistConfirm,
+    SavePlaylistPopup, Source, TranscriptSearchPopup, UpNext, YSInputPopup, YSTablePopup,
 };
 use crate::utils::{
     draw_area_in_absolute, draw_area_in_relative, draw_area_top_right_absolute, get_parent_folder,
 };
 
+use crate::ui::model::mouse::InputListener;
 use crate::ui::model::{ConfigEditorLayout, Model, TermusicLayout};
 use crate::{
     ui::{Application, DBMsg, Id, IdConfigEditor, IdTagEditor, Msg, PCMsg},
     VERSION,
 };
 use anyhow::{bail, Result};
+use crossterm::event::MouseEvent;
+use std::borrow::Cow;
+use std::sync::mpsc::Sender;
 use std::time::{Duration, Instant};
 use tui_realm_treeview::Tree;
 use tuirealm::event::NoUserEvent;
 use tuirealm::props::{AttrValue, Attribute, Color, PropPayload, PropValue, TextSpan};
-use tuirealm::tui::layout::{Constraint, Direction, Layout};
-use tuirealm::tui::widgets::Clear;
+use tuirealm::tui::layout::{Constraint, Direction, Layout, Rect};
+use tuirealm::tui::style::Style;
+use tuirealm::tui::symbols;
+use tuirealm::tui::widgets::{Clear, LineGauge, Paragraph};
 use tuirealm::EventListenerCfg;
 use tuirealm::{Frame, State, StateValue};
 
 impl Model {
-    pub fn init_app(tree: &Tree, config: &Settings) -> Application<Id, Msg, NoUserEvent> {
+    #[allow(clippy::too_many_lines)]
+    pub fn init_app(
+        tree: &Tree,
+        config: &Settings,
+        mouse_tx: Sender<MouseEvent>,
+    ) -> Application<Id, Msg, NoUserEvent> {
         // Setup application
         // NOTE: NoUserEvent is a shorthand to tell tui-realm we're not going to use any custom user event
-        // NOTE: the event listener is configured to use the default crossterm input listener and to raise a Tick event each second
-        // which we will use to update the clock
+        // NOTE: the event listener is configured to use our crossterm input listener, which passes mouse events
+        // to the model, and to raise a Tick event each second which we will use to update the clock
 
+        let input_interval = Duration::from_millis(20);
         let mut app: Application<Id, Msg, NoUserEvent> = Application::init(
             EventListenerCfg::default()
-                .default_input_listener(Duration::from_millis(20))
+                .port(
+                    Box::new(InputListener::new(input_interval, mouse_tx)),
+                    input_interval,
+                )
                 .poll_timeout(Duration::from_millis(10))
                 .tick_interval(Duration::from_secs(1)),
         );
@@ -104,6 +122,16 @@ impl Model {
         assert!(app
             .mount(Id::Playlist, Box::new(Playlist::new(config)), vec![])
             .is_ok());
+        assert!(app
+            .mount(Id::UpNext, Box::new(UpNext::new(config)), vec![])
+            .is_ok());
+        assert!(app
+            .mount(
+                Id::PlaylistTabs,
+                Box::new(PlaylistTabs::new(config)),
+                vec![]
+            )
+            .is_ok());
         assert!(app
             .mount(Id::Progress, Box::new(Progress::new(config)), vec![])
             .is_ok());
@@ -175,60 +203,69 @@ impl Model {
                 return;
             }
 
-            match self.layout {
-                TermusicLayout::TreeView => self.view_layout_treeview(),
-                TermusicLayout::DataBase => self.view_layout_database(),
-                TermusicLayout::Podcast => self.view_layout_podcast(),
+            if self.mini {
+                self.view_layout_mini();
+            } else {
+                self.view_layout();
             }
         }
     }
 
-    pub fn view_layout_podcast(&mut self) {
-        assert!(self
-            .terminal
-            .raw_mut()
-            .draw(|f| {
-                let chunks_main = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(0)
-                    .constraints(
-                        [
-                            Constraint::Min(2),
-                            Constraint::Length(3),
-                            Constraint::Length(1),
-                        ]
-                        .as_ref(),
-                    )
-                    .split(f.size());
-                let chunks_center = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .margin(0)
-                    .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
-                    .split(chunks_main[0]);
-
-                let chunks_left = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(0)
-                    .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
-                    .split(chunks_center[0]);
-                let chunks_right = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(0)
-                    .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
-                    .split(chunks_center[1]);
+    /// The layout of the current view, or its built-in layout when the
+    /// configured name is unknown. Borrowed, as the visualizer checks it on
+    /// every tick.
+    pub fn layout_current(&self) -> Cow<'_, LayoutNode> {
+        let (name, fallback) = match self.layout {
+            TermusicLayout::TreeView => (&self.config.layout_treeview, "default"),
+            TermusicLayout::DataBase => (&self.config.layout_database, "default"),
+            TermusicLayout::Podcast => (&self.config.layout_podcast, "podcast"),
+        };
+        self.config.layouts.get(name).map_or_else(
+            || {
+                Cow::Owned(
+                    default_layouts()
+                        .remove(fallback)
+                        .expect("built-in layout is missing"),
+                )
+            },
+            Cow::Borrowed,
+        )
+    }
 
-                self.app.view(&Id::Podcast, f, chunks_left[0]);
-                self.app.view(&Id::Episode, f, chunks_left[1]);
-                self.app.view(&Id::Playlist, f, chunks_right[0]);
-                self.app.view(&Id::Lyric, f, chunks_right[1]);
-                self.app.view(&Id::Progress, f, chunks_main[1]);
-                self.app.view(&Id::Label, f, chunks_main[2]);
+    /// Whether the visualizer is on screen and moving, so it needs frequent
+    /// redraws.
+    pub fn visualizer_active(&self) -> bool {
+        if self.mini || self.player.playlist.status() != Status::Running {
+            return false;
+        }
+        let layout = self.layout_current();
+        layout.has_pane(Pane::Spectrum) || layout.has_pane(Pane::Oscilloscope)
+    }
 
-                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
-            })
-            .is_ok());
+    /// Switches the current view to the next named layout.
+    pub fn layout_cycle(&mut self) {
+        let name = match self.layout {
+            TermusicLayout::TreeView => &mut self.config.layout_treeview,
+            TermusicLayout::DataBase => &mut self.config.layout_database,
+            TermusicLayout::Podcast => &mut self.config.layout_podcast,
+        };
+        let mut names = self.config.layouts.keys();
+        let next = names
+            .clone()
+            .skip_while(|n| *n != name)
+            .nth(1)
+            .or_else(|| names.next());
+        let Some(next) = next else {
+            return;
+        };
+        *name = next.clone();
+        self.show_message_timeout_label_help(format!("Layout: {next}"), None, None, None);
     }
-    pub fn view_layout_database(&mut self) {
+
+    pub fn view_layout(&mut self) {
+        let layout = self.layout_current().into_owned();
+        let mut album_art_area = None;
+        let mut mouse_areas = Vec::new();
         assert!(self
             .terminal
             .raw_mut()
@@ -238,91 +275,242 @@ impl Model {
                     .margin(0)
                     .constraints([Constraint::Min(2), Constraint::Length(1)].as_ref())
                     .split(f.size());
-                let chunks_left = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .margin(0)
-                    .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
-                    .split(chunks_main[0]);
 
-                let chunks_left_sections = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(0)
-                    .constraints(
-                        [
-                            Constraint::Length(10),
-                            Constraint::Length(10),
-                            Constraint::Min(2),
-                        ]
-                        .as_ref(),
-                    )
-                    .split(chunks_left[0]);
-                let chunks_right = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(0)
-                    .constraints(
-                        [
-                            Constraint::Min(2),
-                            Constraint::Length(3),
-                            Constraint::Length(4),
-                        ]
-                        .as_ref(),
-                    )
-                    .split(chunks_left[1]);
-
-                self.app
-                    .view(&Id::DBListCriteria, f, chunks_left_sections[0]);
-                self.app
-                    .view(&Id::DBListSearchResult, f, chunks_left_sections[1]);
-                self.app
-                    .view(&Id::DBListSearchTracks, f, chunks_left_sections[2]);
-
-                self.app.view(&Id::Playlist, f, chunks_right[0]);
-                self.app.view(&Id::Progress, f, chunks_right[1]);
-                self.app.view(&Id::Lyric, f, chunks_right[2]);
+                for (pane, area) in layout.areas(chunks_main[0]) {
+                    let ids_areas = match pane {
+                        Pane::AlbumArt => {
+                            album_art_area = Some(area);
+                            Vec::new()
+                        }
+                        Pane::Main => match self.layout {
+                            TermusicLayout::TreeView => vec![(Id::Library, area)],
+                            TermusicLayout::DataBase => Self::database_areas(area),
+                            TermusicLayout::Podcast => {
+                                let chunks = Layout::default()
+                                    .direction(Direction::Vertical)
+                                    .margin(0)
+                                    .constraints(
+                                        [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref(),
+                                    )
+                                    .split(area);
+                                vec![(Id::Podcast, chunks[0]), (Id::Episode, chunks[1])]
+                            }
+                        },
+                        Pane::Library => vec![(Id::Library, area)],
+                        Pane::Database => Self::database_areas(area),
+                        Pane::Podcast => vec![(Id::Podcast, area)],
+                        Pane::Episode => vec![(Id::Episode, area)],
+                        Pane::Playlist => Self::playlist_areas(
+                            area,
+                            self.player.playlist.queue().len(),
+                            self.playlist_names.len() > 1,
+                        ),
+                        Pane::Lyric => vec![(Id::Lyric, area)],
+                        Pane::Progress => vec![(Id::Progress, area)],
+                        Pane::Spectrum => vec![(Id::Spectrum, area)],
+                        Pane::Oscilloscope => vec![(Id::Oscilloscope, area)],
+                    };
+                    for (id, area) in ids_areas {
+                        self.app.view(&id, f, area);
+                        mouse_areas.push((id, area));
+                    }
+                }
+
+                self.album_art.render(f);
                 Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
             })
             .is_ok());
+        self.mouse.areas = mouse_areas;
+        // printed after the draw, which would otherwise paint over it
+        self.album_art
+            .print_sixel(self.terminal.raw_mut().backend_mut())
+            .ok();
+
+        // the album art follows its pane when the layout or terminal size changes
+        if album_art_area != self.album_art_area {
+            self.album_art_area = album_art_area;
+            if let Err(e) = self.update_photo() {
+                self.mount_error_popup(format!("update photo error: {e}"));
+            }
+        }
     }
 
-    pub fn view_layout_treeview(&mut self) {
+    /// The mini player: as many of the track, progress and state lines as
+    /// the terminal has rows for. A single line holds all three.
+    pub fn view_layout_mini(&mut self) {
+        self.mouse.areas.clear();
+        let track_line = self.mini_track_line();
+        let state_line = self.mini_state_line();
+        let duration = self
+            .player
+            .playlist
+            .current_track()
+            .map(Track::duration)
+            .unwrap_or_default();
+        let time_pos = Duration::from_secs(self.time_pos.try_into().unwrap_or(0));
+        let time_line = format!(
+            "{} / {}",
+            Track::duration_formatted_short(&time_pos),
+            Track::duration_formatted_short(&duration)
+        );
+        let ratio = if duration.is_zero() {
+            0.0
+        } else {
+            (time_pos.as_secs_f64() / duration.as_secs_f64()).clamp(0.0, 1.0)
+        };
+        let style_color_symbol = &self.config.style_color_symbol;
+        let text_style =
+            Style::default().fg(style_color_symbol.lyric_foreground().unwrap_or(Color::Cyan));
+        let gauge_style = Style::default().fg(style_color_symbol
+            .progress_foreground()
+            .unwrap_or(Color::Yellow));
+
         assert!(self
             .terminal
             .raw_mut()
             .draw(|f| {
-                let chunks_main = Layout::default()
+                let rows = f.size().height.min(3);
+                let chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .margin(0)
-                    .constraints([Constraint::Min(2), Constraint::Length(1)].as_ref())
+                    .constraints(vec![Constraint::Length(1); rows.into()])
                     .split(f.size());
-                let chunks_left = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .margin(0)
-                    .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
-                    .split(chunks_main[0]);
-                let chunks_right = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(0)
-                    .constraints(
-                        [
-                            Constraint::Min(2),
-                            Constraint::Length(3),
-                            Constraint::Length(4),
-                        ]
-                        .as_ref(),
-                    )
-                    .split(chunks_left[1]);
-
-                self.app.view(&Id::Library, f, chunks_left[0]);
-                self.app.view(&Id::Playlist, f, chunks_right[0]);
-                self.app.view(&Id::Progress, f, chunks_right[1]);
-                self.app.view(&Id::Lyric, f, chunks_right[2]);
-                self.app.view(&Id::Label, f, chunks_main[1]);
+                match chunks.len() {
+                    0 => {}
+                    1 => f.render_widget(
+                        Paragraph::new(format!("{track_line}  {time_line}  {state_line}"))
+                            .style(text_style),
+                        chunks[0],
+                    ),
+                    len => {
+                        f.render_widget(Paragraph::new(track_line).style(text_style), chunks[0]);
+                        f.render_widget(
+                            LineGauge::default()
+                                .gauge_style(gauge_style)
+                                .line_set(symbols::line::THICK)
+                                .label(time_line)
+                                .ratio(ratio),
+                            chunks[1],
+                        );
+                        if len > 2 {
+                            f.render_widget(
+                                Paragraph::new(state_line).style(text_style),
+                                chunks[2],
+                            );
+                        }
+                    }
+                }
 
-                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
+                Self::view_popups(f, &mut self.app);
             })
             .is_ok());
     }
 
+    /// Switches between the mini player and the layout. No view is on screen
+    /// in the mini player, so the global listener takes the keys there and
+    /// the view focused before gets them back after.
+    pub fn mini_toggle(&mut self) {
+        self.mini = !self.mini;
+        self.mini_focus_update();
+    }
+
+    /// Gives the keys to the global listener in the mini player, keeping the
+    /// view that had them, or hands them back to that view.
+    pub fn mini_focus_update(&mut self) {
+        if self.mini {
+            if let Some(focus) = self.app.focus().filter(|id| **id != Id::GlobalListener) {
+                self.mini_focus = Some(focus.clone());
+                self.app.active(&Id::GlobalListener).ok();
+            }
+        } else if let Some(id) = self.mini_focus.take() {
+            self.app.active(&id).ok();
+        }
+    }
+
+    fn mini_track_line(&self) -> String {
+        let status = self.player.playlist.status();
+        let status = if self.config.playlist_display_symbol {
+            match status {
+                Status::Running => "▶".to_string(),
+                Status::Paused => "⏸".to_string(),
+                Status::Stopped => "⏹".to_string(),
+            }
+        } else {
+            status.to_string()
+        };
+        match self.player.playlist.current_track() {
+            Some(track) => format!(
+                "{status} {} - {}",
+                track.title().unwrap_or("Unknown title"),
+                track.artist().unwrap_or("Unknown artist")
+            ),
+            None => status,
+        }
+    }
+
+    fn mini_state_line(&self) -> String {
+        let gapless = if self.config.gapless { "on" } else { "off" };
+        format!(
+            "{} | Gapless: {gapless} | Speed: {:.1} | Volume: {}",
+            self.config
+                .loop_mode
+                .display(self.config.playlist_display_symbol),
+            f64::from(self.player.speed()) / 10.0,
+            self.player.volume(),
+        )
+    }
+
+    fn database_areas(area: Rect) -> Vec<(Id, Rect)> {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(0)
+            .constraints(
+                [
+                    Constraint::Length(10),
+                    Constraint::Length(10),
+                    Constraint::Min(2),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+        vec![
+            (Id::DBListCriteria, chunks[0]),
+            (Id::DBListSearchResult, chunks[1]),
+            (Id::DBListSearchTracks, chunks[2]),
+        ]
+    }
+
+    /// The playlist, with the tabs of the named playlists and up next above
+    /// it while there is more than one playlist and anything queued.
+    fn playlist_areas(area: Rect, queued: usize, tabs: bool) -> Vec<(Id, Rect)> {
+        let tabs_rows = u16::from(tabs);
+        let queue_rows = match queued {
+            0 => 0,
+            queued => u16::try_from(queued.min(5)).unwrap_or(5) + 2,
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(0)
+            .constraints(
+                [
+                    Constraint::Length(tabs_rows),
+                    Constraint::Length(queue_rows),
+                    Constraint::Min(3),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+        let mut areas = Vec::new();
+        if tabs {
+            areas.push((Id::PlaylistTabs, chunks[0]));
+        }
+        if queued > 0 {
+            areas.push((Id::UpNext, chunks[1]));
+        }
+        areas.push((Id::Playlist, chunks[2]));
+        areas
+    }
+
     #[allow(clippy::too_many_lines)]
     fn view_layout_commons(
         f: &mut Frame<'_>,
@@ -360,7 +548,11 @@ impl Model {
             app.view(&Id::Label, f, chunks_main[1]);
         }
 
-        // -- popups
+        Self::view_popups(f, app);
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn view_popups(f: &mut Frame<'_>, app: &mut Application<Id, Msg, NoUserEvent>) {
         if app.mounted(&Id::QuitPopup) {
             let popup = draw_area_in_absolute(f.size(), 30, 3);
             f.render_widget(Clear, popup);
@@ -400,6 +592,15 @@ impl Model {
                 .split(popup);
             app.view(&Id::GeneralSearchInput, f, popup_chunks[0]);
             app.view(&Id::GeneralSearchTable, f, popup_chunks[1]);
+        } else if app.mounted(&Id::CommandPaletteInput) {
+            let popup = draw_area_in_relative(f.size(), 65, 68);
+            f.render_widget(Clear, popup);
+            let popup_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(2)].as_ref())
+                .split(popup);
+            app.view(&Id::CommandPaletteInput, f, popup_chunks[0]);
+            app.view(&Id::CommandPaletteTable, f, popup_chunks[1]);
         } else if app.mounted(&Id::YoutubeSearchInputPopup) {
             let popup = draw_area_in_absolute(f.size(), 50, 3);
             f.render_widget(Clear, popup);
@@ -429,6 +630,14 @@ impl Model {
             let popup = draw_area_in_absolute(f.size(), 65, 3);
             f.render_widget(Clear, popup);
             app.view(&Id::PodcastAddPopup, f, popup);
+        } else if app.mounted(&Id::FeedSettingsPopup) {
+            let popup = draw_area_in_absolute(f.size(), 65, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::FeedSettingsPopup, f, popup);
+        } else if app.mounted(&Id::TranscriptSearchPopup) {
+            let popup = draw_area_in_absolute(f.size(), 65, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::TranscriptSearchPopup, f, popup);
         }
         if app.mounted(&Id::MessagePopup) {
             let popup = draw_area_top_right_absolute(f.size(), 25, 4);
@@ -475,6 +684,31 @@ impl Model {
         assert!(self.app.active(&Id::HelpPopup).is_ok());
     }
 
+    pub fn mount_command_palette(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::CommandPaletteInput,
+                Box::new(CPInputPopup::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::CommandPaletteTable,
+                Box::new(CPTablePopup::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        self.command_palette_update("");
+
+        assert!(self.app.active(&Id::CommandPaletteInput).is_ok());
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("update photo error: {e}"));
+        }
+    }
+
     pub fn mount_search_library(&mut self) {
         assert!(self
             .app
@@ -610,84 +844,84 @@ impl Model {
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_foreground()
+                                .footer_foreground()
                                 .unwrap_or(Color::Blue))
                             .bold(),
                         TextSpan::new(VERSION)
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .footer_highlight()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
                         TextSpan::new(" Help: ")
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_foreground()
+                                .footer_foreground()
                                 .unwrap_or(Color::Blue))
                             .bold(),
                         TextSpan::new(format!("<{}>", self.config.keys.global_help))
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .footer_highlight()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
                         TextSpan::new(" Config: ")
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_foreground()
+                                .footer_foreground()
                                 .unwrap_or(Color::Blue))
                             .bold(),
                         TextSpan::new(format!("<{}>", self.config.keys.global_config_open))
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .footer_highlight()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
                         TextSpan::new(" Library: ")
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_foreground()
+                                .footer_foreground()
                                 .unwrap_or(Color::Blue))
                             .bold(),
                         TextSpan::new(format!("<{}>", self.config.keys.global_layout_treeview))
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .footer_highlight()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
                         TextSpan::new(" Database: ")
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_foreground()
+                                .footer_foreground()
                                 .unwrap_or(Color::Blue))
                             .bold(),
                         TextSpan::new(format!("<{}>", self.config.keys.global_layout_database))
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .footer_highlight()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
                         TextSpan::new(" Podcasts: ")
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_foreground()
+                                .footer_foreground()
                                 .unwrap_or(Color::Blue))
                             .bold(),
                         TextSpan::new(format!("<{}>", self.config.keys.global_layout_podcast))
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .footer_highlight()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
                     ]
@@ -752,14 +986,14 @@ impl Model {
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_highlight()
+                                .popup_highlight()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
                         TextSpan::new(path_string)
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_foreground()
+                                .popup_foreground()
                                 .unwrap_or(Color::Red))
                             .bold(),
                         TextSpan::new(filename).fg(Color::Cyan).bold(),
@@ -767,7 +1001,7 @@ impl Model {
                             .fg(self
                                 .config
                                 .style_color_symbol
-                                .library_foreground()
+                                .popup_foreground()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
                     ]
@@ -815,6 +1049,50 @@ impl Model {
         }
     }
 
+    pub fn mount_feed_settings_popup(&mut self) {
+        let Some(playback) = self.podcast_playback_selected() else {
+            return;
+        };
+        assert!(self
+            .app
+            .remount(
+                Id::FeedSettingsPopup,
+                Box::new(FeedSettingsPopup::new(
+                    &self.config.style_color_symbol,
+                    &playback.to_string()
+                )),
+                vec![]
+            )
+            .is_ok());
+
+        assert!(self.app.active(&Id::FeedSettingsPopup).is_ok());
+    }
+
+    pub fn umount_feed_settings_popup(&mut self) {
+        if self.app.mounted(&Id::FeedSettingsPopup) {
+            assert!(self.app.umount(&Id::FeedSettingsPopup).is_ok());
+        }
+    }
+
+    pub fn mount_transcript_search_popup(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::TranscriptSearchPopup,
+                Box::new(TranscriptSearchPopup::new(&self.config.style_color_symbol)),
+                vec![]
+            )
+            .is_ok());
+
+        assert!(self.app.active(&Id::TranscriptSearchPopup).is_ok());
+    }
+
+    pub fn umount_transcript_search_popup(&mut self) {
+        if self.app.mounted(&Id::TranscriptSearchPopup) {
+            assert!(self.app.umount(&Id::TranscriptSearchPopup).is_ok());
+        }
+    }
+
     pub fn show_message_timeout_label_help<S: AsRef<str>>(
         &mut self,
         active_msg: S,
@@ -826,14 +1104,14 @@ impl Model {
             .fg(foreground.unwrap_or_else(|| {
                 self.config
                     .style_color_symbol
-                    .library_highlight()
+                    .footer_highlight()
                     .unwrap_or(Color::Cyan)
             }))
             .bold()
             .bg(background.unwrap_or_else(|| {
                 self.config
                     .style_color_symbol
-                    .library_background()
+                    .footer_background()
                     .unwrap_or(Color::Reset)
             }))];
         self.app
@@ -22,7 +22,7 @@
  * SOFTWARE.
  */
 use super::Model;
-use crate::invidious::{Instance, YoutubeVideo};
+use crate::invidious::{AudioStream, Instance, YoutubeItem, YoutubeVideo};
 use crate::track::Track;
 use crate::ui::{DLMsg, Id, Msg};
 use crate::utils::get_parent_folder;
@@ -46,11 +46,25 @@ lazy_static! {
         Regex::new(r"\[ExtractAudio\] Destination: (?P<name>.*)\.mp3").unwrap();
 }
 
+/// What the table of results lists.
+#[derive(Clone, PartialEq, Eq)]
+enum Listing {
+    Search,
+    Playlist(String),
+    /// The channel, and the continuations for the pages after the first
+    Channel(String, Vec<String>),
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct YoutubeOptions {
-    items: Vec<YoutubeVideo>,
+    items: Vec<YoutubeItem>,
     page: u32,
     invidious_instance: Instance,
+    listing: Listing,
+    /// Name of the playlist or channel browsed
+    title: String,
+    /// The results the playlist or channel was opened from
+    parent: Option<Box<YoutubeOptions>>,
 }
 
 impl YoutubeOptions {
@@ -59,9 +73,12 @@ impl YoutubeOptions {
             items: Vec::new(),
             page: 1,
             invidious_instance: crate::invidious::Instance::default(),
+            listing: Listing::Search,
+            title: String::new(),
+            parent: None,
         }
     }
-    pub fn get_by_index(&self, index: usize) -> Result<&YoutubeVideo> {
+    pub fn get_by_index(&self, index: usize) -> Result<&YoutubeItem> {
         if let Some(item) = self.items.get(index) {
             return Ok(item);
         }
@@ -70,27 +87,127 @@ impl YoutubeOptions {
 
     pub fn prev_page(&mut self) -> Result<()> {
         if self.page > 1 {
-            self.page -= 1;
-            self.items = self.invidious_instance.get_search_query(self.page)?;
+            self.load_page(self.page - 1)?;
         }
         Ok(())
     }
 
     pub fn next_page(&mut self) -> Result<()> {
-        self.page += 1;
-        self.items = self.invidious_instance.get_search_query(self.page)?;
-        Ok(())
+        self.load_page(self.page + 1)
     }
 
     pub const fn page(&self) -> u32 {
         self.page
     }
+
+    fn load_page(&mut self, page: u32) -> Result<()> {
+        let videos =
+            |videos: Vec<YoutubeVideo>| videos.into_iter().map(YoutubeItem::Video).collect();
+        self.items = match &mut self.listing {
+            Listing::Search => self.invidious_instance.get_search_query(page)?,
+            Listing::Playlist(playlist_id) => {
+                let (title, items) = self.invidious_instance.get_playlist(playlist_id, page)?;
+                self.title = title;
+                videos(items)
+            }
+            Listing::Channel(author_id, continuations) => {
+                let index = page as usize - 1;
+                // channels only go on from the page before
+                let continuation = match index.checked_sub(1) {
+                    None => None,
+                    Some(i) => match continuations.get(i) {
+                        Some(continuation) => Some(continuation.as_str()),
+                        None => return Ok(()),
+                    },
+                };
+                let (items, next) = self
+                    .invidious_instance
+                    .get_channel_videos(author_id, continuation)?;
+                continuations.truncate(index);
+                continuations.extend(next);
+                videos(items)
+            }
+        };
+        self.page = page;
+        Ok(())
+    }
+
+    /// The videos of the playlist or channel at `index`, going back to these
+    /// results.
+    pub fn open(&self, index: usize) -> Result<Self> {
+        let (listing, title) = match self.get_by_index(index)? {
+            YoutubeItem::Playlist {
+                title, playlist_id, ..
+            } => (Listing::Playlist(playlist_id.clone()), title.clone()),
+            YoutubeItem::Channel {
+                author, author_id, ..
+            } => (
+                Listing::Channel(author_id.clone(), Vec::new()),
+                author.clone(),
+            ),
+            YoutubeItem::Video(_) => bail!("not a playlist or channel"),
+        };
+        let mut options = Self {
+            items: Vec::new(),
+            page: 1,
+            invidious_instance: self.invidious_instance.clone(),
+            listing,
+            title,
+            parent: Some(Box::new(self.clone())),
+        };
+        options.load_page(1)?;
+        Ok(options)
+    }
+
+    /// The videos of a playlist or channel, on the first instance that has it.
+    fn browse(listing: &Listing, preferred: &[String]) -> Result<Self> {
+        let title = match listing {
+            Listing::Channel(id, _) | Listing::Playlist(id) => id.clone(),
+            Listing::Search => String::new(),
+        };
+        let (_, options) = Instance::connect(None, preferred, |instance| {
+            let mut options = Self {
+                items: Vec::new(),
+                page: 1,
+                invidious_instance: instance.clone(),
+                listing: listing.clone(),
+                title: title.clone(),
+                parent: None,
+            };
+            options.load_page(1)?;
+            Ok(options)
+        })?;
+        Ok(options)
+    }
+}
+
+impl Listing {
+    /// The playlist or channel a youtube or invidious url points to.
+    fn from_url(url: &str) -> Option<Self> {
+        if let Some(id) = url_param(url, "/channel/") {
+            return Some(Self::Channel(id, Vec::new()));
+        }
+        if url.contains("/playlist?") {
+            return url_param(url, "list=").map(Self::Playlist);
+        }
+        None
+    }
+}
+
+/// The id following `key` in `url`, up to the next `/`, `&`, `?` or `#`.
+fn url_param(url: &str, key: &str) -> Option<String> {
+    let (_, rest) = url.split_once(key)?;
+    let id: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    (!id.is_empty()).then_some(id)
 }
 
 impl Model {
     pub fn youtube_options_download(&mut self, index: usize) -> Result<()> {
         // download from search result here
-        if let Ok(item) = self.youtube_options.get_by_index(index) {
+        if let Ok(YoutubeItem::Video(item)) = self.youtube_options.get_by_index(index) {
             let url = format!("https://www.youtube.com/watch?v={}", item.video_id);
             if let Err(e) = self.youtube_dl(url.as_ref()) {
                 bail!("Error download: {e}");
@@ -99,16 +216,31 @@ impl Model {
         Ok(())
     }
 
+    /// The instances from the config, then the one that answered last.
+    fn youtube_options_instances(&self) -> Vec<String> {
+        let mut instances = self.config.invidious_instances.clone();
+        if let Some(domain) = &self.youtube_options.invidious_instance.domain {
+            if !domain.is_empty() && !instances.contains(domain) {
+                instances.push(domain.clone());
+            }
+        }
+        instances
+    }
+
     pub fn youtube_options_search(&mut self, keyword: &str) {
         let search_word = keyword.to_string();
+        let preferred = self.youtube_options_instances();
         let tx = self.tx_to_main.clone();
         thread::spawn(
-            move || match crate::invidious::Instance::new(&search_word) {
+            move || match crate::invidious::Instance::new(&search_word, &preferred) {
                 Ok((instance, result)) => {
                     let youtube_options = YoutubeOptions {
                         items: result,
                         page: 1,
                         invidious_instance: instance,
+                        listing: Listing::Search,
+                        title: String::new(),
+                        parent: None,
                     };
                     tx.send(Msg::Download(DLMsg::YoutubeSearchSuccess(youtube_options)))
                         .ok();
@@ -121,6 +253,90 @@ impl Model {
         );
     }
 
+    /// Lists the videos of a youtube or invidious playlist or channel url,
+    /// false for other urls.
+    pub fn youtube_options_browse(&mut self, url: &str) -> bool {
+        let Some(listing) = Listing::from_url(url) else {
+            return false;
+        };
+        let preferred = self.youtube_options_instances();
+        let tx = self.tx_to_main.clone();
+        thread::spawn(move || match YoutubeOptions::browse(&listing, &preferred) {
+            Ok(youtube_options) => {
+                tx.send(Msg::Download(DLMsg::YoutubeSearchSuccess(youtube_options)))
+                    .ok();
+            }
+            Err(e) => {
+                tx.send(Msg::Download(DLMsg::YoutubeSearchFail(e.to_string())))
+                    .ok();
+            }
+        });
+        true
+    }
+
+    /// Downloads the video at `index`, or lists the videos of the playlist or
+    /// channel there.
+    pub fn youtube_options_select(&mut self, index: usize) -> Result<()> {
+        if let Ok(YoutubeItem::Video(_)) = self.youtube_options.get_by_index(index) {
+            return self.youtube_options_download(index);
+        }
+        let youtube_options = self.youtube_options.clone();
+        let tx = self.tx_to_main.clone();
+        thread::spawn(move || match youtube_options.open(index) {
+            Ok(youtube_options) => {
+                tx.send(Msg::Download(DLMsg::YoutubeSearchSuccess(youtube_options)))
+                    .ok();
+            }
+            Err(e) => {
+                tx.send(Msg::Download(DLMsg::YoutubeSearchFail(e.to_string())))
+                    .ok();
+            }
+        });
+        Ok(())
+    }
+
+    /// Goes back to the results the playlist or channel was opened from.
+    pub fn youtube_options_back(&mut self) {
+        if let Some(parent) = self.youtube_options.parent.take() {
+            self.youtube_options = *parent;
+            self.sync_youtube_options();
+        }
+    }
+
+    /// Puts the best audio-only format of the video at `index` in up next,
+    /// to play without downloading it.
+    pub fn youtube_options_stream(&mut self, index: usize) {
+        let Ok(YoutubeItem::Video(video)) = self.youtube_options.get_by_index(index) else {
+            return;
+        };
+        let video_id = video.video_id.clone();
+        let instance = self.youtube_options.invidious_instance.clone();
+        let tx = self.tx_to_main.clone();
+        thread::spawn(move || match instance.get_audio_stream(&video_id) {
+            Ok(stream) => {
+                tx.send(Msg::Download(DLMsg::YoutubeStreamReady(stream)))
+                    .ok();
+            }
+            Err(e) => {
+                tx.send(Msg::Download(DLMsg::YoutubeStreamFail(e.to_string())))
+                    .ok();
+            }
+        });
+    }
+
+    pub fn youtube_options_queue_stream(&mut self, stream: &AudioStream) {
+        self.player
+            .playlist
+            .add_track_queue(Track::from_audio_stream(stream));
+        self.playlist_sync();
+        self.show_message_timeout_label_help(
+            format!("Streaming {} next", stream.title),
+            None,
+            None,
+            None,
+        );
+    }
+
     pub fn youtube_options_prev_page(&mut self) {
         match self.youtube_options.prev_page() {
             Ok(_) => self.sync_youtube_options(),
@@ -156,12 +372,28 @@ impl Model {
             if idx > 0 {
                 table.add_row();
             }
-            let duration =
-                Track::duration_formatted_short(&Duration::from_secs(record.length_seconds))
-                    .to_string();
-            let duration_string = format!("[{duration:^10.10}]");
-
-            let title = record.title.as_str();
+            let (duration_string, title) = match record {
+                YoutubeItem::Video(video) => {
+                    let duration =
+                        Track::duration_formatted_short(&Duration::from_secs(video.length_seconds))
+                            .to_string();
+                    (format!("[{duration:^10.10}]"), video.title.clone())
+                }
+                YoutubeItem::Playlist {
+                    title, video_count, ..
+                } => (
+                    format!("[{:^10.10}]", format!("{video_count} videos")),
+                    format!("Playlist: {title}"),
+                ),
+                YoutubeItem::Channel {
+                    author,
+                    video_count,
+                    ..
+                } => (
+                    format!("[{:^10.10}]", format!("{video_count} videos")),
+                    format!("Channel: {author}"),
+                ),
+            };
 
             table
                 .add_col(TextSpan::new(duration_string))
@@ -177,11 +409,23 @@ impl Model {
             .ok();
 
         if let Some(domain) = &self.youtube_options.invidious_instance.domain {
+            let mut hint = format!(
+                "Tab/Shift+Tab switch pages, {} stream to up next",
+                self.config.keys.library_add_queue
+            );
+            if self.youtube_options.parent.is_some() {
+                hint.push_str(", Backspace back");
+            }
+            let page = match self.youtube_options.listing {
+                Listing::Search => format!("Page {}", self.youtube_options.page()),
+                _ => format!(
+                    "{} Page {}",
+                    self.youtube_options.title,
+                    self.youtube_options.page()
+                ),
+            };
             let title = format!(
-                    "\u{2500}\u{2500}\u{2500} Page {} \u{2500}\u{2500}\u{2500}\u{2524} {} \u{251c}\u{2500}\u{2500} {} \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
-                    self.youtube_options.page(),
-                    "Tab/Shift+Tab switch pages",
-                    domain,
+                    "\u{2500}\u{2500}\u{2500} {page} \u{2500}\u{2500}\u{2500}\u{2524} {hint} \u{251c}\u{2500}\u{2500} {domain} \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
                 );
             self.app
                 .attr(
@@ -377,7 +621,7 @@ fn embed_downloaded_lrc(path: &Path, file_fullname: &str) {
 #[allow(clippy::non_ascii_literal)]
 mod tests {
 
-    use crate::ui::model::youtube_options::extract_filepath;
+    use crate::ui::model::youtube_options::{extract_filepath, Listing};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -400,4 +644,17 @@ mod tests {
             "/tmp/观众说“小哥哥，到饭点了”《干饭人之歌》走，端起饭盆干饭去.mp3".to_string()
         );
     }
+
+    #[test]
+    fn test_listing_from_url() {
+        assert!(matches!(
+            Listing::from_url("https://www.youtube.com/playlist?list=PLx-y_z&si=abc"),
+            Some(Listing::Playlist(id)) if id == "PLx-y_z"
+        ));
+        assert!(matches!(
+            Listing::from_url("https://yewtu.be/channel/UCxyz/videos"),
+            Some(Listing::Channel(id, _)) if id == "UCxyz"
+        ));
+        assert!(Listing::from_url("https://www.youtube.com/watch?v=abc&list=PLxyz").is_none());
+    }
 }
@@ -3,9 +3,9 @@ use anyhow::{anyhow, bail, Result};
 use lazy_static::lazy_static;
 use pinyin::ToPinyin;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tuirealm::props::Color;
 use tuirealm::tui::layout::{Constraint, Direction, Layout, Rect};
 use unicode_segmentation::UnicodeSegmentation;
@@ -22,15 +22,21 @@ lazy_static! {
 
 pub struct DownloadTracker {
     items: HashSet<String>,
+    progress: HashMap<String, (u64, Option<u64>)>,
+    /// When progress last asked for the episodes to be redrawn
+    progress_shown: Instant,
     pub time_stamp_for_cache: Instant,
 }
 
 impl Default for DownloadTracker {
     fn default() -> Self {
         let items = HashSet::new();
+        let progress = HashMap::new();
         let time_stamp_for_cache = Instant::now();
         Self {
             items,
+            progress,
+            progress_shown: time_stamp_for_cache,
             time_stamp_for_cache,
         }
     }
@@ -43,6 +49,32 @@ impl DownloadTracker {
 
     pub fn decrease_one(&mut self, url: &str) {
         self.items.remove(url);
+        self.progress.remove(url);
+    }
+
+    /// Records the progress of an item. True when it is time to show it,
+    /// which is at most twice a second.
+    pub fn set_progress(&mut self, url: &str, downloaded: u64, total: Option<u64>) -> bool {
+        if !self.items.contains(url) {
+            return false;
+        }
+        self.progress.insert(url.to_string(), (downloaded, total));
+        if self.progress_shown.elapsed() < Duration::from_millis(500) {
+            return false;
+        }
+        self.progress_shown = Instant::now();
+        true
+    }
+
+    /// Short progress text for an item, percent if the size is known and
+    /// megabytes received otherwise.
+    pub fn progress_str(&self, url: &str) -> Option<String> {
+        match self.progress.get(url)? {
+            (downloaded, Some(total)) if *total > 0 => {
+                Some(format!("{:>3}%", downloaded * 100 / total))
+            }
+            (downloaded, _) => Some(format!("{}MB", downloaded >> 20)),
+        }
     }
 
     pub fn contains(&self, url: &str) -> bool {
@@ -139,6 +171,16 @@ impl DownloadTracker {
         }
     }
 
+    pub fn message_download_cancelled(&self, title: &str) -> String {
+        let len = self.items.len();
+
+        if len > 0 {
+            format!(" 1 item {title:^.10} download cancelled. {len} is still running. ")
+        } else {
+            format!(" {title:^.20} download cancelled.")
+        }
+    }
+
     pub fn message_download_error_file_write(&self, title: &str) -> String {
         let len = self.items.len();
 
@@ -286,9 +328,9 @@ pub fn draw_area_in_absolute(parent: Rect, width: u16, height: u16) -> Rect {
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Length((parent.height - height) / 2),
+                Constraint::Length(parent.height.saturating_sub(height) / 2),
                 Constraint::Length(height),
-                Constraint::Length((parent.height - height) / 2),
+                Constraint::Length(parent.height.saturating_sub(height) / 2),
             ]
             .as_ref(),
         )
@@ -297,9 +339,9 @@ pub fn draw_area_in_absolute(parent: Rect, width: u16, height: u16) -> Rect {
         .direction(Direction::Horizontal)
         .constraints(
             [
-                Constraint::Length((parent.width - width) / 2),
+                Constraint::Length(parent.width.saturating_sub(width) / 2),
                 Constraint::Length(width),
-                Constraint::Length((parent.width - width) / 2),
+                Constraint::Length(parent.width.saturating_sub(width) / 2),
             ]
             .as_ref(),
         )
@@ -313,7 +355,7 @@ pub fn draw_area_top_right_absolute(parent: Rect, width: u16, height: u16) -> Re
             [
                 Constraint::Length(1),
                 Constraint::Length(height),
-                Constraint::Length(parent.height - height - 1),
+                Constraint::Length(parent.height.saturating_sub(height + 1)),
             ]
             .as_ref(),
         )
@@ -322,7 +364,7 @@ pub fn draw_area_top_right_absolute(parent: Rect, width: u16, height: u16) -> Re
         .direction(Direction::Horizontal)
         .constraints(
             [
-                Constraint::Length(parent.width - width - 1),
+                Constraint::Length(parent.width.saturating_sub(width + 1)),
                 Constraint::Length(width),
                 Constraint::Length(1),
             ]
@@ -0,0 +1,159 @@
+//! Amplitude envelopes of whole tracks, drawn by the progress bar.
+//!
+//! An envelope is the peak amplitude of each of a fixed number of equal
+//! slices of the track, scaled so the loudest slice is 255.
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::path::Path;
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::DecoderOptions,
+    errors::Error,
+    formats::FormatOptions,
+    io::{MediaSourceStream, MediaSourceStreamOptions},
+    meta::MetadataOptions,
+    probe::Hint,
+};
+
+/// Slices per envelope, enough for a progress bar across a wide terminal.
+pub const WAVEFORM_POINTS: usize = 400;
+/// Frames per peak taken while decoding, so the resolution doesn't depend on
+/// how large the packets of a format are.
+const BLOCK_FRAMES: usize = 1024;
+/// Level a slice must cross, out of 255, to start or end a section.
+const SECTION_THRESHOLD: u8 = 90;
+
+/// Decodes the whole file and returns its envelope.
+pub fn envelope(path: &Path, points: usize) -> Result<Vec<u8>> {
+    let mss = MediaSourceStream::new(
+        Box::new(File::open(path)?),
+        MediaSourceStreamOptions::default(),
+    );
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(std::ffi::OsStr::to_str) {
+        hint.with_extension(ext);
+    }
+    let mut probed = symphonia::default::get_probe().format(
+        &hint,
+        mss,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let track = probed
+        .format
+        .default_track()
+        .ok_or_else(|| anyhow!("no audio track"))?;
+    let track_id = track.id;
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut peaks = Vec::new();
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+    loop {
+        let packet = match probed.format.next_packet() {
+            Ok(packet) => packet,
+            Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(Error::ResetRequired) => break,
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let audio = match decoder.decode(&packet) {
+            Ok(audio) => audio,
+            Err(Error::DecodeError(_)) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let buffer =
+            buffer.get_or_insert_with(|| SampleBuffer::new(audio.capacity() as u64, *audio.spec()));
+        if buffer.capacity() < audio.capacity() * audio.spec().channels.count() {
+            *buffer = SampleBuffer::new(audio.capacity() as u64, *audio.spec());
+        }
+        let channels = audio.spec().channels.count();
+        buffer.copy_interleaved_ref(audio);
+        peaks.extend(
+            buffer
+                .samples()
+                .chunks(BLOCK_FRAMES * channels)
+                .map(|block| block.iter().fold(0.0, |peak, s| s.abs().max(peak))),
+        );
+    }
+
+    if peaks.is_empty() {
+        return Err(anyhow!("no audio track"));
+    }
+    Ok(downsample(&peaks, points))
+}
+
+/// Merges `peaks` into `points` slices, keeping the loudest of each, and
+/// scales them to 0..=255.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn downsample(peaks: &[f32], points: usize) -> Vec<u8> {
+    if peaks.is_empty() || points == 0 {
+        return Vec::new();
+    }
+    let slices: Vec<f32> = (0..points)
+        .map(|point| {
+            let from = point * peaks.len() / points;
+            let to = ((point + 1) * peaks.len() / points).max(from + 1);
+            peaks[from..to.min(peaks.len())]
+                .iter()
+                .fold(0.0, |peak, p| p.max(peak))
+        })
+        .collect();
+    let loudest = slices.iter().fold(0.0, |peak, p| p.max(peak));
+    if loudest <= 0.0 {
+        return vec![0; points];
+    }
+    slices
+        .iter()
+        .map(|slice| (slice / loudest * 255.0).round() as u8)
+        .collect()
+}
+
+/// Where the track turns loud or quiet, as fractions of its length: the end
+/// of a quiet intro, a break, a drop.
+#[allow(clippy::cast_precision_loss)]
+pub fn sections(envelope: &[u8]) -> Vec<f64> {
+    // the median of each slice and its neighbours, so a single quiet slice
+    // doesn't split a section
+    let smoothed: Vec<u8> = (0..envelope.len())
+        .map(|i| {
+            if i == 0 || i + 1 == envelope.len() {
+                return envelope[i];
+            }
+            let mut window = [envelope[i - 1], envelope[i], envelope[i + 1]];
+            window.sort_unstable();
+            window[1]
+        })
+        .collect();
+    smoothed
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| (pair[0] >= SECTION_THRESHOLD) != (pair[1] >= SECTION_THRESHOLD))
+        .map(|(i, _)| (i + 1) as f64 / envelope.len() as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_downsample_and_sections() {
+        let mut peaks = vec![0.05; 20];
+        peaks.extend(vec![0.5; 40]);
+        peaks.extend(vec![0.1; 20]);
+        peaks.extend(vec![0.4; 20]);
+        let envelope = downsample(&peaks, 10);
+        assert_eq!(envelope, vec![26, 26, 255, 255, 255, 255, 51, 51, 204, 204]);
+        assert_eq!(sections(&envelope), vec![0.2, 0.6, 0.8]);
+        assert!(sections(&[]).is_empty());
+        assert!(downsample(&[], 10).is_empty());
+    }
+}